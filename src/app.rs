@@ -1,8 +1,34 @@
-use crate::config::{ClickModifier, Config, PauseMode};
-use crate::feeds::{self, Headline};
-use crate::ticker::Ticker;
-use crate::ui::{HyperlinkRenderer, StatusBar, TickerWidget};
-use anyhow::Result;
+use crate::archive::{self, Archive};
+use crate::cache::{DisabledFeeds, SavedHeadlines, SeenTitles, SharedPosition, SharedRotation};
+use crate::clipboard;
+use crate::config::{
+    example_config, get_data_dir, ClickAction, ClickModifier, Config, LayoutMode, MarkRead,
+    OfflineMode, PauseMode, QuietDisplay, SortMode,
+};
+use crate::dates::DateStyle;
+use crate::export;
+use crate::feeds::{self, FeedHealth, FeedStatus, Headline};
+use crate::http::HttpClient;
+use crate::logos::{ImageProtocol, Logos, LOGO_WIDTH};
+use crate::memory::{self, Usage};
+use crate::overlay::{
+    ContextMenu, ContextMenuWidget, FeedList, FeedListWidget, FeedManager, FeedManagerWidget,
+    HistoryList, HistoryWidget, Overlay, Panel, PanelWidget, Prompt, PromptKind,
+};
+use crate::platform;
+use crate::replay::Replay;
+use crate::rotation;
+use crate::source::{self, Fetched, HeadlineSource};
+use crate::stats::Stats;
+use crate::systemd::Notifier;
+use crate::ticker::{headline_key, Exclusion, Ticker};
+use crate::ui::{
+    crawl_span, single_headline_text, wrap, HyperlinkRenderer, LowerThirdWidget,
+    StaticHeadlineWidget, StatusBar, TakeoverWidget, TickerWidget, LOWER_THIRD_HEIGHT,
+    MIN_CRAWL_WIDTH,
+};
+use crate::urls::{FeedEntry, UrlsFile};
+use anyhow::{Context, Result};
 use crossterm::event::{
     self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyModifiers,
     MouseEventKind,
@@ -11,114 +37,875 @@ use crossterm::terminal::{
     self, DisableLineWrap, EnableLineWrap, EnterAlternateScreen, LeaveAlternateScreen,
 };
 use crossterm::{execute, cursor};
+use rand::Rng;
 use ratatui::backend::CrosstermBackend;
-use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::layout::{Constraint, Direction, Layout, Position, Rect};
+use ratatui::style::{Color, Style, Stylize};
+use ratatui::text::Line;
+use ratatui::widgets::Clear;
 use ratatui::Terminal;
-use std::io::{self, Stdout};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::io::{self, IsTerminal, Stdout, Write};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
+use tokio::task::JoinHandle;
+
+/// How often aged-out headlines are swept from the live rotation
+const EXPIRY_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Most often read marks are sent to an aggregator backend
+const READ_PUSH_INTERVAL: Duration = Duration::from_secs(15);
+
+/// How often a leader publishes, and followers pick up, the shared crawl
+const SYNC_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Age at which a leader's published position counts as abandoned
+const LEADER_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Frame interval while the terminal is in the background
+const BACKGROUND_FRAME_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Save the terminal title on the terminal's title stack, and restore it (xterm)
+const PUSH_TITLE: &str = "\x1b[22;0t";
+const POP_TITLE: &str = "\x1b[23;0t";
+
+/// How soon feeds are tried again while every one is failing, so the rotation catches up
+/// as soon as the network is back rather than at the next regular refresh
+const OFFLINE_RETRY: Duration = Duration::from_secs(30);
+
+/// How long transient notices stay in the status bar
+const FLASH_DURATION: Duration = Duration::from_secs(5);
+
+/// What a background refresh produced: the feeds list, when it's kept on a server and was
+/// fetched again, and each source's key and what it fetched
+type Refresh = (Option<Result<Vec<FeedEntry>>>, Vec<(String, Fetched)>);
 
 pub struct App {
     config: Config,
     ticker: Arc<RwLock<Ticker>>,
-    client: reqwest::Client,
-    feed_urls: Vec<String>,
+    client: Arc<HttpClient>,
+    /// Feeds from the urls file, in file order
+    feeds: Vec<FeedEntry>,
+    /// Outcome of the most recent fetch of each feed, keyed by URL
+    feed_health: HashMap<String, FeedHealth>,
+    /// Headlines from the most recent fetch of each feed, keyed by URL
+    feed_headlines: HashMap<String, Vec<Headline>>,
+    /// Refresh running in the background
+    pending_fetch: Option<JoinHandle<Refresh>>,
+    /// Feeds switched off from the feed list: neither fetched nor shown
+    disabled_feeds: DisabledFeeds,
+    /// Recently seen titles, for dropping edited reposts under new URLs
+    seen_titles: SeenTitles,
+    /// Backend entry ids of the headlines from the last backend fetch, by headline key
+    backend_ids: HashMap<String, String>,
+    /// Backend entry ids waiting to be marked read on the server
+    pending_read: BTreeSet<String>,
+    last_read_push: Instant,
+    archive: Option<Archive>,
+    /// Archive replay driving the ticker instead of live feeds
+    replay: Option<Replay>,
+    /// Last time the crawl was published (leading) or picked up (following)
+    last_sync: Instant,
+    /// Generation of the rotation last published or mirrored
+    sync_generation: u64,
+    /// Whether the followed leader is paused
+    leader_paused: bool,
+    /// Whether it's quiet hours, when feeds aren't fetched
+    quiet: bool,
+    /// Which `[[schedule]]` windows were open when last checked
+    schedules_open: Vec<bool>,
+    /// Do not disturb: the ticker hidden behind a placeholder, notifications held back
+    dnd: bool,
     running: bool,
+    /// When every feed started failing, while they all still are
+    offline_since: Option<chrono::DateTime<chrono::Utc>>,
     status_message: Option<String>,
+    /// Transient notice shown in the status bar until it expires
+    flash: Option<(String, Instant)>,
+    /// Terminal title last set (terminal_title), once the original has been saved
+    title: Option<String>,
+    /// Keys of headlines already notified about (None until the first fetch, which
+    /// notifies nothing, like NEW badges)
+    notified: Option<HashSet<String>>,
+    /// Keys of priority-feed headlines already in rotation (None until the first fetch,
+    /// whose headlines aren't breaking)
+    breaking_seen: Option<HashSet<String>>,
+    /// URL (and the headline it belongs to) awaiting y/N confirmation before it is opened
+    pending_open: Option<(String, Headline)>,
+    /// Popup capturing input (context menu, stats, history)
+    overlay: Option<Overlay>,
+    /// Counters for this session
+    session_stats: Stats,
+    /// Counters from previous sessions (None if the stats file couldn't be read)
+    lifetime_stats: Option<Stats>,
     mouse_x: Option<u16>,
     mouse_y: Option<u16>,
     terminal_focused: bool,
     last_refresh: Instant,
+    /// Time from last_refresh to the next automatic refresh: the refresh interval, give or
+    /// take the jitter
+    refresh_due: Duration,
+    last_expiry_check: Instant,
+    last_render: Instant,
+    /// File to open in $EDITOR once the current event is handled (it needs the terminal)
+    pending_edit: Option<PathBuf>,
+    /// Readiness and watchdog pings for systemd
+    notifier: Notifier,
+    /// Whether there's a terminal to read keys and mouse events from
+    input: bool,
+    /// Whether the terminal renders OSC 8 links
+    hyperlinks: bool,
+    /// Source logos for the lower-third bug, when the terminal draws images
+    logos: Logos,
     ticker_row: u16,
+    /// Rows the ticker takes from ticker_row (more than one when a headline wraps)
+    ticker_height: u16,
+    /// Where the refresh countdown is drawn in the status bar, if it is
+    refresh_field: Option<Rect>,
+    /// Row of the status bar, while it's shown (the wheel over it changes speed)
+    status_row: Option<u16>,
+    /// Headline held on a static line above the crawl
+    pinned: Option<Headline>,
+    /// Row of the pinned line, while something is pinned
+    pinned_row: Option<u16>,
+    /// Row of the latest-headline line in broadcast layout
+    latest_row: Option<u16>,
+    /// First column and width of the crawl on the ticker row
+    crawl_x: u16,
+    crawl_width: u16,
 }
 
 impl App {
-    pub async fn new(config: Config) -> Result<Self> {
-        let client = feeds::create_http_client()?;
-        let feed_urls = feeds::parse_feeds_file(&config.feeds_path).await?;
+    pub async fn new(config: Config, client: Arc<HttpClient>) -> Result<Self> {
+        let replay = match &config.replay {
+            Some(path) => Some(Replay::open(path, config.speed_factor)?),
+            None => None,
+        };
+        let feeds = if replay.is_some() || config.follow.is_some() {
+            Vec::new()
+        } else {
+            load_feeds(&config, &client).await?
+        };
+        // Headlines saved by the last session, shown until the first fetch completes
+        let feed_headlines = if replay.is_some() || config.follow.is_some() {
+            HashMap::new()
+        } else {
+            let sources = source::configured(&config, &feeds);
+            let mut saved = SavedHeadlines::load(config.max_age).feeds;
+            saved.retain(|key, _| sources.iter().any(|source| source.key() == key));
+            saved
+        };
         let ticker = Arc::new(RwLock::new(Ticker::new(&config)));
+        let disabled_feeds = if config.remember_disabled_feeds {
+            DisabledFeeds::load()
+        } else {
+            DisabledFeeds::default()
+        };
+        let archive = match &config.archive_path {
+            Some(path) => Some(Archive::open(path.clone())?),
+            None => None,
+        };
+        let refresh_due = config.refresh_interval;
+        let seen_titles = SeenTitles::load(config.duplicate_window);
 
         Ok(Self {
             config,
             ticker,
             client,
-            feed_urls,
+            feeds,
+            feed_health: HashMap::new(),
+            feed_headlines,
+            pending_fetch: None,
+            disabled_feeds,
+            seen_titles,
+            backend_ids: HashMap::new(),
+            pending_read: BTreeSet::new(),
+            last_read_push: Instant::now(),
+            archive,
+            replay,
+            last_sync: Instant::now(),
+            sync_generation: 0,
+            leader_paused: false,
+            quiet: false,
+            schedules_open: Vec::new(),
+            dnd: false,
             running: true,
+            offline_since: None,
             status_message: None,
+            flash: None,
+            title: None,
+            notified: None,
+            breaking_seen: None,
+            pending_open: None,
+            overlay: None,
+            session_stats: Stats::new(),
+            lifetime_stats: Stats::load().ok(),
             mouse_x: None,
             mouse_y: None,
             terminal_focused: true,
             last_refresh: Instant::now(),
+            refresh_due,
+            last_expiry_check: Instant::now(),
+            last_render: Instant::now(),
+            pending_edit: None,
+            notifier: Notifier::from_env(),
+            input: true,
+            hyperlinks: !platform::is_conhost(&|name| std::env::var(name).ok()),
+            logos: Logos::new(ImageProtocol::detect(&|name| std::env::var(name).ok())),
             ticker_row: 0,
+            ticker_height: 1,
+            refresh_field: None,
+            status_row: None,
+            pinned: None,
+            pinned_row: None,
+            latest_row: None,
+            crawl_x: 0,
+            crawl_width: 0,
         })
     }
 
-    /// Fetch all feeds and update ticker
-    pub async fn refresh_feeds(&mut self) -> Result<()> {
-        let mut all_headlines: Vec<Headline> = Vec::new();
+    /// Start fetching all feeds in the background (restarting a fetch already running);
+    /// `collect_fetch` merges the results into the rotation once every feed has answered.
+    /// `stagger` spreads the fetches over the refresh jitter, for scheduled refreshes
+    pub async fn refresh_feeds(&mut self, stagger: bool) -> Result<()> {
+        if self.replay.is_some() {
+            self.advance_replay().await;
+            return Ok(());
+        }
+        if self.config.follow.is_some() {
+            self.follow_leader().await;
+            return Ok(());
+        }
 
-        for url in &self.feed_urls {
-            match feeds::fetch_feed(
-                &self.client,
-                url,
-                self.config.max_per_feed,
-                self.config.max_age,
-            )
-            .await
-            {
-                Ok((_source, mut headlines)) => {
-                    all_headlines.append(&mut headlines);
-                }
-                Err(e) => {
-                    eprintln!("Error fetching {}: {}", url, e);
+        if let Some(fetch) = self.pending_fetch.take() {
+            fetch.abort();
+        }
+        let sources: Vec<_> = self
+            .sources()
+            .into_iter()
+            .filter(|source| !self.disabled_feeds.urls.contains(source.key()))
+            // Servers that asked for a pause (Retry-After, Cache-Control) keep their headlines
+            .filter(|source| self.client.wait_before(source.key()).is_none())
+            .collect();
+        // Pauses between fetches, together at most the jitter
+        let jitter = self.config.refresh_jitter;
+        let gap = jitter.as_secs_f64() / sources.len().max(1) as f64;
+        let mut rng = rand::rng();
+        let delays: Vec<Duration> = (0..sources.len())
+            .map(|i| {
+                if stagger && i > 0 && gap > 0.0 {
+                    Duration::from_secs_f64(rng.random_range(0.0..gap))
+                } else {
+                    Duration::ZERO
                 }
+            })
+            .collect();
+        let client = Arc::clone(&self.client);
+        let config = self.config.clone();
+        self.pending_fetch = Some(tokio::spawn(async move {
+            // A list kept on a server may have changed: the new one is polled from the next
+            // refresh on
+            let listed = if config.remote_feeds() && config.backend.is_none() {
+                Some(load_feeds(&config, &client).await)
+            } else {
+                None
+            };
+            let mut results = Vec::new();
+            for (source, delay) in sources.into_iter().zip(delays) {
+                tokio::time::sleep(delay).await;
+                let fetched = source.fetch(&client, &config).await;
+                results.push((source.key().to_string(), fetched));
+            }
+            (listed, results)
+        }));
+        self.last_refresh = Instant::now();
+        self.refresh_due = jittered(self.config.refresh_interval, jitter);
+        Ok(())
+    }
+
+    /// Merge the background fetch into the rotation, once it has finished
+    async fn collect_fetch(&mut self) {
+        if !self.pending_fetch.as_ref().is_some_and(|fetch| fetch.is_finished()) {
+            return;
+        }
+        let Some(fetch) = self.pending_fetch.take() else {
+            return;
+        };
+        let (listed, mut results) = match fetch.await {
+            Ok(fetched) => fetched,
+            Err(e) => {
+                // A panicked or cancelled refresh mustn't leave "Refreshing feeds..." up
+                self.status_message = None;
+                self.flash(format!("Refresh failed: {}", e));
+                return;
+            }
+        };
+        if let Some(listed) = listed {
+            if self.use_feeds(listed) {
+                let urls = self.feed_urls();
+                results.retain(|(key, _)| urls.contains(key));
             }
         }
+        // Every source failing at once is the network (or the machine), not the feeds
+        let last_error = results.iter().find_map(|(_, result)| match &result.status {
+            FeedStatus::Error(e) => Some(e.clone()),
+            FeedStatus::Ok { .. } => None,
+        });
+        let all_failed = !results.is_empty()
+            && results.iter().all(|(_, result)| matches!(result.status, FeedStatus::Error(_)));
+        let cached = (all_failed && self.config.offline == OfflineMode::Cached)
+            .then(|| self.feed_headlines.clone());
 
-        // Apply max_total limit
-        all_headlines.truncate(self.config.max_total);
+        let mut fetched = Vec::new();
+        let mut outdated = Vec::new();
+        for (key, mut result) in results {
+            outdated.push((key.clone(), std::mem::take(&mut result.outdated)));
+            let (headlines, _) = self.record_fetch(&key, result);
+            fetched.extend(headlines);
+        }
+        let disabled = &self.disabled_feeds.urls;
+        let restored = rotation::clock_fallback(&mut self.feed_headlines, &outdated, disabled);
+        let clock_suspect = !restored.is_empty();
+        fetched.extend(restored);
+        if let Some(cached) = cached {
+            self.feed_headlines = cached;
+        }
+        let since = self.offline_since.unwrap_or_else(chrono::Utc::now);
+        let back_online = self.offline_since.is_some() && !all_failed;
+        self.offline_since = all_failed.then_some(since);
+        if all_failed {
+            self.refresh_due = self.refresh_due.min(OFFLINE_RETRY);
+        }
+        let banner = match (self.offline_since, self.config.offline, last_error) {
+            (Some(since), OfflineMode::Banner, Some(error)) => Some(format!(
+                "⚠ OFFLINE since {}: every feed failed. Last error: {}",
+                self.config.dates.format(since, "%H:%M"),
+                error
+            )),
+            _ => None,
+        };
+        let mut ticker = self.ticker.write().await;
+        ticker.set_banner(banner);
+        ticker.set_clock_suspect(clock_suspect);
+        drop(ticker);
+        let added = self.update_rotation(fetched).await;
+        self.status_message = None;
 
+        // While offline, the saved headlines stay those of the last fetch that worked
+        let saved = if all_failed {
+            Ok(())
+        } else {
+            SavedHeadlines {
+                feeds: self.feed_headlines.clone(),
+            }
+            .save()
+        };
+        if let Err(e) = saved {
+            self.flash(format!("Couldn't save headlines: {}", e));
+        } else if clock_suspect {
+            let now = self.config.dates.format(chrono::Utc::now(), "%Y-%m-%d %H:%M");
+            self.flash(format!(
+                "Every headline is older than max_age, showing the newest anyway. Is the clock \
+                 right? It says {}",
+                now
+            ));
+        } else if back_online {
+            let plural = if added == 1 { "" } else { "s" };
+            self.flash(format!("Back online, {} new headline{}", added, plural));
+        } else if added > 0 {
+            self.flash(format!("+{} new", added));
+        }
+
+        let count = self.ticker.read().await.headline_count();
+        let status = match self.offline_since {
+            Some(since) => {
+                format!("Offline since {}", self.config.dates.format(since, "%Y-%m-%d %H:%M"))
+            }
+            None => {
+                let failing = self.feed_health.values().filter(|h| !h.is_ok()).count();
+                format!("{} headlines, {} feeds failing", count, failing)
+            }
+        };
+        self.notifier.status(&status);
+    }
+
+    /// Refetch one feed right away, merging its headlines into the rotation
+    async fn refresh_feed(&mut self, url: &str) {
+        let Some(source) = self.sources().into_iter().find(|s| s.key() == url) else {
+            return;
+        };
+        let (headlines, status) = self.fetch_source(source.as_ref()).await;
+        let added = self.update_rotation(headlines).await;
+        let mut msg = match status {
+            FeedStatus::Ok {
+                title, item_count, ..
+            } => format!("{}: {} headlines", title, item_count),
+            FeedStatus::Error(e) => format!("Refresh failed: {}", e),
+        };
+        if added > 0 {
+            msg.push_str(&format!(" (+{} new)", added));
+        }
+        self.flash(msg);
+    }
+
+    /// Switch a feed off (dropping its headlines) or back on (fetching it if needed)
+    async fn toggle_feed(&mut self, url: &str) {
+        let disabling = !self.disabled_feeds.urls.remove(url);
+        if disabling {
+            self.disabled_feeds.urls.insert(url.to_string());
+            self.update_rotation(Vec::new()).await;
+        } else if self.feed_headlines.contains_key(url) {
+            self.update_rotation(Vec::new()).await;
+        } else {
+            self.refresh_feed(url).await;
+        }
+
+        if self.config.remember_disabled_feeds {
+            if let Err(e) = self.disabled_feeds.save() {
+                self.flash(format!("Couldn't save disabled feeds: {}", e));
+                return;
+            }
+        }
+        let name = match self.feed_health.get(url).map(|h| &h.status) {
+            Some(FeedStatus::Ok { title, .. }) => title.clone(),
+            _ => url.to_string(),
+        };
+        self.flash(format!("{} {}", if disabling { "Disabled" } else { "Enabled" }, name));
+    }
+
+    /// Display name set for a feed in the urls file, if any
+    fn feed_name(&self, url: &str) -> Option<String> {
+        self.feeds
+            .iter()
+            .find(|feed| feed.url == url)
+            .and_then(|feed| feed.name.clone())
+    }
+
+    /// Re-apply a feed's display name (or its own title) to headlines already fetched from it
+    fn relabel_headlines(&mut self, url: &str) {
+        let name = self.feed_name(url).or_else(|| match self.feed_health.get(url) {
+            Some(FeedHealth {
+                status: FeedStatus::Ok { title, .. },
+                ..
+            }) => Some(title.clone()),
+            _ => None,
+        });
+        if let (Some(name), Some(headlines)) = (name, self.feed_headlines.get_mut(url)) {
+            for headline in headlines {
+                headline.source = name.clone();
+            }
+        }
+    }
+
+    fn feed_urls(&self) -> Vec<String> {
+        self.feeds.iter().map(|feed| feed.url.clone()).collect()
+    }
+
+    /// Sources to poll, one per feed (or the backend server standing in for them all)
+    fn sources(&self) -> Vec<Box<dyn HeadlineSource>> {
+        source::configured(&self.config, &self.feeds)
+    }
+
+    /// Fetch a source, recording its health and keeping its headlines for the rotation
+    async fn fetch_source(&mut self, source: &dyn HeadlineSource) -> (Vec<Headline>, FeedStatus) {
+        let fetched = source.fetch(&self.client, &self.config).await;
+        self.record_fetch(source.key(), fetched)
+    }
+
+    /// Record a source's health and keep its headlines for the rotation
+    fn record_fetch(&mut self, key: &str, fetched: Fetched) -> (Vec<Headline>, FeedStatus) {
+        if self.config.backend.is_some() && matches!(fetched.status, FeedStatus::Ok { .. }) {
+            self.backend_ids = fetched.ids;
+        }
+        let previous = self.feed_health.get(key);
+        // Log each kind of warning when it appears, not on every refresh it lasts through
+        let known: Vec<_> = previous
+            .map_or(&[][..], |h| h.warnings.as_slice())
+            .iter()
+            .map(std::mem::discriminant)
+            .collect();
+        let appeared: Vec<_> = fetched
+            .warnings
+            .iter()
+            .filter(|warning| !known.contains(&std::mem::discriminant(*warning)))
+            .cloned()
+            .collect();
+        if !appeared.is_empty() && self.replay.is_none() {
+            let _ = feeds::log_warnings(&feeds::warnings_log_path(), key, &appeared);
+        }
+        let health = FeedHealth::update(previous, fetched.status.clone(), fetched.warnings);
+        self.feed_health.insert(key.to_string(), health);
+        self.feed_headlines.insert(key.to_string(), fetched.headlines.clone());
+        (fetched.headlines, fetched.status)
+    }
+
+    /// Record freshly fetched headlines and rebuild the rotation from every feed's latest
+    /// headlines, returning how many are new
+    async fn update_rotation(&mut self, mut fresh: Vec<Headline>) -> usize {
+        // Drop headlines from blocked domains, regardless of which feed carried them
+        feeds::filter_blocked(&mut fresh, &self.config.blocked_domains);
+        self.session_stats.record_fetched(&fresh);
+        self.fit_memory_budget().await;
+
+        let safe_mode = self.ticker.read().await.safe_mode();
+        self.notify(&fresh, safe_mode);
+
+        // Record everything fetched before the rotation limit is applied
+        let archived = self.archive.as_mut().map(|a| a.append(&fresh));
+        if let Some(Err(e)) = archived {
+            self.flash(format!("Archive error: {}", e));
+        }
+
+        let assembled = rotation::assemble(
+            &self.config,
+            &self.feeds,
+            &self.feed_headlines,
+            &self.disabled_feeds.urls,
+            &mut self.seen_titles,
+        );
+        if assembled.seen_changed {
+            let _ = self.seen_titles.save(); // Ignore errors, the cache is non-critical
+        }
+
+        let breaking = self.breaking_news(&fresh, &assembled.priority, &assembled.headlines);
         let mut ticker = self.ticker.write().await;
-        ticker.set_headlines(all_headlines, self.config.sort);
-        self.last_refresh = Instant::now();
+        ticker.set_source_tags(assembled.source_tags);
+        // In do-not-disturb, priority headlines just join the rotation
+        for headline in breaking.into_iter().filter(|_| !self.dnd) {
+            ticker.break_in(headline);
+        }
+        ticker.set_headlines(assembled.headlines, self.config.sort)
+    }
+
+    /// Headlines from priority feeds entering the rotation for the first time
+    fn breaking_news(
+        &mut self,
+        fresh: &[Headline],
+        priority: &HashSet<String>,
+        rotation: &[Headline],
+    ) -> Vec<Headline> {
+        // Headlines saved by the last session aren't breaking, nor is the first fetch's news
+        if priority.is_empty() || fresh.is_empty() {
+            return Vec::new();
+        }
+        let first = self.breaking_seen.is_none();
+        let seen = self.breaking_seen.get_or_insert_with(HashSet::new);
+        seen.retain(|key| priority.contains(key));
+        rotation
+            .iter()
+            .filter(|h| {
+                let key = headline_key(h);
+                priority.contains(&key) && seen.insert(key) && !first
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Approximate memory taken up by headlines and caches
+    fn memory_usage(&self, ticker: &Ticker) -> Usage {
+        let mut usage = Usage::default();
+        let headlines = self.feed_headlines.values().flatten();
+        let count = self.feed_headlines.values().map(Vec::len).sum();
+        usage.add("feed headlines", memory::headlines_bytes(headlines), count);
+        ticker.memory(&mut usage);
+        let fetched = self.session_stats.fetched_keys();
+        usage.add("fetched keys", memory::keys_bytes(fetched), fetched.len());
+        let (bytes, count) = self.seen_titles.memory();
+        usage.add("title history", bytes, count);
+        usage
+    }
+
+    /// Get back under memory_budget: trim the shown keys, forget fetched keys no feed
+    /// carries any more, then drop the oldest headlines (undated first)
+    async fn fit_memory_budget(&mut self) {
+        let Some(budget) = self.config.memory_budget else {
+            return;
+        };
+        let mut ticker = self.ticker.write().await;
+        if self.memory_usage(&ticker).total() <= budget {
+            return;
+        }
+        ticker.compact_shown();
+        let current: HashSet<String> =
+            self.feed_headlines.values().flatten().map(headline_key).collect();
+        self.session_stats.forget_fetched_except(&current);
+        let mut excess = self.memory_usage(&ticker).total().saturating_sub(budget);
+        drop(ticker);
+
+        let mut oldest: Vec<_> = self
+            .feed_headlines
+            .iter()
+            .flat_map(|(url, headlines)| headlines.iter().map(move |h| (h.published, url)))
+            .map(|(published, url)| (published, url.clone()))
+            .collect();
+        oldest.sort();
+        for (_, url) in oldest {
+            if excess == 0 {
+                break;
+            }
+            let Some(headlines) = self.feed_headlines.get_mut(&url) else {
+                continue;
+            };
+            let Some(index) = (0..headlines.len()).min_by_key(|&i| headlines[i].published) else {
+                continue;
+            };
+            // It goes from the feed's headlines, the pool, and the rotation
+            let freed = memory::headline_bytes(&headlines.remove(index)) * 3;
+            excess = excess.saturating_sub(freed);
+        }
+    }
+
+    /// Release any archived headlines that are due and push them into the ticker
+    async fn advance_replay(&mut self) {
+        let Some(replay) = &mut self.replay else {
+            return;
+        };
+        if !replay.advance() {
+            return;
+        }
+        let headlines = replay.headlines(self.config.max_total);
+        let finished = replay.finished();
+
+        let mut ticker = self.ticker.write().await;
+        ticker.set_headlines(headlines, self.config.sort);
+        drop(ticker);
+
+        if finished {
+            self.flash("Replay finished");
+        }
+    }
+
+    /// Terminal unfocused while the ticker keeps scrolling: draw less often, skip hyperlinks
+    fn in_background(&self) -> bool {
+        !self.terminal_focused && self.config.pause_mode != PauseMode::Focus
+    }
+
+    /// Keep `quiet` in step with the clock, saying when quiet hours start and end
+    fn check_quiet_hours(&mut self) {
+        let now = self.config.dates.wall_clock(chrono::Utc::now()).time();
+        let quiet = self.config.quiet_hours.filter(|hours| hours.contains(now));
+        if quiet.is_some() == self.quiet {
+            return;
+        }
+        self.quiet = quiet.is_some();
+        match quiet {
+            Some(hours) => {
+                self.flash(format!("Quiet hours: no fetching until {}", hours.end.format("%H:%M")))
+            }
+            None => self.flash("Quiet hours over"),
+        }
+    }
+
+    /// Rebuild the rotation when a `[[schedule]]` window opens or closes
+    async fn check_schedules(&mut self) {
+        let now = self.config.dates.wall_clock(chrono::Utc::now());
+        let open: Vec<bool> = self.config.schedules.iter().map(|s| s.open(now)).collect();
+        if open != self.schedules_open {
+            self.schedules_open = open;
+            self.update_rotation(Vec::new()).await;
+        }
+    }
+
+    /// Quiet hours with the screen blanked and the crawl stopped
+    fn blanked(&self) -> bool {
+        self.quiet && self.config.quiet_display == QuietDisplay::Blank
+    }
+
+    /// Switch do-not-disturb on (closing anything open over the ticker) or off
+    fn toggle_dnd(&mut self) {
+        self.dnd = !self.dnd;
+        if self.dnd {
+            self.overlay = None;
+            self.pending_open = None;
+            self.flash = None;
+        } else {
+            self.flash("Do not disturb off");
+        }
+    }
+
+    /// Show a transient notice in the status bar
+    fn flash(&mut self, msg: impl Into<String>) {
+        self.flash = Some((msg.into(), Instant::now()));
+    }
 
+    /// Suspend the TUI to edit a file in $VISUAL/$EDITOR, then reload config and feeds
+    async fn edit_file(
+        &mut self,
+        terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+        path: &Path,
+    ) -> Result<()> {
+        self.restore_terminal(terminal)?;
+        let edited = run_editor(path);
+        enter_tui(terminal.backend_mut(), !self.config.no_tty_check)?;
+        terminal.clear()?;
+        self.logos.forget();
+
+        if let Err(e) = edited {
+            self.flash(format!("{:#}", e));
+            return Ok(());
+        }
+        if let Err(e) = self.reload_config().await {
+            self.flash(format!("{:#}", e));
+            return Ok(());
+        }
+        if self.replay.is_none() && self.reload_feeds().await {
+            self.refresh_feeds(false).await?;
+        }
         Ok(())
     }
 
+    /// Read the feeds files again; false (with the error flashed) if they can't be read
+    async fn reload_feeds(&mut self) -> bool {
+        let loaded = load_feeds(&self.config, &self.client).await;
+        self.use_feeds(loaded)
+    }
+
+    /// Poll the feeds just read, dropping headlines and health of feeds no longer listed
+    fn use_feeds(&mut self, loaded: Result<Vec<FeedEntry>>) -> bool {
+        match loaded {
+            Ok(feeds) => {
+                self.feeds = feeds;
+                let urls = self.feed_urls();
+                self.feed_headlines.retain(|url, _| urls.contains(url));
+                self.feed_health.retain(|url, _| urls.contains(url));
+                true
+            }
+            Err(e) => {
+                self.flash(format!("{:#}", e));
+                false
+            }
+        }
+    }
+
+    /// Queue the config file for editing, starting it from the commented example if missing
+    fn edit_config(&mut self) {
+        let Some(path) = self.config.config_path.clone() else {
+            return;
+        };
+        if !path.exists() {
+            let created = path
+                .parent()
+                .map_or(Ok(()), std::fs::create_dir_all)
+                .and_then(|_| std::fs::write(&path, example_config()));
+            if let Err(e) = created {
+                self.flash(format!("Couldn't create {}: {}", path.display(), e));
+                return;
+            }
+        }
+        self.pending_edit = Some(path);
+    }
+
     /// Reload config from file and apply changes
     async fn reload_config(&mut self) -> Result<()> {
-        if self.config.reload()? {
-            // Apply speed change to ticker
-            let mut ticker = self.ticker.write().await;
+        let Some(changed) = self.config.reload()? else {
+            return Ok(());
+        };
+        let mut ticker = self.ticker.write().await;
+        // Keep a speed set with the keys unless the file's speed changed
+        if changed.iter().any(|key| key == "speed") {
             ticker.set_speed(self.config.speed);
         }
+        // Likewise safe mode toggled with S
+        let safe_mode_changed = changed.iter().any(|key| key == "safe_mode");
+        if safe_mode_changed && ticker.safe_mode() != self.config.safe_mode {
+            ticker.toggle_safe_mode();
+        }
+        // And the sort cycled with o and unread-only toggled with n
+        if !changed.iter().any(|key| key == "sort") {
+            self.config.sort = ticker.sort();
+        }
+        if !changed.iter().any(|key| key == "unread_only") {
+            self.config.unread_only = ticker.unread_only();
+        }
+        ticker.reconfigure(&self.config);
+        drop(ticker);
+        // Blocked domains and max_total apply when the rotation is assembled
+        if self.replay.is_none() && self.config.follow.is_none() {
+            self.update_rotation(Vec::new()).await;
+        }
+
+        let summary = if changed.is_empty() {
+            "Config reloaded, nothing changed".to_string()
+        } else {
+            format!("Config reloaded: {}", changed.join(", "))
+        };
+        match self.config_warnings() {
+            Some(warnings) => self.flash(format!("{} | {}", summary, warnings)),
+            None => self.flash(summary),
+        }
         Ok(())
     }
 
+    /// Config problems that didn't stop it loading, like misspelled keys, in brief
+    fn config_warnings(&self) -> Option<String> {
+        let first = self.config.warnings.first()?;
+        Some(match self.config.warnings.len() {
+            1 => first.clone(),
+            n => format!("{} (+{} more; see chyron doctor)", first, n - 1),
+        })
+    }
+
     /// Main application loop
     pub async fn run(&mut self) -> Result<()> {
-        // Initial feed fetch
-        self.status_message = Some("Loading feeds...".to_string());
-        self.refresh_feeds().await?;
-        self.status_message = None;
+        if !self.config.no_tty_check && !io::stdout().is_terminal() {
+            anyhow::bail!(
+                "stdout is not a terminal. For a status bar use --output; to draw anyway (a \
+                 kiosk console under systemd) pass --no-tty-check"
+            );
+        }
+        if let Some(warnings) = self.config_warnings() {
+            self.flash(warnings);
+        }
+        // Scroll last session's headlines while the first fetch runs
+        if !self.feed_headlines.is_empty() {
+            self.update_rotation(Vec::new()).await;
+        }
+        self.refresh_feeds(false).await?;
+        if self.pending_fetch.is_some() {
+            self.status_message = Some("Loading feeds...".to_string());
+        }
 
         // Setup terminal
         let mut terminal = self.setup_terminal()?;
+        self.notifier.ready();
+        self.check_quiet_hours();
 
         let tick_rate = Duration::from_millis(16); // ~60 FPS
         let mut last_tick = Instant::now();
 
         while self.running {
-            // Handle events
-            if event::poll(Duration::from_millis(1))? {
-                self.handle_event().await?;
+            // Handle events (waiting longer between frames in the background)
+            let poll_timeout = if self.in_background() {
+                Duration::from_millis(20)
+            } else {
+                Duration::from_millis(1)
+            };
+            self.notifier.keep_alive();
+            if !self.input {
+                tokio::time::sleep(poll_timeout).await;
+            } else {
+                match event::poll(poll_timeout) {
+                    Ok(true) => self.handle_event().await?,
+                    Ok(false) => {}
+                    // Headless: nothing to read keys from, so just keep drawing
+                    Err(_) if self.config.no_tty_check => self.input = false,
+                    Err(e) => return Err(e.into()),
+                }
+            }
+            if let Some(path) = self.pending_edit.take() {
+                self.edit_file(&mut terminal, &path).await?;
             }
 
             // Update ticker
             let elapsed = last_tick.elapsed();
             if elapsed >= tick_rate {
                 let delta = elapsed.as_secs_f64();
+                self.check_quiet_hours();
+                if self.replay.is_none() && self.config.follow.is_none() {
+                    self.check_schedules().await;
+                }
                 {
                     let mut ticker = self.ticker.write().await;
 
@@ -126,7 +913,7 @@ impl App {
                     match self.config.pause_mode {
                         PauseMode::Hover => {
                             let mouse_on_ticker = self.terminal_focused
-                                && self.mouse_y.map(|y| y == self.ticker_row).unwrap_or(false);
+                                && self.mouse_y.map(|y| self.on_ticker(y)).unwrap_or(false);
                             if mouse_on_ticker {
                                 ticker.auto_pause();
                             } else {
@@ -145,42 +932,210 @@ impl App {
                             ticker.auto_resume();
                         }
                     }
+                    if self.leader_paused || self.blanked() || self.dnd {
+                        ticker.auto_pause();
+                    }
 
                     ticker.tick(delta);
+                    let shown = ticker.take_shown();
+                    drop(ticker);
+                    for headline in shown {
+                        self.session_stats.record_shown(&headline.source);
+                        if self.config.mark_read == MarkRead::Scrolled {
+                            self.queue_read(&headline);
+                        }
+                    }
                 }
                 last_tick = Instant::now();
 
+                if !self.pending_read.is_empty()
+                    && self.last_read_push.elapsed() >= READ_PUSH_INTERVAL
+                {
+                    self.push_read_state().await;
+                }
+
                 // Check if refresh needed
-                if self.last_refresh.elapsed() >= self.config.refresh_interval {
-                    self.refresh_feeds().await?;
+                if self.replay.is_some() {
+                    self.advance_replay().await;
+                } else if self.config.follow.is_some() {
+                    if self.last_sync.elapsed() >= SYNC_INTERVAL {
+                        self.follow_leader().await;
+                    }
+                } else if self.pending_fetch.is_some() {
+                    self.collect_fetch().await;
+                } else if self.last_refresh.elapsed() >= self.refresh_due && !self.quiet {
+                    self.refresh_feeds(true).await?;
+                }
+                self.update_title().await?;
+                if self.config.lead && self.last_sync.elapsed() >= SYNC_INTERVAL {
+                    self.publish_crawl().await;
+                }
+
+                // Drop headlines that aged out since the last fetch (archived headlines are
+                // old by definition, and a followed leader expires its own)
+                let mirroring = self.replay.is_some() || self.config.follow.is_some();
+                if !mirroring && self.last_expiry_check.elapsed() >= EXPIRY_CHECK_INTERVAL {
+                    let mut ticker = self.ticker.write().await;
+                    ticker.expire_headlines(self.config.max_age);
+                    self.last_expiry_check = Instant::now();
                 }
             }
 
-            // Render
-            self.render(&mut terminal).await?;
+            // Render, at a reduced rate while in the background
+            if !self.in_background() || self.last_render.elapsed() >= BACKGROUND_FRAME_INTERVAL {
+                self.render(&mut terminal).await?;
+                self.last_render = Instant::now();
+            }
         }
 
-        // Save shown headlines cache before exit
-        {
+        self.notifier.stopping();
+        self.push_read_state().await;
+
+        // Save shown headlines cache before exit (replays shouldn't mark anything seen, and
+        // followers would overwrite their leader's position)
+        if self.replay.is_none() && self.config.follow.is_none() {
             let ticker = self.ticker.read().await;
             ticker.save_shown_cache();
+            ticker.save_position();
+            drop(ticker);
+            if let Some(lifetime) = &mut self.lifetime_stats {
+                lifetime.merge(&self.session_stats);
+                let _ = lifetime.save(); // Ignore errors, stats are non-critical
+            }
         }
 
         self.restore_terminal(&mut terminal)?;
         Ok(())
     }
 
+    /// Raise an OSC 9 terminal notification for each new headline matching notify, unless
+    /// safe mode is on
+    fn notify(&mut self, fresh: &[Headline], safe_mode: bool) {
+        // Replayed headlines aren't news, and saved ones were news last session
+        if self.config.notify.is_empty() || self.replay.is_some() || fresh.is_empty() {
+            return;
+        }
+        let first = self.notified.is_none();
+        // Do not disturb and safe mode hold them back
+        let held = self.dnd || safe_mode;
+        let notified = self.notified.get_or_insert_with(HashSet::new);
+        let mut out = io::stdout();
+        for headline in fresh {
+            if !feeds::mentions_any(&headline.title, &self.config.notify)
+                || !notified.insert(headline_key(headline))
+                || first
+                || held
+            {
+                continue;
+            }
+            let text = format!("{}: {}", headline.source, headline.title)
+                .replace(char::is_control, " ");
+            let _ = write!(out, "\x1b]9;{}\x07", text);
+        }
+        let _ = out.flush();
+    }
+
+    /// Keep the terminal title on the headline entering the crawl from the right (the one
+    /// showing, in flip display), saving the original title first and restoring it once
+    /// terminal_title is switched off
+    async fn update_title(&mut self) -> Result<()> {
+        let mut out = io::stdout();
+        // Do not disturb hides the headlines from the title too
+        if !self.config.terminal_title || self.dnd {
+            if self.title.take().is_some() {
+                write!(out, "{}", POP_TITLE)?;
+                out.flush()?;
+            }
+            return Ok(());
+        }
+
+        let ticker = self.ticker.read().await;
+        let width = self.crawl_width as usize;
+        let entering = if ticker.is_flip() || width == 0 {
+            ticker.leading_headline()
+        } else {
+            let segments = ticker.get_visible_segments(width);
+            segments.last().and_then(|s| ticker.headlines().get(s.index))
+        };
+        let Some(headline) = entering else {
+            return Ok(());
+        };
+        let title = ticker.caption(headline).replace(char::is_control, " ");
+        drop(ticker);
+        if self.title.as_ref() == Some(&title) {
+            return Ok(());
+        }
+        if self.title.is_none() {
+            write!(out, "{}", PUSH_TITLE)?;
+        }
+        execute!(out, terminal::SetTitle(&title))?;
+        self.title = Some(title);
+        Ok(())
+    }
+
+    /// Publish the rotation (when it changed) and crawl position for followers
+    async fn publish_crawl(&mut self) {
+        self.last_sync = Instant::now();
+        let ticker = self.ticker.read().await;
+        let generation = SharedRotation::generation(ticker.headlines());
+        if generation != self.sync_generation {
+            let rotation = SharedRotation {
+                headlines: ticker.headlines().to_vec(),
+            };
+            if rotation.save().is_err() {
+                return;
+            }
+            self.sync_generation = generation;
+        }
+        let shared = SharedPosition {
+            generation,
+            position: ticker.crawl_position(),
+            speed: ticker.speed(),
+            paused: ticker.is_paused(),
+            at: chrono::Utc::now(),
+        };
+        let _ = shared.save(); // Followers just hold still until the next one
+    }
+
+    /// Mirror the rotation and crawl position a --lead instance publishes
+    async fn follow_leader(&mut self) {
+        self.last_sync = Instant::now();
+        let Some(shift) = self.config.follow else {
+            return;
+        };
+        let shared = SharedPosition::load().filter(|shared| {
+            let age = (chrono::Utc::now() - shared.at).to_std().unwrap_or_default();
+            age < LEADER_TIMEOUT
+        });
+        let Some(shared) = shared else {
+            self.leader_paused = false;
+            self.status_message = Some("Waiting for a chyron --lead instance".to_string());
+            return;
+        };
+        self.status_message = None;
+
+        let mut ticker = self.ticker.write().await;
+        if shared.generation != self.sync_generation {
+            let Some(rotation) = SharedRotation::load() else {
+                return;
+            };
+            ticker.mirror(rotation.headlines);
+            self.sync_generation = shared.generation;
+        }
+        ticker.set_speed(shared.speed);
+        self.leader_paused = shared.paused;
+        let ahead = if shared.paused || ticker.is_flip() {
+            0.0
+        } else {
+            let elapsed = (chrono::Utc::now() - shared.at).to_std().unwrap_or_default();
+            elapsed.as_secs_f64() * shared.speed as f64
+        };
+        ticker.follow(&shared.position, shift, ahead);
+    }
+
     fn setup_terminal(&self) -> Result<Terminal<CrosstermBackend<Stdout>>> {
-        terminal::enable_raw_mode()?;
         let mut stdout = io::stdout();
-        execute!(
-            stdout,
-            EnterAlternateScreen,
-            EnableMouseCapture,
-            DisableLineWrap,
-            event::EnableFocusChange,
-            cursor::Hide
-        )?;
+        enter_tui(&mut stdout, !self.config.no_tty_check)?;
         let backend = CrosstermBackend::new(stdout);
         let terminal = Terminal::new(backend)?;
         Ok(terminal)
@@ -190,7 +1145,14 @@ impl App {
         &self,
         terminal: &mut Terminal<CrosstermBackend<Stdout>>,
     ) -> Result<()> {
-        terminal::disable_raw_mode()?;
+        if self.title.is_some() {
+            write!(terminal.backend_mut(), "{}", POP_TITLE)?;
+        }
+        if let Err(e) = terminal::disable_raw_mode() {
+            if !self.config.no_tty_check {
+                return Err(e.into());
+            }
+        }
         execute!(
             terminal.backend_mut(),
             LeaveAlternateScreen,
@@ -220,7 +1182,8 @@ impl App {
                 self.mouse_y = None;
             }
             Event::Resize(_, _) => {
-                // Terminal will handle redraw
+                // Terminal will handle redraw, clearing the screen (and the logo)
+                self.logos.forget();
             }
             _ => {}
         }
@@ -228,6 +1191,36 @@ impl App {
     }
 
     async fn handle_key(&mut self, code: KeyCode, modifiers: KeyModifiers) -> Result<()> {
+        // Do not disturb only listens for its own key and quitting
+        if self.dnd {
+            match code {
+                KeyCode::Char('b') => self.toggle_dnd(),
+                KeyCode::Char('q') | KeyCode::Esc => self.running = false,
+                KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => {
+                    self.running = false;
+                }
+                _ => {}
+            }
+            return Ok(());
+        }
+        if self.overlay.is_some() {
+            if code == KeyCode::Char('c') && modifiers.contains(KeyModifiers::CONTROL) {
+                self.running = false;
+                return Ok(());
+            }
+            return self.handle_overlay_key(code).await;
+        }
+
+        // An open confirmation swallows the next key
+        if let Some((url, headline)) = self.pending_open.take() {
+            if matches!(code, KeyCode::Char('y') | KeyCode::Char('Y')) {
+                self.launch_url(&url, &headline)?;
+            } else {
+                self.flash("Open cancelled");
+            }
+            return Ok(());
+        }
+
         match code {
             KeyCode::Char('q') | KeyCode::Esc => {
                 self.running = false;
@@ -239,20 +1232,141 @@ impl App {
                 let mut ticker = self.ticker.write().await;
                 ticker.toggle_pause();
             }
-            KeyCode::Char('+') | KeyCode::Char('=') => {
+            KeyCode::Char('+') | KeyCode::Char('=') => self.step_speed(true).await,
+            KeyCode::Char('-') | KeyCode::Char('_') => self.step_speed(false).await,
+            KeyCode::Char(c @ '1'..='9') => {
+                let number = c as usize - '0' as usize;
+                if let Some(&speed) = self.config.speed_presets.get(number - 1) {
+                    let speed = speed.clamp(1, 100);
+                    self.ticker.write().await.set_speed(speed);
+                    self.flash(format!("Speed preset {}: {}", number, speed));
+                }
+            }
+            KeyCode::Char('r') => {
+                self.refresh_now().await?;
+            }
+            KeyCode::Char('o') => {
                 let mut ticker = self.ticker.write().await;
-                let speed = ticker.speed();
-                ticker.set_speed(speed.saturating_add(2).min(100));
+                let sort = ticker.sort().next();
+                ticker.set_sort(sort);
+                drop(ticker);
+                // Refreshes sort by the config, so keep the choice there
+                self.config.sort = sort;
+                self.flash(format!("Sort: {}", sort.name()));
             }
-            KeyCode::Char('-') | KeyCode::Char('_') => {
+            KeyCode::Char('R') => {
                 let mut ticker = self.ticker.write().await;
-                let speed = ticker.speed();
-                ticker.set_speed(speed.saturating_sub(2).max(1));
+                if ticker.sort() == SortMode::Random {
+                    ticker.reshuffle();
+                    drop(ticker);
+                    self.flash("Reshuffled");
+                } else {
+                    drop(ticker);
+                    self.flash("Reshuffling needs sort = random (o cycles the sort)");
+                }
             }
-            KeyCode::Char('r') => {
-                self.status_message = Some("Refreshing feeds...".to_string());
-                self.refresh_feeds().await?;
-                self.status_message = None;
+            KeyCode::Char('n') => {
+                let mut ticker = self.ticker.write().await;
+                let unread_only = ticker.toggle_unread_only();
+                drop(ticker);
+                self.flash(if unread_only { "Unread only: on" } else { "Unread only: off" });
+            }
+            KeyCode::Char('t') => {
+                let relative = self.ticker.write().await.toggle_relative_times();
+                self.flash(match relative {
+                    Some(true) => "Times: relative",
+                    Some(false) => "Times: absolute",
+                    None => "No times shown (set date_format or use {age} in the template)",
+                });
+            }
+            KeyCode::Char('h') => {
+                self.open_hovered_source().await?;
+            }
+            KeyCode::Char('x') | KeyCode::Char('m') => {
+                let action = if code == KeyCode::Char('x') {
+                    ClickAction::Skip
+                } else {
+                    ClickAction::Mute
+                };
+                match self.hovered_headline().await? {
+                    Some(headline) => self.headline_action(action, headline).await?,
+                    None => self.flash("Hover a headline first"),
+                }
+            }
+            KeyCode::Char('p') => {
+                // Pin the hovered headline, or unpin when hovering the pin or nothing
+                let hovered = match self.mouse_y {
+                    Some(y) if Some(y) == self.pinned_row => self.pinned.clone(),
+                    _ => self.hovered_headline().await?,
+                };
+                match hovered.or_else(|| self.pinned.clone()) {
+                    Some(headline) => self.headline_action(ClickAction::Pin, headline).await?,
+                    None => self.flash("Hover a headline to pin it"),
+                }
+            }
+            KeyCode::Char('b') => self.toggle_dnd(),
+            KeyCode::Char('S') => {
+                let safe_mode = self.ticker.write().await.toggle_safe_mode();
+                self.flash(if safe_mode { "Safe mode: titles only" } else { "Safe mode off" });
+            }
+            KeyCode::Char('z') => {
+                // The hovered headline, else the one in the middle of the crawl
+                let middle = (self.crawl_x + self.crawl_width / 2) as usize;
+                let headline = match self.hovered_headline().await? {
+                    Some(headline) => Some(headline),
+                    None => self.headline_at(middle).await?,
+                };
+                if let Some(headline) = headline {
+                    self.headline_action(ClickAction::Center, headline).await?;
+                }
+            }
+            KeyCode::Char('u') => {
+                let undone = self.ticker.write().await.undo_exclusion();
+                match undone {
+                    Some(Exclusion::Skipped { title, .. }) => {
+                        self.flash(format!("Restored: {}", title))
+                    }
+                    Some(Exclusion::Muted(source)) => self.flash(format!("Unmuted {}", source)),
+                    None => self.flash("Nothing to undo"),
+                }
+            }
+            KeyCode::Char('s') => {
+                let ticker = self.ticker.read().await;
+                let panel = self.stats_panel(&ticker);
+                drop(ticker);
+                self.overlay = Some(Overlay::Panel(panel));
+            }
+            KeyCode::Char('g') => {
+                let ticker = self.ticker.read().await;
+                let panel = stories_panel(&ticker);
+                drop(ticker);
+                match panel {
+                    Some(panel) => self.overlay = Some(Overlay::Panel(panel)),
+                    None if self.config.group_stories => self.flash("No stories to group"),
+                    None => self.flash("Story grouping is off (group_stories)"),
+                }
+            }
+            KeyCode::Char('F') => self.open_feed_manager(),
+            KeyCode::Char('E') => self.edit_config(),
+            KeyCode::Char('U') if self.replay.is_none() && self.config.backend.is_none() => {
+                match self.config.feeds_file() {
+                    Some(path) => self.pending_edit = Some(path),
+                    None => self.flash(format!("No feeds file in {}", self.config.feeds_label())),
+                }
+            }
+            KeyCode::Char('f') => {
+                self.overlay = Some(Overlay::Feeds(FeedList::new(self.feed_urls())));
+            }
+            KeyCode::Char('H') => match archive::read_history(&archive::history_path(), 50) {
+                Ok(entries) => self.overlay = Some(Overlay::History(HistoryList::new(entries))),
+                Err(e) => self.flash(format!("History error: {}", e)),
+            },
+            KeyCode::Char('e') => {
+                let result = self.export_rotation().await;
+                match result {
+                    Ok(msg) => self.flash(msg),
+                    Err(e) => self.flash(format!("Export failed: {}", e)),
+                }
             }
             KeyCode::Char('c') => {
                 self.status_message = Some("Reloading config...".to_string());
@@ -264,13 +1378,52 @@ impl App {
         Ok(())
     }
 
+    /// Speed up or slow down by one step, for ± and the wheel over the status bar
+    async fn step_speed(&self, up: bool) {
+        let mut ticker = self.ticker.write().await;
+        let speed = ticker.speed();
+        if up {
+            ticker.set_speed(speed.saturating_add(2).min(100));
+        } else {
+            ticker.set_speed(speed.saturating_sub(2).max(1));
+        }
+    }
+
     async fn handle_mouse(&mut self, mouse: event::MouseEvent) -> Result<()> {
+        if self.dnd {
+            return Ok(());
+        }
+        if self.overlay.is_some() {
+            return self.handle_overlay_mouse(mouse).await;
+        }
+
         match mouse.kind {
             MouseEventKind::Moved => {
                 self.mouse_x = Some(mouse.column);
                 self.mouse_y = Some(mouse.row);
             }
-            MouseEventKind::Down(event::MouseButton::Left) => {
+            MouseEventKind::Down(event::MouseButton::Left)
+                if self
+                    .refresh_field
+                    .is_some_and(|field| field.contains(Position::new(mouse.column, mouse.row))) =>
+            {
+                self.refresh_now().await?;
+            }
+            MouseEventKind::ScrollUp | MouseEventKind::ScrollDown
+                if self.status_row == Some(mouse.row) =>
+            {
+                self.step_speed(mouse.kind == MouseEventKind::ScrollUp).await;
+            }
+            MouseEventKind::Down(event::MouseButton::Left)
+                if mouse.modifiers.contains(KeyModifiers::SHIFT)
+                    && self.config.click_modifier != ClickModifier::Shift =>
+            {
+                // Shift-click opens the publication's homepage instead of the article
+                self.mouse_x = Some(mouse.column);
+                self.mouse_y = Some(mouse.row);
+                self.open_hovered_source().await?;
+            }
+            MouseEventKind::Down(button) => {
                 // Check if required modifier is held
                 let modifier_ok = match self.config.click_modifier {
                     ClickModifier::None => true,
@@ -279,35 +1432,613 @@ impl App {
                     ClickModifier::Alt => mouse.modifiers.contains(KeyModifiers::ALT),
                 };
 
+                let action = match button {
+                    event::MouseButton::Left => self.config.click_action,
+                    event::MouseButton::Right => self.config.right_click_action,
+                    event::MouseButton::Middle => self.config.middle_click_action,
+                };
+
                 if modifier_ok {
-                    // Check for click on hyperlink
-                    let ticker = self.ticker.read().await;
-                    let term_width = terminal::size()?.0 as usize;
-                    if let Some(url) = ticker.get_url_at_position(mouse.column as usize, term_width) {
-                        drop(ticker);
-                        self.open_url(&url)?;
+                    self.perform_click_action(action, mouse.column, mouse.row).await?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Open the feed manager on the urls file
+    fn open_feed_manager(&mut self) {
+        if self.replay.is_some() {
+            self.flash("No feeds to manage while replaying");
+            return;
+        }
+        if let Some(backend) = &self.config.backend {
+            self.flash(format!("Feeds are managed on the {} server", backend.name()));
+            return;
+        }
+        let Some(path) = self.config.feeds_file() else {
+            self.flash(format!("No feeds file in {}", self.config.feeds_label()));
+            return;
+        };
+        match UrlsFile::load(&path) {
+            Ok(file) => self.overlay = Some(Overlay::Manager(FeedManager::new(file))),
+            Err(e) => self.flash(format!("{:#}", e)),
+        }
+    }
+
+    /// Handle a key in the feed manager, which is taken out of the overlay meanwhile
+    async fn handle_manager_key(&mut self, mut manager: FeedManager, code: KeyCode) -> Result<()> {
+        if let Some(kind) = manager.prompt.as_ref().map(|p| p.kind) {
+            match code {
+                KeyCode::Esc => manager.prompt = None,
+                KeyCode::Char(c) if kind == PromptKind::Remove => {
+                    manager.prompt = None;
+                    if c == 'y' || c == 'Y' {
+                        self.remove_feed(&mut manager).await;
+                    }
+                }
+                KeyCode::Enter => {
+                    if let Some(prompt) = manager.prompt.take() {
+                        self.apply_prompt(&mut manager, prompt).await;
+                    }
+                }
+                KeyCode::Backspace => {
+                    if let Some(prompt) = &mut manager.prompt {
+                        prompt.input.pop();
+                    }
+                }
+                KeyCode::Char(c) => {
+                    if let Some(prompt) = &mut manager.prompt {
+                        prompt.input.push(c);
+                    }
+                }
+                _ => {}
+            }
+            self.overlay = Some(Overlay::Manager(manager));
+            return Ok(());
+        }
+
+        let selected = manager.selected_entry().map(|entry| entry.url);
+        match code {
+            KeyCode::Up | KeyCode::Char('k') => manager.select_prev(),
+            KeyCode::Down | KeyCode::Char('j') | KeyCode::Tab => manager.select_next(),
+            KeyCode::Char('a') => manager.prompt(PromptKind::Add),
+            KeyCode::Char('n') => manager.prompt(PromptKind::Rename),
+            KeyCode::Char('t') => manager.prompt(PromptKind::Tags),
+            KeyCode::Char('d') | KeyCode::Delete => manager.prompt(PromptKind::Remove),
+            KeyCode::Char('K') | KeyCode::Char('J') => {
+                let up = code == KeyCode::Char('K');
+                if manager.file.move_entry(manager.selected, up) {
+                    manager.selected = if up { manager.selected - 1 } else { manager.selected + 1 };
+                    if self.save_feeds(&manager) {
+                        self.update_rotation(Vec::new()).await;
                     }
                 }
             }
+            KeyCode::Char('r') => {
+                if let Some(url) = selected {
+                    self.refresh_feed(&url).await;
+                }
+            }
+            KeyCode::Char(' ') => {
+                if let Some(url) = selected {
+                    self.toggle_feed(&url).await;
+                }
+            }
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('F') => return Ok(()),
             _ => {}
         }
+        self.overlay = Some(Overlay::Manager(manager));
         Ok(())
     }
 
-    fn open_url(&self, url: &str) -> Result<()> {
-        #[cfg(target_os = "macos")]
-        {
-            std::process::Command::new("open").arg(url).spawn()?;
+    /// Act on a submitted feed manager prompt
+    async fn apply_prompt(&mut self, manager: &mut FeedManager, prompt: Prompt) {
+        let input = prompt.input.trim().to_string();
+        let selected = manager.selected;
+        match prompt.kind {
+            PromptKind::Add if !input.is_empty() => self.add_feed(manager, &input).await,
+            PromptKind::Rename => {
+                let Some(entry) = manager.file.entry_mut(selected) else {
+                    return;
+                };
+                entry.name = (!input.is_empty()).then_some(input);
+                let url = entry.url.clone();
+                if self.save_feeds(manager) {
+                    self.relabel_headlines(&url);
+                    self.update_rotation(Vec::new()).await;
+                }
+            }
+            PromptKind::Tags => {
+                let Some(entry) = manager.file.entry_mut(selected) else {
+                    return;
+                };
+                entry.tags = input
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|tag| !tag.is_empty())
+                    .map(str::to_string)
+                    .collect();
+                // Tags pick schedules, breaking feeds, and style and title rules
+                if self.save_feeds(manager) {
+                    self.update_rotation(Vec::new()).await;
+                }
+            }
+            // Removal is confirmed with y; Enter takes the default, no
+            PromptKind::Add | PromptKind::Remove => {}
         }
-        #[cfg(target_os = "linux")]
-        {
-            std::process::Command::new("xdg-open").arg(url).spawn()?;
+    }
+
+    /// Subscribe to a feed URL, or to the feed a page links to, and fetch it
+    async fn add_feed(&mut self, manager: &mut FeedManager, input: &str) {
+        match feeds::discover_feed(&self.client, input).await {
+            Ok(url) if manager.file.contains(&url) => {
+                self.flash(format!("Already subscribed to {}", url))
+            }
+            Ok(url) => {
+                manager.file.push(FeedEntry::new(url.clone()));
+                if self.save_feeds(manager) {
+                    manager.selected = manager.len() - 1;
+                    self.refresh_feed(&url).await;
+                }
+            }
+            Err(e) => self.flash(format!("{:#}", e)),
         }
-        #[cfg(target_os = "windows")]
-        {
-            std::process::Command::new("cmd")
-                .args(["/C", "start", url])
-                .spawn()?;
+    }
+
+    /// Unsubscribe from the selected feed, dropping its headlines
+    async fn remove_feed(&mut self, manager: &mut FeedManager) {
+        let Some(entry) = manager.file.remove(manager.selected) else {
+            return;
+        };
+        if !self.save_feeds(manager) {
+            return;
+        }
+        manager.clamp_selection();
+        self.feed_headlines.remove(&entry.url);
+        self.feed_health.remove(&entry.url);
+        self.disabled_feeds.urls.remove(&entry.url);
+        self.update_rotation(Vec::new()).await;
+        self.flash(format!("Removed {}", entry.url));
+    }
+
+    /// Write the feed manager's changes to the urls file and adopt its feed list
+    fn save_feeds(&mut self, manager: &FeedManager) -> bool {
+        match manager.file.save() {
+            Ok(()) => {
+                self.feeds = manager.file.entries();
+                true
+            }
+            Err(e) => {
+                self.flash(format!("{:#}", e));
+                false
+            }
+        }
+    }
+
+    /// Refresh all feeds now (the r key, or a click on the status bar countdown)
+    async fn refresh_now(&mut self) -> Result<()> {
+        self.refresh_feeds(false).await?;
+        if self.pending_fetch.is_some() {
+            self.status_message = Some("Refreshing feeds...".to_string());
+        }
+        Ok(())
+    }
+
+    /// Run a click action on the headline at a screen position
+    /// (the pinned headline if its line was clicked, otherwise whatever is under x in the crawl)
+    async fn perform_click_action(&mut self, action: ClickAction, x: u16, y: u16) -> Result<()> {
+        if action == ClickAction::None {
+            return Ok(());
+        }
+
+        let on_static = self.pinned_row == Some(y) || self.latest_row == Some(y);
+        if !on_static && !self.on_ticker(y) {
+            return Ok(());
+        }
+        let headline = if on_static {
+            self.static_headline_at(y).await
+        } else {
+            self.headline_at(x as usize).await?
+        };
+        let Some(headline) = headline else {
+            return Ok(());
+        };
+
+        if action == ClickAction::Menu {
+            let row = if on_static { y } else { self.ticker_row };
+            let safe_mode = self.ticker.read().await.safe_mode();
+            self.overlay = Some(Overlay::Menu(ContextMenu::new(headline, x, row, safe_mode)));
+            return Ok(());
+        }
+        self.headline_action(action, headline).await
+    }
+
+    /// Headline shown on a static row (pinned or latest), if y is one
+    async fn static_headline_at(&self, y: u16) -> Option<Headline> {
+        if self.pinned_row == Some(y) {
+            self.pinned.clone()
+        } else if self.latest_row == Some(y) {
+            self.ticker.read().await.latest_headline().cloned()
+        } else {
+            None
+        }
+    }
+
+    /// Headline at screen column x on the ticker row
+    async fn headline_at(&self, x: usize) -> Result<Option<Headline>> {
+        let ticker = self.ticker.read().await;
+        // The breaking slate covers the whole row
+        if let Some(takeover) = ticker.takeover() {
+            return Ok(Some(takeover.headline.clone()));
+        }
+        let crawl_x = self.crawl_x as usize;
+        if x < crawl_x {
+            return Ok(None);
+        }
+        // Flip display and narrow terminals show the leading headline instead of the crawl
+        if ticker.is_flip() || self.crawl_width < MIN_CRAWL_WIDTH {
+            return Ok(ticker.leading_headline().cloned());
+        }
+        Ok(ticker
+            .headline_at_position(x - crawl_x, self.crawl_width as usize)
+            .cloned())
+    }
+
+    /// Headline under the mouse, if the mouse is over the ticker row
+    async fn hovered_headline(&self) -> Result<Option<Headline>> {
+        match (self.mouse_x, self.mouse_y) {
+            (Some(x), Some(y)) if self.on_ticker(y) => self.headline_at(x as usize).await,
+            _ => Ok(None),
+        }
+    }
+
+    /// Whether screen row y is part of the ticker
+    fn on_ticker(&self, y: u16) -> bool {
+        y >= self.ticker_row && y < self.ticker_row + self.ticker_height
+    }
+
+    /// Apply a per-headline action (from a click, the context menu, or a key)
+    async fn headline_action(&mut self, action: ClickAction, headline: Headline) -> Result<()> {
+        match action {
+            ClickAction::Open => {
+                if let Some(url) = &headline.url {
+                    self.ticker.write().await.mark_read(url);
+                    self.session_stats.record_clicked(&headline.source);
+                    self.open_url(url, &headline)?;
+                }
+            }
+            ClickAction::Copy => match &headline.url {
+                Some(url) => {
+                    clipboard::copy(url)?;
+                    self.flash("Copied link");
+                }
+                None => self.flash("Headline has no link"),
+            },
+            ClickAction::Preview => {
+                // Safe mode shows titles only
+                if self.ticker.read().await.safe_mode() {
+                    self.flash(headline.title);
+                } else {
+                    self.flash(format!(
+                        "[{}] {} {}",
+                        headline.source,
+                        headline.title,
+                        headline.url.as_deref().unwrap_or("")
+                    ));
+                }
+            }
+            ClickAction::Command => {
+                let Some(template) = self.config.click_command.clone() else {
+                    self.flash("click_command is not set");
+                    return Ok(());
+                };
+                if let Some(url) = &headline.url {
+                    self.ticker.write().await.mark_read(url);
+                }
+                self.session_stats.record_clicked(&headline.source);
+                if self.config.mark_read != MarkRead::Never {
+                    self.queue_read(&headline);
+                }
+                if let Err(e) = run_click_command(&template, &headline) {
+                    self.flash(format!("click_command failed: {}", e));
+                }
+            }
+            ClickAction::Bookmark => match archive::bookmark(&headline) {
+                Ok(_) => self.flash("Bookmarked"),
+                Err(e) => self.flash(format!("Bookmark failed: {}", e)),
+            },
+            ClickAction::Mute => {
+                let mut ticker = self.ticker.write().await;
+                ticker.mute_source(&headline.source);
+                let safe_mode = ticker.safe_mode();
+                drop(ticker);
+                if safe_mode {
+                    self.flash("Muted this headline's source");
+                } else {
+                    self.flash(format!("Muted {}", headline.source));
+                }
+            }
+            ClickAction::Skip => {
+                self.ticker.write().await.skip_headline(&headline);
+                self.flash("Skipped");
+            }
+            ClickAction::Pin => {
+                let key = headline_key(&headline);
+                let unpin = self.pinned.as_ref().is_some_and(|p| headline_key(p) == key);
+                if unpin {
+                    self.pinned = None;
+                    self.flash("Unpinned");
+                } else {
+                    self.pinned = Some(headline);
+                    self.flash("Pinned");
+                }
+            }
+            ClickAction::Center => {
+                let mut ticker = self.ticker.write().await;
+                // Flip display holds one headline at a time, from the left edge
+                let width = if ticker.is_flip() { 0 } else { self.crawl_width as usize };
+                let centered = ticker.center(&headline, width);
+                drop(ticker);
+                self.flash(if centered {
+                    "Paused (Space resumes)"
+                } else {
+                    "That headline has left the rotation"
+                });
+            }
+            ClickAction::Menu | ClickAction::None => {}
+        }
+        Ok(())
+    }
+
+    /// Route a key to the open overlay
+    async fn handle_overlay_key(&mut self, code: KeyCode) -> Result<()> {
+        let menu = match &mut self.overlay {
+            Some(Overlay::Menu(menu)) => menu,
+            Some(Overlay::Panel(_)) => {
+                if matches!(
+                    code,
+                    KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') | KeyCode::Char('s')
+                ) {
+                    self.overlay = None;
+                }
+                return Ok(());
+            }
+            Some(Overlay::History(list)) => {
+                match code {
+                    KeyCode::Up | KeyCode::Char('k') => list.select_prev(),
+                    KeyCode::Down | KeyCode::Char('j') | KeyCode::Tab => list.select_next(),
+                    KeyCode::Enter | KeyCode::Char(' ') => self.reopen_selected().await?,
+                    KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('H') => {
+                        self.overlay = None
+                    }
+                    _ => {}
+                }
+                return Ok(());
+            }
+            Some(Overlay::Manager(_)) => {
+                if let Some(Overlay::Manager(manager)) = self.overlay.take() {
+                    self.handle_manager_key(manager, code).await?;
+                }
+                return Ok(());
+            }
+            Some(Overlay::Feeds(list)) => {
+                match code {
+                    KeyCode::Up | KeyCode::Char('k') => list.select_prev(),
+                    KeyCode::Down | KeyCode::Char('j') | KeyCode::Tab => list.select_next(),
+                    KeyCode::Char('r') => {
+                        if let Some(url) = list.selected_url().map(str::to_string) {
+                            self.refresh_feed(&url).await;
+                        }
+                    }
+                    KeyCode::Char(' ') | KeyCode::Enter => {
+                        if let Some(url) = list.selected_url().map(str::to_string) {
+                            self.toggle_feed(&url).await;
+                        }
+                    }
+                    KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('f') => {
+                        self.overlay = None
+                    }
+                    _ => {}
+                }
+                return Ok(());
+            }
+            None => return Ok(()),
+        };
+        match code {
+            KeyCode::Up | KeyCode::Char('k') => menu.select_prev(),
+            KeyCode::Down | KeyCode::Char('j') | KeyCode::Tab => menu.select_next(),
+            KeyCode::Enter | KeyCode::Char(' ') => {
+                let action = menu.selected_action();
+                if let Some(Overlay::Menu(menu)) = self.overlay.take() {
+                    self.headline_action(action, menu.headline).await?;
+                }
+            }
+            KeyCode::Esc | KeyCode::Char('q') => self.overlay = None,
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Route a mouse event to the open overlay
+    async fn handle_overlay_mouse(&mut self, mouse: event::MouseEvent) -> Result<()> {
+        let (width, height) = terminal::size()?;
+        let screen = Rect::new(0, 0, width, height);
+
+        let menu = match &mut self.overlay {
+            Some(Overlay::Menu(menu)) => menu,
+            Some(Overlay::Panel(_)) => {
+                if matches!(mouse.kind, MouseEventKind::Down(_)) {
+                    self.overlay = None;
+                }
+                return Ok(());
+            }
+            Some(Overlay::History(list)) => {
+                let item = list.item_at(screen, mouse.column, mouse.row);
+                match (mouse.kind, item) {
+                    (MouseEventKind::Moved, Some(idx)) => list.selected = idx,
+                    (MouseEventKind::ScrollDown, _) => list.select_next(),
+                    (MouseEventKind::ScrollUp, _) => list.select_prev(),
+                    (MouseEventKind::Down(_), Some(idx)) => {
+                        list.selected = idx;
+                        self.reopen_selected().await?;
+                    }
+                    (MouseEventKind::Down(_), None) => self.overlay = None,
+                    _ => {}
+                }
+                return Ok(());
+            }
+            Some(Overlay::Manager(manager)) => {
+                let item = manager.item_at(screen, mouse.row);
+                match (mouse.kind, item) {
+                    (MouseEventKind::Down(_), Some(idx)) => manager.selected = idx,
+                    (MouseEventKind::ScrollDown, _) => manager.select_next(),
+                    (MouseEventKind::ScrollUp, _) => manager.select_prev(),
+                    _ => {}
+                }
+                return Ok(());
+            }
+            Some(Overlay::Feeds(list)) => {
+                let item = list.item_at(screen, mouse.column, mouse.row);
+                match (mouse.kind, item) {
+                    (MouseEventKind::Moved | MouseEventKind::Down(_), Some(idx)) => {
+                        list.selected = idx
+                    }
+                    (MouseEventKind::ScrollDown, _) => list.select_next(),
+                    (MouseEventKind::ScrollUp, _) => list.select_prev(),
+                    (MouseEventKind::Down(_), None) => self.overlay = None,
+                    _ => {}
+                }
+                return Ok(());
+            }
+            None => return Ok(()),
+        };
+        let item = menu.item_at(screen, mouse.column, mouse.row);
+
+        match mouse.kind {
+            MouseEventKind::Moved => {
+                if let Some(idx) = item {
+                    menu.selected = idx;
+                }
+            }
+            MouseEventKind::Down(_) => match item {
+                Some(idx) => {
+                    menu.selected = idx;
+                    let action = menu.selected_action();
+                    if let Some(Overlay::Menu(menu)) = self.overlay.take() {
+                        self.headline_action(action, menu.headline).await?;
+                    }
+                }
+                // Clicking outside dismisses the menu
+                None => self.overlay = None,
+            },
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Open the history entry selected in the history overlay, closing it
+    async fn reopen_selected(&mut self) -> Result<()> {
+        let Some(Overlay::History(list)) = self.overlay.take() else {
+            return Ok(());
+        };
+        if let Some(entry) = list.selected_entry() {
+            self.open_url(&entry.url, &entry.to_headline())?;
+        }
+        Ok(())
+    }
+
+    /// Open the homepage of the publication behind the hovered headline
+    async fn open_hovered_source(&mut self) -> Result<()> {
+        match self.hovered_headline().await? {
+            Some(headline) => match headline.source_url.clone() {
+                Some(url) => self.open_url(&url, &headline)?,
+                None => self.flash(format!("No site link for {}", headline.source)),
+            },
+            None => self.flash("Hover a headline to open its site"),
+        }
+        Ok(())
+    }
+
+    /// Session and lifetime stats for the `s` overlay, and memory usage
+    fn stats_panel(&self, ticker: &Ticker) -> Panel {
+        let mut lines = vec!["This session".to_string()];
+        lines.extend(self.session_stats.summary());
+        if let Some(lifetime) = &self.lifetime_stats {
+            let mut total = lifetime.clone();
+            total.merge(&self.session_stats);
+            lines.push(String::new());
+            lines.push("All time".to_string());
+            lines.extend(total.summary());
+        }
+        lines.push(String::new());
+        lines.push("Memory (approximate)".to_string());
+        lines.extend(self.memory_usage(ticker).lines(self.config.memory_budget));
+        Panel::new("Stats", lines)
+    }
+
+    /// Write the current rotation to a timestamped file in the data dir
+    async fn export_rotation(&self) -> Result<String> {
+        let ticker = self.ticker.read().await;
+        let format = self.config.export_format;
+        let content = export::render(ticker.headlines(), format, &self.config.dates)?;
+        let count = ticker.headline_count();
+        drop(ticker);
+
+        let dir = get_data_dir().join("exports");
+        std::fs::create_dir_all(&dir)?;
+        let path = dir.join(format!(
+            "headlines-{}.{}",
+            chrono::Local::now().format("%Y%m%d-%H%M%S"),
+            format.extension()
+        ));
+        std::fs::write(&path, content)?;
+
+        Ok(format!("Exported {} headlines to {}", count, path.display()))
+    }
+
+    /// Queue a headline to be marked read on the backend, if it came from one
+    fn queue_read(&mut self, headline: &Headline) {
+        if let Some(id) = self.backend_ids.get(&headline_key(headline)) {
+            self.pending_read.insert(id.clone());
+        }
+    }
+
+    /// Send queued read marks to the backend, keeping them queued if that fails
+    async fn push_read_state(&mut self) {
+        self.last_read_push = Instant::now();
+        let Some(backend) = &self.config.backend else {
+            return;
+        };
+        if self.pending_read.is_empty() {
+            return;
+        }
+        let ids: Vec<String> = self.pending_read.iter().cloned().collect();
+        match backend.mark_read(&self.client, &ids).await {
+            Ok(()) => self.pending_read.clear(),
+            Err(e) => self.flash(format!("Couldn't mark read on {}: {:#}", backend.name(), e)),
+        }
+    }
+
+    /// Open a URL, asking first if confirm_open is set
+    fn open_url(&mut self, url: &str, headline: &Headline) -> Result<()> {
+        if self.config.confirm_open {
+            self.pending_open = Some((url.to_string(), headline.clone()));
+            Ok(())
+        } else {
+            self.launch_url(url, headline)
+        }
+    }
+
+    /// Open a URL in the browser and record it in the click history
+    fn launch_url(&mut self, url: &str, headline: &Headline) -> Result<()> {
+        platform::open_command(url).spawn()?;
+        if let Err(e) = archive::record_opened(&archive::history_path(), headline, url) {
+            self.flash(format!("History error: {}", e));
+        }
+        if self.config.mark_read != MarkRead::Never {
+            self.queue_read(headline);
         }
         Ok(())
     }
@@ -316,62 +2047,428 @@ impl App {
         &mut self,
         terminal: &mut Terminal<CrosstermBackend<Stdout>>,
     ) -> Result<()> {
+        // Do not disturb shows just its placeholder, and quiet hours may blank the screen
+        // (unless an overlay was opened over it)
+        let placeholder = if self.dnd {
+            let clock = || self.config.dates.now("%H:%M");
+            Some(self.config.dnd_message.clone().unwrap_or_else(clock))
+        } else {
+            (self.blanked() && self.overlay.is_none()).then(String::new)
+        };
+        if let Some(text) = placeholder {
+            terminal.draw(|frame| {
+                let area = frame.area();
+                frame.render_widget(Clear, area);
+                let row = Rect::new(0, area.height / 2, area.width, area.height.min(1));
+                frame.render_widget(Line::from(text).centered(), row);
+            })?;
+            let hide = self.logos.hide();
+            if !hide.is_empty() {
+                let mut stdout = io::stdout();
+                stdout.write_all(&hide)?;
+                stdout.flush()?;
+            }
+            return Ok(());
+        }
         let ticker = self.ticker.read().await;
         let mouse_x = self.mouse_x;
-        let status_msg = self.status_message.clone();
-        let show_status = self.config.show_status_bar;
-
-        // Calculate ticker row position for centering
+        if self
+            .flash
+            .as_ref()
+            .is_some_and(|(_, at)| at.elapsed() >= FLASH_DURATION)
+        {
+            self.flash = None;
+        }
+        let status_msg = self
+            .pending_open
+            .as_ref()
+            .map(|(url, _)| format!("Open {}? [y/N]", url))
+            .or_else(|| self.status_message.clone())
+            .or_else(|| self.flash.as_ref().map(|(msg, _)| msg.clone()));
+        // A pending confirmation needs somewhere to be seen
+        let show_status = self.config.show_status_bar || self.pending_open.is_some();
+        let failing = self
+            .feed_health
+            .iter()
+            .filter(|(url, h)| !h.is_ok() && !self.disabled_feeds.urls.contains(*url))
+            .count();
+        let next_refresh = (self.replay.is_none() && !self.quiet)
+            .then(|| self.refresh_due.saturating_sub(self.last_refresh.elapsed()));
+        let overlay = &self.overlay;
+        let health = &self.feed_health;
+        let disabled = &self.disabled_feeds.urls;
+        let dates = &self.config.dates;
+        let stale_after = self.config.stale_after;
+        let dim = self.quiet && self.config.quiet_display == QuietDisplay::Dim;
+        let pinned = self.pinned.as_ref();
+        let latest = match self.config.layout {
+            LayoutMode::Broadcast | LayoutMode::LowerThird => ticker.latest_headline(),
+            LayoutMode::Single => None,
+        };
         let size = terminal.size()?;
-        let content_height = if show_status { 2 } else { 1 };
-        let top_padding = size.height.saturating_sub(content_height) / 2;
-        self.ticker_row = top_padding;
-
-        terminal.draw(|frame| {
-            let area = frame.area();
-
-            // Create layout with centering
-            let outer_chunks = Layout::default()
-                .direction(Direction::Vertical)
-                .constraints([
-                    Constraint::Length(top_padding),
-                    Constraint::Length(content_height),
-                    Constraint::Min(0),
-                ])
-                .split(area);
-
-            let content_area = outer_chunks[1];
-
-            if show_status {
-                // Split content area into ticker and status bar
-                let chunks = Layout::default()
+        let screen = Rect::new(0, 0, size.width, size.height);
+        let row_hovered = |row: Option<u16>| self.mouse_y.is_some() && self.mouse_y == row;
+
+        // Short terminals keep the crawl first, then the status bar, then static lines
+        let show_status = show_status && size.height >= 2;
+        // The crawl's columns inside the theme's padding (the band spans the whole row)
+        let (crawl_x, crawl_width) = crawl_span(ticker.theme(), size.width);
+        let band = ticker.theme().band;
+        // A headline shown on its own wraps over as many rows as it needs and the screen allows
+        let ticker_height = single_headline_text(&ticker, crawl_width)
+            .map(|text| {
+                let rows = size.height.saturating_sub(show_status as u16).min(self.config.wrap_lines);
+                wrap(&text, crawl_width as usize, rows as usize).len() as u16
+            })
+            .unwrap_or(1);
+        let static_room = size.height.saturating_sub(ticker_height + show_status as u16);
+        let pinned = pinned.filter(|_| static_room >= 1);
+        let latest = latest.filter(|_| static_room > pinned.is_some() as u16);
+        let lower_third = self.config.layout == LayoutMode::LowerThird && size.height >= 2;
+        // The bug's logo: an image once fetched, else the source's text icon
+        let logos = lower_third && self.config.logos && !ticker.safe_mode();
+        let leading = ticker.leading_headline().filter(|_| logos);
+        let logo_source = leading
+            .filter(|h| self.logos.available(&self.client, &h.source, h.source_url.as_deref()))
+            .map(|h| h.source.clone());
+        let logo_text = logos.then(|| match leading {
+            Some(h) if logo_source.is_none() => self.config.icons.get(&h.source).map_or("", |i| i),
+            _ => "",
+        });
+        let offline_since = self.offline_since.map(|at| dates.format(at, "%H:%M"));
+        let status = show_status.then(|| {
+            let bar = StatusBar::new(&ticker)
+                .with_failing(failing)
+                .with_offline_since(offline_since.as_deref())
+                .with_next_refresh(next_refresh)
+                .with_preset(speed_preset(&self.config.speed_presets, ticker.speed()));
+            match &status_msg {
+                Some(msg) => bar.with_message(msg),
+                None => bar,
+            }
+        });
+        let refresh_span = status.as_ref().and_then(|bar| bar.refresh_span());
+
+        if lower_third {
+            // Band across the bottom; its top row holds the pin if there is one, else the latest
+            let band_height = LOWER_THIRD_HEIGHT.min(size.height);
+            let band = Rect::new(
+                0,
+                size.height - band_height,
+                size.width,
+                band_height,
+            );
+            let top = pinned.map(|h| (h, "PINNED")).or(latest.map(|h| (h, "LATEST")));
+            self.pinned_row = pinned.map(|_| band.y);
+            self.latest_row = latest.filter(|_| pinned.is_none()).map(|_| band.y);
+
+            let mut widget = LowerThirdWidget::new(&ticker).logo(logo_text);
+            let crawl = widget.crawl_area(band);
+            self.ticker_row = crawl.y;
+            self.ticker_height = 1;
+            self.crawl_x = crawl.x;
+            self.crawl_width = crawl.width;
+
+            let headline = top.map(|(h, label)| {
+                StaticHeadlineWidget::new(h, label)
+                    .label_style(Style::default().fg(Color::White).bg(Color::Red).bold())
+                    .hovered(row_hovered(Some(band.y)))
+                    .safe_mode(ticker.safe_mode())
+            });
+            self.status_row = status.as_ref().filter(|_| band.height > 2).map(|_| band.y + 2);
+            self.refresh_field = refresh_span
+                .filter(|_| band.height > 2)
+                .map(|span| Rect::new(band.x + span.start, band.y + 2, span.len() as u16, 1));
+            let hovered = mouse_x.filter(|_| row_hovered(Some(crawl.y)));
+            widget = widget.headline(headline).status(status).hovered(hovered);
+
+            terminal.draw(|frame| {
+                frame.render_widget(widget, band);
+                if let Some(takeover) = ticker.takeover() {
+                    let row = Rect::new(band.x, crawl.y, band.width, 1);
+                    frame.render_widget(TakeoverWidget::new(takeover), row);
+                }
+                if dim {
+                    frame.buffer_mut().set_style(screen, Style::default().dim());
+                }
+                render_overlay(frame, overlay, health, disabled, dates, stale_after, screen);
+            })?;
+        } else {
+            // Static lines (pinned, then latest) stack above the crawl
+            let static_height = pinned.is_some() as u16 + latest.is_some() as u16;
+            let content_height = static_height + ticker_height + show_status as u16;
+            let top_padding = size.height.saturating_sub(content_height) / 2;
+            self.pinned_row = pinned.map(|_| top_padding);
+            self.latest_row = latest.map(|_| top_padding + pinned.is_some() as u16);
+            self.ticker_row = top_padding + static_height;
+            self.ticker_height = ticker_height;
+            self.status_row = show_status.then_some(self.ticker_row + ticker_height);
+            self.refresh_field = refresh_span.map(|span| {
+                let row = self.ticker_row + ticker_height;
+                Rect::new(span.start, row, span.len() as u16, 1)
+            });
+            self.crawl_x = crawl_x;
+            self.crawl_width = crawl_width;
+            let mouse_x = mouse_x.filter(|_| self.mouse_y.is_some_and(|y| self.on_ticker(y)));
+            let pin_hovered = row_hovered(self.pinned_row);
+            let latest_hovered = row_hovered(self.latest_row);
+
+            terminal.draw(|frame| {
+                let area = frame.area();
+
+                // Create layout with centering
+                let outer_chunks = Layout::default()
                     .direction(Direction::Vertical)
-                    .constraints([Constraint::Length(1), Constraint::Length(1)])
-                    .split(content_area);
+                    .constraints([
+                        Constraint::Length(top_padding),
+                        Constraint::Length(content_height),
+                        Constraint::Min(0),
+                    ])
+                    .split(area);
 
-                // Render ticker
-                let ticker_widget = TickerWidget::new(&ticker).hovered(mouse_x);
-                frame.render_widget(ticker_widget, chunks[0]);
+                let mut content_area = outer_chunks[1];
+
+                let static_lines = [
+                    pinned.map(|h| {
+                        StaticHeadlineWidget::new(h, "PINNED")
+                            .hovered(pin_hovered)
+                            .safe_mode(ticker.safe_mode())
+                    }),
+                    latest.map(|h| {
+                        StaticHeadlineWidget::new(h, "LATEST")
+                            .label_style(Style::default().fg(Color::White).bg(Color::Red).bold())
+                            .hovered(latest_hovered)
+                            .safe_mode(ticker.safe_mode())
+                    }),
+                ];
+                for widget in static_lines.into_iter().flatten() {
+                    let chunks = Layout::default()
+                        .direction(Direction::Vertical)
+                        .constraints([Constraint::Length(1), Constraint::Min(0)])
+                        .split(content_area);
+                    frame.render_widget(widget, chunks[0]);
+                    content_area = chunks[1];
+                }
 
-                // Render status bar
-                let status_bar = if let Some(msg) = &status_msg {
-                    StatusBar::new(&ticker).with_message(msg)
+                let ticker_area = if let Some(status_bar) = status {
+                    // Split content area into ticker and status bar
+                    let chunks = Layout::default()
+                        .direction(Direction::Vertical)
+                        .constraints([Constraint::Length(ticker_height), Constraint::Length(1)])
+                        .split(content_area);
+                    frame.render_widget(status_bar, chunks[1]);
+                    chunks[0]
                 } else {
-                    StatusBar::new(&ticker)
+                    // Just ticker, centered
+                    content_area
                 };
-                frame.render_widget(status_bar, chunks[1]);
-            } else {
-                // Just ticker, centered
-                let ticker_widget = TickerWidget::new(&ticker).hovered(mouse_x);
-                frame.render_widget(ticker_widget, content_area);
-            }
-        })?;
+
+                // Band across the ticker row, crawl inside its padding
+                frame.buffer_mut().set_style(ticker_area, band);
+                let crawl = Rect {
+                    x: ticker_area.x + crawl_x,
+                    width: crawl_width,
+                    ..ticker_area
+                };
+                frame.render_widget(TickerWidget::new(&ticker).hovered(mouse_x), crawl);
+                if let Some(takeover) = ticker.takeover() {
+                    frame.render_widget(TakeoverWidget::new(takeover), ticker_area);
+                }
+                if dim {
+                    frame.buffer_mut().set_style(area, Style::default().dim());
+                }
+
+                render_overlay(frame, overlay, health, disabled, dates, stale_after, area);
+            })?;
+        }
+
+        // Overlays and the breaking slate may cover the bug, and kitty would keep the logo
+        // above them
+        let covered = self.overlay.is_some() || ticker.takeover().is_some();
+        let logo = if !covered && self.crawl_x > LOGO_WIDTH {
+            self.logos.draw(logo_source.as_deref(), 1, self.ticker_row)
+        } else {
+            self.logos.hide()
+        };
+        if !logo.is_empty() {
+            let mut stdout = io::stdout();
+            stdout.write_all(&logo)?;
+            stdout.flush()?;
+        }
+
+        // Nobody is clicking a background window; the links return on focus.
+        // Overlays may cover the ticker row, which the links would be written over
+        if self.in_background() || self.overlay.is_some() {
+            return Ok(());
+        }
 
         // Render hyperlinks overlay (OSC 8) at the correct row
-        let mut renderer = HyperlinkRenderer::new();
-        renderer.render_ticker_line(&ticker, size.width as usize, self.ticker_row)?;
+        let crawl_hover = self.mouse_x.filter(|_| self.mouse_y.is_some_and(|y| self.on_ticker(y)));
+        let mut renderer = HyperlinkRenderer::new()
+            .with_band(lower_third)
+            .with_links(self.hyperlinks)
+            .dimmed(dim)
+            .hovered(crawl_hover);
+        renderer.render_ticker_line(
+            &ticker,
+            self.crawl_width as usize,
+            self.ticker_row,
+            self.crawl_x,
+        )?;
         renderer.flush()?;
 
         Ok(())
     }
 }
+
+/// Feeds to poll: those in the feeds file, or with an aggregator backend the server
+/// alone, standing in for all of them
+async fn load_feeds(config: &Config, client: &HttpClient) -> Result<Vec<FeedEntry>> {
+    match &config.backend {
+        Some(backend) => Ok(vec![FeedEntry::new(backend.url())]),
+        None => feeds::parse_feeds_files(client, &config.feeds_paths).await,
+    }
+}
+
+/// The grouped stories, each with the headlines it shows in turn; None if there are none
+fn stories_panel(ticker: &Ticker) -> Option<Panel> {
+    let stories = ticker.stories();
+    if stories.is_empty() {
+        return None;
+    }
+    let mut lines = Vec::new();
+    for story in stories {
+        if !lines.is_empty() {
+            lines.push(String::new());
+        }
+        let lead = &story.members[0];
+        lines.push(format!("{} ({} sources)", lead.title, story.sources));
+        lines.extend(story.members.iter().map(|h| format!("  {}", ticker.caption(h))));
+    }
+    Some(Panel::new("Stories", lines))
+}
+
+/// Number (1-based) of the number-key preset set to `speed`, if any
+fn speed_preset(presets: &[u32], speed: u32) -> Option<usize> {
+    presets.iter().take(9).position(|&p| p.clamp(1, 100) == speed).map(|i| i + 1)
+}
+
+/// The refresh interval moved by up to `jitter` (at most half the interval) either way, so
+/// instances started together drift apart
+fn jittered(interval: Duration, jitter: Duration) -> Duration {
+    let jitter = jitter.min(interval / 2);
+    if jitter.is_zero() {
+        return interval;
+    }
+    let offset = rand::rng().random_range(0.0..=2.0) * jitter.as_secs_f64();
+    interval - jitter + Duration::from_secs_f64(offset)
+}
+
+/// Draw the open overlay, if any, over the whole screen
+fn render_overlay(
+    frame: &mut ratatui::Frame,
+    overlay: &Option<Overlay>,
+    health: &HashMap<String, FeedHealth>,
+    disabled: &BTreeSet<String>,
+    dates: &DateStyle,
+    stale_after: Duration,
+    area: Rect,
+) {
+    match overlay {
+        Some(Overlay::Menu(menu)) => frame.render_widget(ContextMenuWidget::new(menu), area),
+        Some(Overlay::Panel(panel)) => frame.render_widget(PanelWidget::new(panel), area),
+        Some(Overlay::History(list)) => {
+            frame.render_widget(HistoryWidget::new(list, dates), area)
+        }
+        Some(Overlay::Feeds(list)) => {
+            let widget = FeedListWidget::new(list, health, disabled, dates, stale_after);
+            frame.render_widget(widget, area)
+        }
+        Some(Overlay::Manager(manager)) => {
+            frame.render_widget(FeedManagerWidget::new(manager, health, disabled), area)
+        }
+        None => {}
+    }
+}
+
+/// Raw mode, alternate screen, and the mouse and focus reporting the ticker relies on.
+/// Without `require_tty`, going without raw mode (no terminal to put in it) is fine
+fn enter_tui(out: &mut impl io::Write, require_tty: bool) -> Result<()> {
+    if let Err(e) = terminal::enable_raw_mode() {
+        if require_tty {
+            return Err(e.into());
+        }
+    }
+    execute!(
+        out,
+        EnterAlternateScreen,
+        EnableMouseCapture,
+        DisableLineWrap,
+        event::EnableFocusChange,
+        cursor::Hide
+    )?;
+    Ok(())
+}
+
+/// Run $VISUAL or $EDITOR (falling back to vi) on a file and wait for it to exit
+fn run_editor(path: &Path) -> Result<()> {
+    let editor = ["VISUAL", "EDITOR"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|editor| !editor.trim().is_empty())
+        .unwrap_or_else(|| "vi".to_string());
+    let mut words = editor.split_whitespace();
+    let program = words.next().unwrap_or("vi");
+
+    let status = std::process::Command::new(program)
+        .args(words)
+        .arg(path)
+        .status()
+        .with_context(|| format!("Failed to run editor {}", program))?;
+    if !status.success() {
+        anyhow::bail!("Editor {} exited with {}", program, status);
+    }
+    Ok(())
+}
+
+/// Spawn click_command with placeholders substituted per argument (no shell involved)
+fn run_click_command(template: &str, headline: &Headline) -> Result<()> {
+    let args: Vec<String> = template
+        .split_whitespace()
+        .map(|arg| {
+            arg.replace("{url}", headline.url.as_deref().unwrap_or(""))
+                .replace("{title}", &headline.title)
+                .replace("{source}", &headline.source)
+        })
+        .collect();
+
+    let Some((program, rest)) = args.split_first() else {
+        anyhow::bail!("click_command is empty");
+    };
+
+    std::process::Command::new(program)
+        .args(rest)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .spawn()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_jittered() {
+        let interval = Duration::from_secs(600);
+        assert_eq!(jittered(interval, Duration::ZERO), interval);
+        for _ in 0..100 {
+            let due = jittered(interval, Duration::from_secs(30));
+            assert!((Duration::from_secs(570)..=Duration::from_secs(630)).contains(&due));
+            // Never more than half the interval either way
+            let due = jittered(interval, Duration::from_secs(3600));
+            assert!((Duration::from_secs(300)..=Duration::from_secs(900)).contains(&due));
+        }
+    }
+}
@@ -0,0 +1,235 @@
+use crate::feeds::Headline;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs::{self, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+/// A single line in the headline archive
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveRecord {
+    pub title: String,
+    pub url: Option<String>,
+    pub source: String,
+    pub published: Option<DateTime<Utc>>,
+    pub first_seen: DateTime<Utc>,
+}
+
+impl ArchiveRecord {
+    pub fn into_headline(self) -> Headline {
+        Headline {
+            title: self.title,
+            url: self.url,
+            source: self.source,
+            published: self.published,
+            source_url: None,
+        }
+    }
+}
+
+/// Append-only JSONL record of every headline that crossed the ticker
+pub struct Archive {
+    path: PathBuf,
+    /// Keys already written, so each headline is archived once
+    seen: HashSet<String>,
+}
+
+impl Archive {
+    /// Open the archive, indexing existing records so they aren't written twice
+    pub fn open(path: PathBuf) -> Result<Self> {
+        let mut seen = HashSet::new();
+        if path.exists() {
+            for record in read_records(&path)? {
+                seen.insert(record_key(record.url.as_deref(), &record.title));
+            }
+        }
+        Ok(Self { path, seen })
+    }
+
+    /// Append headlines not already in the archive
+    /// Returns the number of records written
+    pub fn append(&mut self, headlines: &[Headline]) -> Result<usize> {
+        let now = Utc::now();
+        let mut lines = String::new();
+        let mut written = 0;
+
+        for headline in headlines {
+            let key = record_key(headline.url.as_deref(), &headline.title);
+            if !self.seen.insert(key) {
+                continue;
+            }
+            let record = ArchiveRecord {
+                title: headline.title.clone(),
+                url: headline.url.clone(),
+                source: headline.source.clone(),
+                published: headline.published,
+                first_seen: now,
+            };
+            lines.push_str(&serde_json::to_string(&record)?);
+            lines.push('\n');
+            written += 1;
+        }
+
+        if written > 0 {
+            if let Some(parent) = self.path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            let mut file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&self.path)
+                .with_context(|| format!("Failed to open archive: {}", self.path.display()))?;
+            file.write_all(lines.as_bytes())?;
+        }
+
+        Ok(written)
+    }
+}
+
+/// Append a headline to the bookmarks file in the data dir
+pub fn bookmark(headline: &Headline) -> Result<PathBuf> {
+    let path = crate::config::get_data_dir().join("bookmarks.jsonl");
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let record = ArchiveRecord {
+        title: headline.title.clone(),
+        url: headline.url.clone(),
+        source: headline.source.clone(),
+        published: headline.published,
+        first_seen: Utc::now(),
+    };
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("Failed to open bookmarks: {}", path.display()))?;
+    writeln!(file, "{}", serde_json::to_string(&record)?)?;
+    Ok(path)
+}
+
+/// A URL opened from the ticker
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OpenedRecord {
+    pub opened_at: DateTime<Utc>,
+    pub url: String,
+    pub title: String,
+    pub source: String,
+}
+
+impl OpenedRecord {
+    /// Headline to hand back to the open action
+    pub fn to_headline(&self) -> Headline {
+        Headline {
+            title: self.title.clone(),
+            url: Some(self.url.clone()),
+            source: self.source.clone(),
+            published: None,
+            source_url: None,
+        }
+    }
+}
+
+/// Where opened URLs are recorded
+pub fn history_path() -> PathBuf {
+    crate::config::get_data_dir().join("history.jsonl")
+}
+
+/// Append an opened URL to the click history
+pub fn record_opened(path: &Path, headline: &Headline, url: &str) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let record = OpenedRecord {
+        opened_at: Utc::now(),
+        url: url.to_string(),
+        title: headline.title.clone(),
+        source: headline.source.clone(),
+    };
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("Failed to open history: {}", path.display()))?;
+    writeln!(file, "{}", serde_json::to_string(&record)?)?;
+    Ok(())
+}
+
+/// Most recently opened URLs, newest first
+pub fn read_history(path: &Path, limit: usize) -> Result<Vec<OpenedRecord>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let file = fs::File::open(path)
+        .with_context(|| format!("Failed to open history: {}", path.display()))?;
+    let mut records: Vec<OpenedRecord> = BufReader::new(file)
+        .lines()
+        .map_while(Result::ok)
+        .filter_map(|line| serde_json::from_str(&line).ok())
+        .collect();
+    records.reverse();
+    records.truncate(limit);
+    Ok(records)
+}
+
+/// Read all records from an archive file, skipping malformed lines
+pub fn read_records(path: &Path) -> Result<Vec<ArchiveRecord>> {
+    let file = fs::File::open(path)
+        .with_context(|| format!("Failed to open archive: {}", path.display()))?;
+    let records = BufReader::new(file)
+        .lines()
+        .map_while(Result::ok)
+        .filter_map(|line| serde_json::from_str(&line).ok())
+        .collect();
+    Ok(records)
+}
+
+fn record_key(url: Option<&str>, title: &str) -> String {
+    url.unwrap_or(title).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::feeds::headline;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_append_is_deduplicated() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("archive.jsonl");
+
+        let headlines = vec![headline("Hello", "Test", Some("https://example.com/hello"))];
+
+        let mut archive = Archive::open(path.clone()).unwrap();
+        assert_eq!(archive.append(&headlines).unwrap(), 1);
+        assert_eq!(archive.append(&headlines).unwrap(), 0);
+
+        // Reopening indexes existing records
+        let mut archive = Archive::open(path.clone()).unwrap();
+        assert_eq!(archive.append(&headlines).unwrap(), 0);
+
+        let records = read_records(&path).unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].title, "Hello");
+    }
+
+    #[test]
+    fn test_history_newest_first() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("history.jsonl");
+        assert!(read_history(&path, 10).unwrap().is_empty());
+
+        for title in ["First", "Second", "Third"] {
+            let headline = headline(title, "Test", None);
+            record_opened(&path, &headline, "https://example.com").unwrap();
+        }
+
+        let history = read_history(&path, 2).unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].title, "Third");
+        assert_eq!(history[1].title, "Second");
+    }
+}
@@ -0,0 +1,591 @@
+use crate::config::{BackendKind, ServerConfig};
+use crate::dates;
+use crate::feeds::{status_line, FeedStatus, Headline};
+use crate::http::{HttpClient, HttpResponse};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use reqwest::Method;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fmt;
+use std::time::Duration;
+
+/// Feedbin's hosted API, used when no url is configured
+const FEEDBIN_API: &str = "https://api.feedbin.com";
+
+/// An aggregator server that does the polling; chyron shows its unread entries
+#[derive(Clone)]
+pub enum Backend {
+    Miniflux(Miniflux),
+    FreshRss(FreshRss),
+    Feedbin(Feedbin),
+    Nextcloud(Nextcloud),
+}
+
+impl fmt::Debug for Backend {
+    // Credentials stay out of debug output
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Backend({} at {})", self.name(), self.url())
+    }
+}
+
+impl Backend {
+    /// The configured backend, or None when polling feeds directly
+    pub fn from_config(kind: BackendKind, server: &ServerConfig) -> Result<Option<Self>> {
+        let url = || -> Result<String> {
+            let url = server
+                .url
+                .as_deref()
+                .or((kind == BackendKind::Feedbin).then_some(FEEDBIN_API))
+                .context("backend needs a server url: add `url = \"https://…\"` under [server]")?;
+            Ok(url.trim_end_matches('/').to_string())
+        };
+        let backend = match kind {
+            BackendKind::Feeds => return Ok(None),
+            BackendKind::Miniflux => Self::Miniflux(Miniflux {
+                url: url()?,
+                token: server.token.clone().context("miniflux needs an API token under [server]")?,
+            }),
+            BackendKind::Freshrss => Self::FreshRss(FreshRss {
+                url: url()?,
+                login: match (&server.token, &server.username, &server.password) {
+                    (Some(token), _, _) => Login::Token(token.clone()),
+                    (None, Some(user), Some(password)) => {
+                        Login::Password(user.clone(), password.clone())
+                    }
+                    _ => anyhow::bail!(
+                        "freshrss needs a token, or username and (API) password, under [server]"
+                    ),
+                },
+            }),
+            BackendKind::Feedbin => {
+                let (Some(user), Some(password)) = (&server.username, &server.password) else {
+                    anyhow::bail!("feedbin needs the account email and password under [server]");
+                };
+                Self::Feedbin(Feedbin {
+                    url: url()?,
+                    auth: basic_auth(user, password),
+                })
+            }
+            BackendKind::Nextcloud => {
+                let (Some(user), Some(password)) = (&server.username, &server.password) else {
+                    anyhow::bail!("nextcloud needs a username and app password under [server]");
+                };
+                Self::Nextcloud(Nextcloud {
+                    url: url()?,
+                    auth: basic_auth(user, password),
+                })
+            }
+        };
+        Ok(Some(backend))
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Miniflux(_) => "Miniflux",
+            Self::FreshRss(_) => "FreshRSS",
+            Self::Feedbin(_) => "Feedbin",
+            Self::Nextcloud(_) => "Nextcloud News",
+        }
+    }
+
+    /// Server base URL, which also keys the backend's health and headlines in place of
+    /// feed URLs
+    pub fn url(&self) -> &str {
+        match self {
+            Self::Miniflux(server) => &server.url,
+            Self::FreshRss(server) => &server.url,
+            Self::Feedbin(server) => &server.url,
+            Self::Nextcloud(server) => &server.url,
+        }
+    }
+
+    /// Fetch up to `limit` unread entries, newest first, reported like a single feed, with
+    /// the server's entry ids for `mark_read`
+    pub async fn fetch_entries(
+        &self,
+        client: &HttpClient,
+        limit: usize,
+        max_age: Duration,
+    ) -> (Vec<Entry>, FeedStatus) {
+        let unread = match self {
+            Self::Miniflux(server) => server.unread(client, limit).await,
+            Self::FreshRss(server) => server.unread(client, limit).await,
+            Self::Feedbin(server) => server.unread(client, limit).await,
+            Self::Nextcloud(server) => server.unread(client, limit).await,
+        };
+        match unread {
+            Ok(mut entries) => {
+                let cutoff = dates::age_cutoff(max_age);
+                let unread = entries.len();
+                entries.retain(|e| e.headline.published.is_none_or(|d| d >= cutoff));
+                let status = FeedStatus::Ok {
+                    title: format!("{} unread", self.name()),
+                    item_count: entries.len(),
+                    expired: unread - entries.len(),
+                    newest: None,
+                    user_agent: None,
+                };
+                (entries, status)
+            }
+            Err(e) => (Vec::new(), FeedStatus::Error(format!("{:#}", e))),
+        }
+    }
+
+    /// Mark entries read on the server, by the ids from `fetch_entries`
+    pub async fn mark_read(&self, client: &HttpClient, ids: &[String]) -> Result<()> {
+        if ids.is_empty() {
+            return Ok(());
+        }
+        match self {
+            Self::Miniflux(server) => server.mark_read(client, ids).await,
+            Self::FreshRss(server) => server.mark_read(client, ids).await,
+            Self::Feedbin(server) => server.mark_read(client, ids).await,
+            Self::Nextcloud(server) => server.mark_read(client, ids).await,
+        }
+    }
+}
+
+/// An unread entry and the server's id for it
+#[derive(Debug, Clone)]
+pub struct Entry {
+    pub headline: Headline,
+    pub id: String,
+}
+
+/// Entry ids as numbers, for the services that number them
+fn id_array(ids: &[String]) -> Vec<i64> {
+    ids.iter().filter_map(|id| id.parse().ok()).collect()
+}
+
+fn json_body(value: serde_json::Value) -> Option<(&'static str, Vec<u8>)> {
+    Some(("application/json", value.to_string().into_bytes()))
+}
+
+/// Miniflux REST API, authenticated with an API token
+#[derive(Clone)]
+pub struct Miniflux {
+    url: String,
+    token: String,
+}
+
+#[derive(Deserialize)]
+struct MinifluxEntries {
+    entries: Vec<MinifluxEntry>,
+}
+
+#[derive(Deserialize)]
+struct MinifluxEntry {
+    id: i64,
+    title: String,
+    url: String,
+    published_at: Option<DateTime<Utc>>,
+    feed: MinifluxFeed,
+}
+
+#[derive(Deserialize)]
+struct MinifluxFeed {
+    title: String,
+    site_url: Option<String>,
+}
+
+impl Miniflux {
+    async fn unread(&self, client: &HttpClient, limit: usize) -> Result<Vec<Entry>> {
+        let url = format!(
+            "{}/v1/entries?status=unread&order=published_at&direction=desc&limit={}",
+            self.url, limit
+        );
+        let headers = [("X-Auth-Token", self.token.as_str())];
+        let response = call(client, Method::GET, &url, &headers, None).await?;
+        let entries: MinifluxEntries =
+            serde_json::from_slice(&response.body).context("Unexpected Miniflux response")?;
+        Ok(entries
+            .entries
+            .into_iter()
+            .map(|entry| Entry {
+                id: entry.id.to_string(),
+                headline: Headline {
+                    title: entry.title,
+                    url: Some(entry.url).filter(|u| !u.is_empty()),
+                    source: entry.feed.title,
+                    published: entry.published_at,
+                    source_url: entry.feed.site_url.filter(|u| !u.is_empty()),
+                },
+            })
+            .collect())
+    }
+
+    async fn mark_read(&self, client: &HttpClient, ids: &[String]) -> Result<()> {
+        let url = format!("{}/v1/entries", self.url);
+        let headers = [("X-Auth-Token", self.token.as_str())];
+        let body = json_body(serde_json::json!({"entry_ids": id_array(ids), "status": "read"}));
+        call(client, Method::PUT, &url, &headers, body).await?;
+        Ok(())
+    }
+}
+
+/// FreshRSS through its Google Reader compatible API
+#[derive(Clone)]
+pub struct FreshRss {
+    url: String,
+    login: Login,
+}
+
+#[derive(Clone)]
+enum Login {
+    /// Auth token as returned by ClientLogin ("user/0123abcd…")
+    Token(String),
+    /// Username and API password, exchanged for a token on each fetch
+    Password(String, String),
+}
+
+#[derive(Deserialize)]
+struct ReaderStream {
+    #[serde(default)]
+    items: Vec<ReaderItem>,
+}
+
+#[derive(Deserialize)]
+struct ReaderItem {
+    id: String,
+    title: String,
+    /// Unix seconds
+    published: Option<i64>,
+    #[serde(default)]
+    canonical: Vec<ReaderLink>,
+    #[serde(default)]
+    alternate: Vec<ReaderLink>,
+    origin: ReaderOrigin,
+}
+
+#[derive(Deserialize)]
+struct ReaderLink {
+    href: String,
+}
+
+#[derive(Deserialize)]
+struct ReaderOrigin {
+    title: String,
+    #[serde(rename = "htmlUrl")]
+    html_url: Option<String>,
+}
+
+impl FreshRss {
+    fn api(&self) -> String {
+        format!("{}/api/greader.php", self.url)
+    }
+
+    /// The GoogleLogin auth token, logging in first if configured with a password
+    async fn auth(&self, client: &HttpClient) -> Result<String> {
+        let (user, password) = match &self.login {
+            Login::Token(token) => return Ok(token.clone()),
+            Login::Password(user, password) => (user, password),
+        };
+        let form = form_urlencoded::Serializer::new(String::new())
+            .append_pair("Email", user)
+            .append_pair("Passwd", password)
+            .finish();
+        let url = format!("{}/accounts/ClientLogin", self.api());
+        let body = Some(("application/x-www-form-urlencoded", form.into_bytes()));
+        let response = call(client, Method::POST, &url, &[], body)
+            .await
+            .context("FreshRSS login failed")?;
+        String::from_utf8_lossy(&response.body)
+            .lines()
+            .find_map(|line| line.strip_prefix("Auth="))
+            .map(str::to_string)
+            .context("FreshRSS login returned no Auth token")
+    }
+
+    async fn unread(&self, client: &HttpClient, limit: usize) -> Result<Vec<Entry>> {
+        let auth = format!("GoogleLogin auth={}", self.auth(client).await?);
+        let url = format!(
+            "{}/reader/api/0/stream/contents/user/-/state/com.google/reading-list\
+             ?xt=user/-/state/com.google/read&n={}&output=json",
+            self.api(),
+            limit
+        );
+        let response = call(client, Method::GET, &url, &[("Authorization", &auth)], None).await?;
+        let stream: ReaderStream =
+            serde_json::from_slice(&response.body).context("Unexpected FreshRSS response")?;
+        Ok(stream
+            .items
+            .into_iter()
+            .map(|item| Entry {
+                id: item.id,
+                headline: Headline {
+                    title: item.title,
+                    url: item.canonical.into_iter().chain(item.alternate).next().map(|l| l.href),
+                    source: item.origin.title,
+                    published: item.published.and_then(|s| DateTime::from_timestamp(s, 0)),
+                    source_url: item.origin.html_url.filter(|u| !u.is_empty()),
+                },
+            })
+            .collect())
+    }
+
+    async fn mark_read(&self, client: &HttpClient, ids: &[String]) -> Result<()> {
+        let auth = format!("GoogleLogin auth={}", self.auth(client).await?);
+        let headers = [("Authorization", auth.as_str())];
+        // Writes need a short-lived action token alongside the auth token
+        let url = format!("{}/reader/api/0/token", self.api());
+        let response = call(client, Method::GET, &url, &headers, None).await?;
+        let token = String::from_utf8_lossy(&response.body).trim().to_string();
+
+        let mut form = form_urlencoded::Serializer::new(String::new());
+        for id in ids {
+            form.append_pair("i", id);
+        }
+        let form = form
+            .append_pair("a", "user/-/state/com.google/read")
+            .append_pair("T", &token)
+            .finish();
+        let url = format!("{}/reader/api/0/edit-tag", self.api());
+        let body = Some(("application/x-www-form-urlencoded", form.into_bytes()));
+        call(client, Method::POST, &url, &headers, body).await?;
+        Ok(())
+    }
+}
+
+/// Feedbin's REST API, authenticated with the account's email and password
+#[derive(Clone)]
+pub struct Feedbin {
+    url: String,
+    /// Basic auth header value
+    auth: String,
+}
+
+#[derive(Deserialize)]
+struct FeedbinEntry {
+    id: i64,
+    feed_id: i64,
+    title: Option<String>,
+    url: Option<String>,
+    published: Option<DateTime<Utc>>,
+}
+
+#[derive(Deserialize)]
+struct FeedbinSubscription {
+    feed_id: i64,
+    title: String,
+    site_url: Option<String>,
+}
+
+impl Feedbin {
+    async fn get<T: DeserializeOwned>(&self, client: &HttpClient, path: &str) -> Result<T> {
+        let url = format!("{}/v2/{}", self.url, path);
+        let headers = [("Authorization", self.auth.as_str())];
+        let response = call(client, Method::GET, &url, &headers, None).await?;
+        serde_json::from_slice(&response.body).context("Unexpected Feedbin response")
+    }
+
+    async fn unread(&self, client: &HttpClient, limit: usize) -> Result<Vec<Entry>> {
+        let path = format!("entries.json?read=false&per_page={}", limit);
+        let entries: Vec<FeedbinEntry> = self.get(client, &path).await?;
+        // Entries only carry a feed id; names and homepages come from subscriptions
+        let subscriptions: Vec<FeedbinSubscription> =
+            self.get(client, "subscriptions.json").await?;
+        let feeds: HashMap<i64, FeedbinSubscription> =
+            subscriptions.into_iter().map(|s| (s.feed_id, s)).collect();
+        Ok(entries
+            .into_iter()
+            .filter_map(|entry| {
+                let feed = feeds.get(&entry.feed_id);
+                let headline = Headline {
+                    title: entry.title.filter(|t| !t.trim().is_empty())?,
+                    url: entry.url,
+                    source: feed.map(|f| f.title.clone()).unwrap_or_else(|| "Feedbin".into()),
+                    published: entry.published,
+                    source_url: feed.and_then(|f| f.site_url.clone()),
+                };
+                Some(Entry { id: entry.id.to_string(), headline })
+            })
+            .collect())
+    }
+
+    async fn mark_read(&self, client: &HttpClient, ids: &[String]) -> Result<()> {
+        let url = format!("{}/v2/unread_entries.json", self.url);
+        let headers = [("Authorization", self.auth.as_str())];
+        let body = json_body(serde_json::json!({"unread_entries": id_array(ids)}));
+        call(client, Method::DELETE, &url, &headers, body).await?;
+        Ok(())
+    }
+}
+
+/// Nextcloud News API v1.3, authenticated with a username and app password
+#[derive(Clone)]
+pub struct Nextcloud {
+    url: String,
+    /// Basic auth header value
+    auth: String,
+}
+
+#[derive(Deserialize)]
+struct NextcloudItems {
+    items: Vec<NextcloudItem>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct NextcloudItem {
+    id: i64,
+    feed_id: i64,
+    title: Option<String>,
+    url: Option<String>,
+    /// Unix seconds
+    pub_date: Option<i64>,
+}
+
+#[derive(Deserialize)]
+struct NextcloudFeeds {
+    feeds: Vec<NextcloudFeed>,
+}
+
+#[derive(Deserialize)]
+struct NextcloudFeed {
+    id: i64,
+    title: String,
+    /// The site's homepage
+    link: Option<String>,
+}
+
+impl Nextcloud {
+    fn api(&self) -> String {
+        format!("{}/index.php/apps/news/api/v1-3", self.url)
+    }
+
+    async fn get<T: DeserializeOwned>(&self, client: &HttpClient, path: &str) -> Result<T> {
+        let url = format!("{}/{}", self.api(), path);
+        let headers = [("Authorization", self.auth.as_str())];
+        let response = call(client, Method::GET, &url, &headers, None).await?;
+        serde_json::from_slice(&response.body).context("Unexpected Nextcloud News response")
+    }
+
+    async fn unread(&self, client: &HttpClient, limit: usize) -> Result<Vec<Entry>> {
+        // type 3 is "all items"; offset 0 with oldestFirst=false starts at the newest
+        let path = format!("items?type=3&getRead=false&oldestFirst=false&batchSize={}", limit);
+        let items: NextcloudItems = self.get(client, &path).await?;
+        let feeds: NextcloudFeeds = self.get(client, "feeds").await?;
+        let feeds: HashMap<i64, NextcloudFeed> =
+            feeds.feeds.into_iter().map(|f| (f.id, f)).collect();
+        Ok(items
+            .items
+            .into_iter()
+            .filter_map(|item| {
+                let feed = feeds.get(&item.feed_id);
+                let headline = Headline {
+                    title: item.title.filter(|t| !t.trim().is_empty())?,
+                    url: item.url,
+                    source: feed.map(|f| f.title.clone()).unwrap_or_else(|| "Nextcloud".into()),
+                    published: item.pub_date.and_then(|s| DateTime::from_timestamp(s, 0)),
+                    source_url: feed.and_then(|f| f.link.clone()),
+                };
+                Some(Entry { id: item.id.to_string(), headline })
+            })
+            .collect())
+    }
+
+    async fn mark_read(&self, client: &HttpClient, ids: &[String]) -> Result<()> {
+        let url = format!("{}/items/read/multiple", self.api());
+        let headers = [("Authorization", self.auth.as_str())];
+        let body = json_body(serde_json::json!({"itemIds": id_array(ids)}));
+        call(client, Method::POST, &url, &headers, body).await?;
+        Ok(())
+    }
+}
+
+/// Authorization header value for HTTP basic auth
+fn basic_auth(user: &str, password: &str) -> String {
+    format!("Basic {}", BASE64.encode(format!("{}:{}", user, password)))
+}
+
+/// Make an API call, turning non-2xx answers into errors
+async fn call(
+    client: &HttpClient,
+    method: Method,
+    url: &str,
+    headers: &[(&str, &str)],
+    body: Option<(&str, Vec<u8>)>,
+) -> Result<HttpResponse> {
+    let response = client
+        .request(method, url, headers, body)
+        .await
+        .with_context(|| format!("Failed to reach {}", url))?;
+    match response.status {
+        200..=299 => Ok(response),
+        status @ (401 | 403) => {
+            anyhow::bail!("HTTP {}: check the [server] credentials", status_line(status))
+        }
+        status => anyhow::bail!("HTTP {}", status_line(status)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_entries() {
+        let miniflux = r#"{"total": 1, "entries": [{"id": 7, "title": "Rates hold",
+            "url": "https://a.example/rates", "published_at": "2024-03-09T20:30:00+01:00",
+            "feed": {"id": 2, "title": "A Daily", "site_url": "https://a.example/"}}]}"#;
+        let entries: MinifluxEntries = serde_json::from_str(miniflux).unwrap();
+        assert_eq!(entries.entries[0].id, 7);
+        assert_eq!(entries.entries[0].feed.title, "A Daily");
+        let published = entries.entries[0].published_at.unwrap();
+        assert_eq!(published.to_rfc3339(), "2024-03-09T19:30:00+00:00");
+
+        let reader = r#"{"id": "user/-/state/com.google/reading-list", "items": [{
+            "id": "tag:google.com,2005:reader/item/0001", "title": "Launch delayed",
+            "published": 1710012600, "alternate": [{"href": "https://b.example/launch"}],
+            "origin": {"streamId": "feed/3", "title": "B Wire",
+            "htmlUrl": "https://b.example/"}}]}"#;
+        let stream: ReaderStream = serde_json::from_str(reader).unwrap();
+        assert_eq!(stream.items[0].alternate[0].href, "https://b.example/launch");
+        assert_eq!(stream.items[0].origin.title, "B Wire");
+
+        let feedbin = r#"[{"id": 2077, "feed_id": 135, "title": null, "url": "https://c.example/1",
+            "published": "2013-02-02T14:07:33.000000Z", "summary": "Untitled post"}]"#;
+        let entries: Vec<FeedbinEntry> = serde_json::from_str(feedbin).unwrap();
+        assert_eq!(entries[0].feed_id, 135);
+        assert!(entries[0].title.is_none() && entries[0].published.is_some());
+
+        let nextcloud = r#"{"items": [{"id": 3443, "guid": "http://d.example/?p=1", "feedId": 67,
+            "title": "Storm season", "url": "https://d.example/storm", "pubDate": 1367270544,
+            "unread": true, "starred": false}]}"#;
+        let items: NextcloudItems = serde_json::from_str(nextcloud).unwrap();
+        assert_eq!(items.items[0].feed_id, 67);
+        assert_eq!(items.items[0].pub_date, Some(1367270544));
+    }
+
+    #[test]
+    fn test_from_config() {
+        let server = ServerConfig {
+            url: Some("https://rss.example/".to_string()),
+            username: Some("me".to_string()),
+            password: Some("secret".to_string()),
+            ..Default::default()
+        };
+        let backend = Backend::from_config(BackendKind::Freshrss, &server).unwrap().unwrap();
+        assert_eq!(backend.url(), "https://rss.example");
+        assert!(!format!("{:?}", backend).contains("secret"));
+        assert!(Backend::from_config(BackendKind::Miniflux, &server).is_err());
+        assert!(Backend::from_config(BackendKind::Feeds, &server).unwrap().is_none());
+
+        let server = ServerConfig {
+            username: Some("me@example.com".to_string()),
+            password: Some("secret".to_string()),
+            ..Default::default()
+        };
+        let Some(Backend::Feedbin(feedbin)) =
+            Backend::from_config(BackendKind::Feedbin, &server).unwrap()
+        else {
+            panic!("expected feedbin");
+        };
+        assert_eq!(feedbin.url, FEEDBIN_API);
+        assert_eq!(feedbin.auth, "Basic bWVAZXhhbXBsZS5jb206c2VjcmV0");
+    }
+}
@@ -0,0 +1,103 @@
+use crate::config::{Config, DisplayMode, OutputMode};
+use crate::feeds;
+use crate::http::HttpClient;
+use crate::source::HeadlineSource;
+use crate::ticker::Ticker;
+use crate::ui::truncate;
+use anyhow::Result;
+use std::io::{self, Write};
+use std::time::Instant;
+
+/// Screen output width when --max-width isn't given
+const SCREEN_WIDTH: usize = 80;
+
+/// Print the rotation one headline per line, a new line every flip interval, refetching
+/// every refresh interval; for status bars that display a command's latest output line
+pub async fn run(
+    mut config: Config,
+    client: &HttpClient,
+    sources: Vec<Box<dyn HeadlineSource>>,
+) -> Result<()> {
+    config.display = DisplayMode::Flip;
+    let mut ticker = Ticker::new(&config);
+    let mut stdout = io::stdout();
+    let mut last_refresh: Option<Instant> = None;
+
+    loop {
+        if last_refresh.is_none_or(|t| t.elapsed() >= config.refresh_interval) {
+            let mut headlines = Vec::new();
+            for source in &sources {
+                headlines.append(&mut source.fetch(client, &config).await.headlines);
+            }
+            feeds::filter_blocked(&mut headlines, &config.blocked_domains);
+            headlines.truncate(config.max_total);
+            ticker.set_headlines(headlines, config.sort);
+            last_refresh = Some(Instant::now());
+        }
+
+        let url = ticker.leading_headline().and_then(|h| h.url.as_deref());
+        let line = format_line(&ticker.leading_text(), url, config.output, config.max_width);
+        // A closed pipe means the bar went away
+        if writeln!(stdout, "{}", line).and_then(|_| stdout.flush()).is_err() {
+            return Ok(());
+        }
+
+        tokio::time::sleep(config.flip_interval).await;
+        ticker.tick(config.flip_interval.as_secs_f64());
+    }
+}
+
+/// One output line: control characters blanked, cut to width, escaped for the bar
+fn format_line(text: &str, url: Option<&str>, output: OutputMode, width: Option<usize>) -> String {
+    let mut text = text.replace(char::is_control, " ");
+    let width = match output {
+        OutputMode::Screen => Some(width.unwrap_or(SCREEN_WIDTH)),
+        _ => width,
+    };
+    if let Some(width) = width {
+        text = truncate(&text, width);
+    }
+    match output {
+        OutputMode::Polybar => polybar_line(&text, url),
+        // Screen reads % as the start of an escape
+        OutputMode::Screen => text.replace('%', "%%"),
+        OutputMode::Plain | OutputMode::Tui => text,
+    }
+}
+
+/// Polybar markup for a headline, left-clickable to open its URL
+fn polybar_line(text: &str, url: Option<&str>) -> String {
+    // %{…} starts a tag, so a literal % is doubled
+    let text = text.replace('%', "%%");
+    let Some(url) = url else {
+        return text;
+    };
+    // The action runs through sh; colons end the action unless escaped
+    let command = format!("xdg-open '{}'", url.replace('\'', r"'\''"));
+    format!("%{{A1:{}:}}{}%{{A}}", command.replace(':', r"\:"), text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_polybar_line() {
+        assert_eq!(
+            polybar_line("Rates up 5%", Some("https://a.example/it's")),
+            r"%{A1:xdg-open 'https\://a.example/it'\''s':}Rates up 5%%%{A}"
+        );
+        assert_eq!(polybar_line("No link", None), "No link");
+    }
+
+    #[test]
+    fn test_screen_line() {
+        let title = format!("Rates\tup 5%\x1b[31m {}", "x".repeat(100));
+        let line = format_line(&title, None, OutputMode::Screen, None);
+        assert!(line.starts_with("Rates up 5%% [31m x"));
+        assert!(line.ends_with('…'));
+        assert_eq!(line.chars().count(), SCREEN_WIDTH + 1);
+        let line = format_line("a b c d", None, OutputMode::Screen, Some(4));
+        assert_eq!(line, "a b…");
+    }
+}
@@ -0,0 +1,136 @@
+use crate::config::Config;
+use crate::feeds::Headline;
+use crate::ticker::Ticker;
+use crate::ui::{HyperlinkRenderer, TickerWidget};
+use anyhow::Result;
+use chrono::Utc;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::widgets::Widget;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// Parameters for the synthetic rotation
+pub struct BenchOptions {
+    pub headlines: usize,
+    pub length: usize,
+    pub wide: bool,
+    pub frames: usize,
+    pub width: u16,
+}
+
+const WORDS: &[&str] = &[
+    "markets", "rally", "after", "central", "bank", "signals", "pause", "storm",
+    "season", "forecast", "shifts", "election", "results", "delayed", "researchers",
+    "unveil", "faster", "battery", "chemistry", "league", "final", "goes", "extra", "time",
+];
+
+const WIDE_WORDS: &[&str] = &["東京", "市場", "速報", "選挙", "天気", "経済", "뉴스", "速報🚀"];
+
+/// Build headlines of roughly `length` chars, optionally mixing in wide characters
+fn synthetic_headlines(opts: &BenchOptions) -> Vec<Headline> {
+    let now = Utc::now();
+    (0..opts.headlines)
+        .map(|i| {
+            let mut title = String::new();
+            let mut n = i;
+            while title.chars().count() < opts.length {
+                if !title.is_empty() {
+                    title.push(' ');
+                }
+                let word = if opts.wide && n % 3 == 0 {
+                    WIDE_WORDS[n % WIDE_WORDS.len()]
+                } else {
+                    WORDS[n % WORDS.len()]
+                };
+                title.push_str(word);
+                n = n.wrapping_mul(31).wrapping_add(7);
+            }
+            Headline {
+                title,
+                url: Some(format!("https://example.com/story/{}", i)),
+                source: format!("Source {}", i % 25),
+                published: Some(now - chrono::Duration::seconds(i as i64)),
+                source_url: None,
+            }
+        })
+        .collect()
+}
+
+/// Time `frames` iterations of `f`, returning the total elapsed time
+fn measure(frames: usize, mut f: impl FnMut()) -> Duration {
+    let start = Instant::now();
+    for _ in 0..frames {
+        f();
+    }
+    start.elapsed()
+}
+
+fn report(label: &str, total: Duration, frames: usize) {
+    let per_frame = total.as_secs_f64() / frames.max(1) as f64;
+    let rate = if per_frame > 0.0 { 1.0 / per_frame } else { f64::INFINITY };
+    println!(
+        "  {:<18} {:>10.2} µs/frame  {:>12.0} frames/s",
+        label,
+        per_frame * 1_000_000.0,
+        rate
+    );
+}
+
+/// Run the rendering benchmark and print a report
+pub fn run(config: &Config, opts: &BenchOptions) -> Result<()> {
+    let headlines = synthetic_headlines(opts);
+    let mut ticker = Ticker::new(config);
+
+    let start = Instant::now();
+    ticker.set_headlines(headlines, config.sort);
+    let build_time = start.elapsed();
+
+    let width = opts.width as usize;
+    let area = Rect::new(0, 0, opts.width, 1);
+    let mut buf = Buffer::empty(area);
+    let mut renderer = HyperlinkRenderer::new();
+    let delta = 1.0 / 60.0;
+
+    println!(
+        "Synthetic rotation: {} headlines, ~{} chars each{}, {} chars total",
+        opts.headlines,
+        opts.length,
+        if opts.wide { " (wide chars)" } else { "" },
+        ticker.text_len()
+    );
+    println!("Width: {} columns, {} frames", opts.width, opts.frames);
+    println!();
+    println!("  {:<18} {:>10.2} ms", "set_headlines", build_time.as_secs_f64() * 1000.0);
+
+    let tick = measure(opts.frames, || ticker.tick(black_box(delta)));
+    report("tick", tick, opts.frames);
+
+    let segments = measure(opts.frames, || {
+        black_box(ticker.get_visible_segments(width));
+        ticker.tick(delta);
+    });
+    report("visible segments", segments, opts.frames);
+
+    let widget = measure(opts.frames, || {
+        TickerWidget::new(&ticker).render(area, &mut buf);
+        black_box(&buf);
+        ticker.tick(delta);
+    });
+    report("widget render", widget, opts.frames);
+
+    let hyperlinks = measure(opts.frames, || {
+        let _ = renderer.render_ticker_line(&ticker, width, 0, 0);
+        ticker.tick(delta);
+    });
+    report("hyperlink render", hyperlinks, opts.frames);
+
+    let total = tick + segments + widget + hyperlinks;
+    println!();
+    println!(
+        "Full frame (tick + segments + widget + hyperlinks): {:.2} µs, budget at 60 FPS: 16667 µs",
+        total.as_secs_f64() * 1_000_000.0 / opts.frames.max(1) as f64
+    );
+
+    Ok(())
+}
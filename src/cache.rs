@@ -1,8 +1,14 @@
+use crate::dates;
+use crate::feeds::Headline;
+use crate::ticker::headline_key;
 use anyhow::Result;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::fs;
-use std::path::PathBuf;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::mem::size_of;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
 /// Cache for tracking shown headlines with timestamps
@@ -13,8 +19,16 @@ pub struct ShownCache {
 }
 
 impl ShownCache {
-    /// Load cache from disk, or return empty cache if not found
-    pub fn load() -> Self {
+    /// Load cache from disk (or an empty cache if not found), pruned to
+    /// max_age and capped at max_entries
+    pub fn load(max_age: Duration, max_entries: usize) -> Self {
+        let mut cache = Self::read();
+        cache.prune(max_age);
+        cache.evict_to(max_entries);
+        cache
+    }
+
+    fn read() -> Self {
         let path = Self::cache_path();
         if path.exists() {
             if let Ok(content) = fs::read_to_string(&path) {
@@ -44,6 +58,20 @@ impl ShownCache {
         self.entries.retain(|_, ts| *ts > cutoff);
     }
 
+    /// Evict the least recently marked entries until at most max_entries remain
+    pub fn evict_to(&mut self, max_entries: usize) {
+        if self.entries.len() <= max_entries {
+            return;
+        }
+        // Oldest first; a batch marked at once is ordered by key so exactly enough go
+        let mut oldest: Vec<(i64, String)> =
+            self.entries.iter().map(|(key, ts)| (*ts, key.clone())).collect();
+        oldest.sort_unstable();
+        for (_, key) in oldest.into_iter().take(self.entries.len() - max_entries) {
+            self.entries.remove(&key);
+        }
+    }
+
     /// Get all shown keys as a HashSet for efficient lookup
     pub fn shown_keys(&self) -> std::collections::HashSet<String> {
         self.entries.keys().cloned().collect()
@@ -58,10 +86,380 @@ impl ShownCache {
     }
 
     fn cache_path() -> PathBuf {
-        dirs_next::home_dir()
-            .unwrap_or_else(|| PathBuf::from("."))
-            .join(".cache")
-            .join("chyron")
-            .join("shown.json")
+        cache_file("shown.json")
+    }
+}
+
+/// When each headline was first fetched, for sorting by arrival and dating undated ones
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct FirstSeen {
+    /// Headline key (URL or title) -> unix timestamp of the first fetch that carried it
+    entries: HashMap<String, i64>,
+}
+
+impl FirstSeen {
+    /// Load the first-seen times (none if missing or unreadable)
+    pub fn load() -> Self {
+        fs::read_to_string(cache_file("first_seen.json"))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> Result<()> {
+        write_replacing(&cache_file("first_seen.json"), &serde_json::to_string(self)?)
+    }
+
+    /// Note the time of the first fetch for keys not seen before, and forget keys that are
+    /// neither in this fetch nor seen within max_age
+    pub fn record(&mut self, keys: &HashSet<String>, max_age: Duration) {
+        let now = Utc::now().timestamp();
+        let cutoff = dates::age_cutoff(max_age).timestamp();
+        self.entries.retain(|key, seen| *seen > cutoff || keys.contains(key));
+        for key in keys {
+            self.entries.entry(key.clone()).or_insert(now);
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<DateTime<Utc>> {
+        self.entries.get(key).and_then(|&seen| DateTime::from_timestamp(seen, 0))
+    }
+
+    /// Approximate bytes held, and the number of keys
+    pub fn memory(&self) -> (usize, usize) {
+        let bytes = self.entries.capacity() * size_of::<(String, i64)>()
+            + self.entries.keys().map(String::capacity).sum::<usize>();
+        (bytes, self.entries.len())
+    }
+}
+
+/// Titles of recently seen headlines, for spotting items republished under a new URL
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SeenTitles {
+    /// Headline key (URL or title) -> normalized title words and when it was first seen
+    entries: HashMap<String, SeenTitle>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct SeenTitle {
+    words: BTreeSet<String>,
+    seen: i64,
+}
+
+/// Share of distinct words two titles must have in common to count as the same story
+const SIMILAR_TITLES: f64 = 0.8;
+
+impl SeenTitles {
+    /// Load the titles seen within window (none if missing or unreadable)
+    pub fn load(window: Duration) -> Self {
+        let mut seen: Self = fs::read_to_string(cache_file("seen_titles.json"))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        seen.prune(window);
+        seen
+    }
+
+    pub fn save(&self) -> Result<()> {
+        write_replacing(&cache_file("seen_titles.json"), &serde_json::to_string(self)?)
+    }
+
+    /// Approximate bytes held, and the number of titles
+    pub fn memory(&self) -> (usize, usize) {
+        let bytes = self
+            .entries
+            .iter()
+            .map(|(key, entry)| {
+                size_of::<(String, SeenTitle)>()
+                    + key.capacity()
+                    + entry.words.iter().map(|w| size_of::<String>() + w.capacity()).sum::<usize>()
+            })
+            .sum();
+        (bytes, self.entries.len())
+    }
+
+    fn prune(&mut self, window: Duration) {
+        let cutoff = Utc::now().timestamp() - window.as_secs() as i64;
+        self.entries.retain(|_, entry| entry.seen > cutoff);
+    }
+
+    /// Drop headlines whose title closely matches one seen within window under another key,
+    /// and remember the rest. Returns whether any new titles were remembered
+    pub fn suppress(&mut self, headlines: &mut Vec<Headline>, window: Duration) -> bool {
+        self.prune(window);
+        let now = Utc::now().timestamp();
+        let mut added = false;
+        headlines.retain(|headline| {
+            let key = headline_key(headline);
+            if self.entries.contains_key(&key) {
+                return true;
+            }
+            let words = title_words(&headline.title);
+            if self.entries.values().any(|entry| similar(&words, &entry.words)) {
+                return false;
+            }
+            self.entries.insert(key, SeenTitle { words, seen: now });
+            added = true;
+            true
+        });
+        added
+    }
+}
+
+/// Lowercased words of a title, ignoring punctuation
+fn title_words(title: &str) -> BTreeSet<String> {
+    title
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+fn similar(a: &BTreeSet<String>, b: &BTreeSet<String>) -> bool {
+    if a.is_empty() || b.is_empty() {
+        return false;
+    }
+    let shared = a.intersection(b).count() as f64;
+    shared / a.union(b).count() as f64 >= SIMILAR_TITLES
+}
+
+/// Where the crawl was when chyron last exited
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrawlPosition {
+    /// Seed used to order the rotation in random sort mode
+    pub seed: u64,
+    /// Key (URL or title) of the headline at the left edge
+    pub headline_key: Option<String>,
+    /// Index of that headline in the rotation, used if it's no longer present
+    pub headline_index: usize,
+    /// Chars scrolled into that headline
+    pub into: f64,
+}
+
+impl CrawlPosition {
+    /// Load the saved position, if any
+    pub fn load() -> Option<Self> {
+        let content = fs::read_to_string(Self::path()).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    /// Save position to disk
+    pub fn save(&self) -> Result<()> {
+        let path = Self::path();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, serde_json::to_string(self)?)?;
+        Ok(())
+    }
+
+    fn path() -> PathBuf {
+        cache_file("position.json")
+    }
+}
+
+/// Each feed's headlines from the latest fetch, shown at startup until fresh ones arrive
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SavedHeadlines {
+    /// Headlines by feed URL (or backend server URL)
+    pub feeds: HashMap<String, Vec<Headline>>,
+}
+
+impl SavedHeadlines {
+    /// Load the saved headlines (none if missing or unreadable), dropping any older
+    /// than max_age
+    pub fn load(max_age: Duration) -> Self {
+        let mut saved: Self = fs::read_to_string(cache_file("headlines.json"))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        let cutoff = dates::age_cutoff(max_age);
+        for headlines in saved.feeds.values_mut() {
+            headlines.retain(|h| h.published.is_none_or(|d| d >= cutoff));
+        }
+        saved
+    }
+
+    pub fn save(&self) -> Result<()> {
+        write_replacing(&cache_file("headlines.json"), &serde_json::to_string(self)?)
+    }
+}
+
+/// Rotation published by a `--lead` instance for `--follow` instances to mirror
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SharedRotation {
+    pub headlines: Vec<Headline>,
+}
+
+impl SharedRotation {
+    /// Identifies a rotation: the same headlines in the same order give the same value
+    pub fn generation(headlines: &[Headline]) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        for headline in headlines {
+            headline_key(headline).hash(&mut hasher);
+        }
+        hasher.finish()
+    }
+
+    pub fn load() -> Option<Self> {
+        let content = fs::read_to_string(cache_file("lead_rotation.json")).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    pub fn save(&self) -> Result<()> {
+        write_replacing(&cache_file("lead_rotation.json"), &serde_json::to_string(self)?)
+    }
+}
+
+/// Where a `--lead` instance's crawl is, republished every second
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SharedPosition {
+    /// `SharedRotation::generation` of the rotation this position is in
+    pub generation: u64,
+    pub position: CrawlPosition,
+    /// Characters per second
+    pub speed: u32,
+    pub paused: bool,
+    /// When the position was taken
+    pub at: DateTime<Utc>,
+}
+
+impl SharedPosition {
+    pub fn load() -> Option<Self> {
+        let content = fs::read_to_string(cache_file("lead_position.json")).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    pub fn save(&self) -> Result<()> {
+        write_replacing(&cache_file("lead_position.json"), &serde_json::to_string(self)?)
+    }
+}
+
+/// Directory for state that can be rebuilt: shown headlines, crawl position, saved feeds
+pub fn cache_dir() -> PathBuf {
+    dirs_next::home_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(".cache")
+        .join("chyron")
+}
+
+fn cache_file(name: &str) -> PathBuf {
+    cache_dir().join(name)
+}
+
+/// Write through a temporary file and rename it into place, so readers polling the file
+/// never see it half written
+fn write_replacing(path: &Path, content: &str) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let temp = path.with_extension("tmp");
+    fs::write(&temp, content)?;
+    fs::rename(&temp, path)?;
+    Ok(())
+}
+
+/// Feeds switched off from the feed list, saved when remember_disabled_feeds is set
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct DisabledFeeds {
+    pub urls: BTreeSet<String>,
+}
+
+impl DisabledFeeds {
+    /// Load the saved set (empty if none)
+    pub fn load() -> Self {
+        fs::read_to_string(Self::path())
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    /// Save the set to disk
+    pub fn save(&self) -> Result<()> {
+        let path = Self::path();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, serde_json::to_string(self)?)?;
+        Ok(())
+    }
+
+    fn path() -> PathBuf {
+        cache_file("disabled_feeds.json")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::feeds::headline;
+
+    #[test]
+    fn test_evict_to_keeps_most_recent() {
+        let mut cache = ShownCache::default();
+        for (i, key) in ["a", "b", "c", "d"].iter().enumerate() {
+            cache.entries.insert(key.to_string(), i as i64);
+        }
+
+        cache.evict_to(2);
+        let mut keys: Vec<_> = cache.shown_keys().into_iter().collect();
+        keys.sort();
+        assert_eq!(keys, vec!["c", "d"]);
+
+        cache.evict_to(10);
+        assert_eq!(cache.shown_keys().len(), 2);
+
+        // A batch merged at once shares a timestamp: trimmed, not emptied
+        let mut cache = ShownCache::default();
+        for i in 0..10 {
+            cache.entries.insert(format!("k{}", i), 100);
+        }
+        cache.entries.insert("newer".to_string(), 200);
+        cache.evict_to(4);
+        assert_eq!(cache.shown_keys().len(), 4);
+        assert!(cache.shown_keys().contains("newer"));
+    }
+
+    #[test]
+    fn test_first_seen_kept_while_fetched() {
+        let mut first_seen = FirstSeen::default();
+        first_seen.entries.insert("gone".to_string(), 0);
+        first_seen.entries.insert("still listed".to_string(), 0);
+        let keys = HashSet::from(["still listed".to_string(), "new".to_string()]);
+        first_seen.record(&keys, Duration::from_secs(3600));
+
+        assert_eq!(first_seen.get("gone"), None);
+        assert_eq!(first_seen.get("still listed"), DateTime::from_timestamp(0, 0));
+        let new = first_seen.get("new").unwrap();
+        assert!(Utc::now() - new < chrono::Duration::minutes(1));
+    }
+
+    #[test]
+    fn test_suppress_reposts() {
+        let window = Duration::from_secs(3600);
+        let mut seen = SeenTitles::default();
+        let posted = |title: &str, url: &str| headline(title, "Test", Some(url));
+        let mut first = vec![
+            posted("Fed raises interest rates by a quarter point", "https://a/1"),
+            posted("Storm closes schools", "https://a/2"),
+        ];
+        assert!(seen.suppress(&mut first, window));
+        assert_eq!(first.len(), 2);
+
+        // The same item again, an edited repost under a new URL, and a new story
+        let mut second = vec![
+            posted("Fed raises interest rates by a quarter point", "https://a/1"),
+            posted("UPDATED: Fed raises interest rates by a quarter-point", "https://a/3"),
+            posted("Storm closes roads", "https://a/4"),
+        ];
+        assert!(seen.suppress(&mut second, window));
+        let urls: Vec<_> = second.iter().filter_map(|h| h.url.as_deref()).collect();
+        assert_eq!(urls, vec!["https://a/1", "https://a/4"]);
+
+        // Outside the window the repost comes back
+        seen.suppress(&mut Vec::new(), Duration::ZERO);
+        let mut third = vec![posted("Fed raises interest rates by a quarter point", "https://b")];
+        seen.suppress(&mut third, window);
+        assert_eq!(third.len(), 1);
     }
 }
@@ -0,0 +1,30 @@
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use std::io::{self, Write};
+
+/// Copy text to the system clipboard via the terminal (OSC 52)
+/// Works over SSH in terminals that support it (kitty, WezTerm, iTerm2, tmux with set-clipboard)
+pub fn copy(text: &str) -> io::Result<()> {
+    let mut stdout = io::stdout();
+    write!(stdout, "{}", osc52(text))?;
+    stdout.flush()
+}
+
+/// Escape setting the clipboard to `text`
+fn osc52(text: &str) -> String {
+    format!("\x1b]52;c;{}\x07", BASE64.encode(text))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_osc52() {
+        assert_eq!(osc52(""), "\x1b]52;c;\x07");
+        assert_eq!(
+            osc52("https://example.com/?a=1&b=2"),
+            "\x1b]52;c;aHR0cHM6Ly9leGFtcGxlLmNvbS8/YT0xJmI9Mg==\x07"
+        );
+    }
+}
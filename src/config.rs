@@ -1,10 +1,22 @@
-use anyhow::{Context, Result};
-use clap::{Parser, ValueEnum};
+use crate::backend::Backend;
+use crate::dates::{schedules, DateStyle, Schedule, ScheduleConfig, TimeSpan};
+use crate::feeds::{self, TitleRule};
+use crate::theme::{style_rules, StyleRule, StyleRuleConfig, Theme, ThemeConfig, ThemeSetting};
+use anyhow::{bail, Context, Result};
+use clap::{Parser, Subcommand, ValueEnum};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
+/// Speeds for the number keys when speed_presets isn't set
+const DEFAULT_SPEED_PRESETS: [u32; 9] = [2, 4, 6, 8, 12, 16, 24, 32, 48];
+
+/// Prefix of environment variables that set config keys, e.g. CHYRON_SPEED=12
+pub const ENV_PREFIX: &str = "CHYRON_";
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize, Default)]
 #[serde(rename_all = "snake_case")]
 pub enum SortMode {
@@ -17,6 +29,71 @@ pub enum SortMode {
     ByDate,
     /// Oldest headlines first
     ByDateAsc,
+    /// Latest arrivals first, by when chyron first fetched them
+    ByFirstSeen,
+}
+
+impl SortMode {
+    /// The mode after this one, wrapping around, for cycling with a key
+    pub fn next(self) -> Self {
+        match self {
+            Self::Random => Self::BySource,
+            Self::BySource => Self::ByDate,
+            Self::ByDate => Self::ByDateAsc,
+            Self::ByDateAsc => Self::ByFirstSeen,
+            Self::ByFirstSeen => Self::Random,
+        }
+    }
+
+    /// Name as written in the config file
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Random => "random",
+            Self::BySource => "by_source",
+            Self::ByDate => "by_date",
+            Self::ByDateAsc => "by_date_asc",
+            Self::ByFirstSeen => "by_first_seen",
+        }
+    }
+}
+
+/// What the crawl shows while every feed is failing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum OfflineMode {
+    /// Keep the last fetched headlines
+    #[default]
+    Cached,
+    /// Replace them with an error banner
+    Banner,
+}
+
+/// What the screen shows during quiet hours
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum QuietDisplay {
+    /// Carry on as usual
+    #[default]
+    Normal,
+    /// Everything drawn faint
+    Dim,
+    /// Nothing at all, with the crawl stopped
+    Blank,
+}
+
+/// Where headlines without a believable date go
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum UndatedMode {
+    /// Dated by when chyron first fetched them, so they sort and age out like the rest
+    #[default]
+    FirstSeen,
+    /// Ahead of dated headlines when sorting by date
+    Top,
+    /// After dated headlines when sorting by date
+    Bottom,
+    /// Left out
+    Exclude,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize, Default)]
@@ -45,6 +122,34 @@ pub enum ClickModifier {
     Alt,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ClickAction {
+    /// Open the headline in the browser
+    #[default]
+    Open,
+    /// Copy the headline URL to the clipboard (OSC 52)
+    Copy,
+    /// Show the full title, source, and URL in the status bar
+    Preview,
+    /// Run click_command with {url}, {title}, {source} substituted
+    Command,
+    /// Save the headline to the bookmarks file
+    Bookmark,
+    /// Hide all headlines from this source for the session
+    Mute,
+    /// Remove this headline from the rotation for the session
+    Skip,
+    /// Hold this headline on a static line above the crawl (again to unpin)
+    Pin,
+    /// Pause, gliding this headline to the middle of the crawl
+    Center,
+    /// Show a menu of these actions
+    Menu,
+    /// Do nothing
+    None,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize, Default)]
 #[serde(rename_all = "snake_case")]
 pub enum RotationMode {
@@ -55,17 +160,248 @@ pub enum RotationMode {
     Continuous,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum LayoutMode {
+    /// A single crawling line
+    #[default]
+    Single,
+    /// Latest headline held on a static row above the crawl, like a TV chyron
+    Broadcast,
+    /// Styled band across the bottom rows: source bug, crawl, and clock
+    LowerThird,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum OutputMode {
+    /// The interactive terminal ticker
+    #[default]
+    Tui,
+    /// One line per headline on stdout with click-to-open action tags, for polybar
+    /// `tail = true` modules
+    Polybar,
+    /// One plain line per headline on stdout, for i3status-rs custom blocks and the like
+    Plain,
+    /// One escaped line per headline cut to --max-width, for GNU screen's backtick command
+    Screen,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum DisplayMode {
+    /// Headlines scroll continuously
+    #[default]
+    Crawl,
+    /// One headline at a time, held still and wrapped over several rows
+    Flip,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportFormat {
+    /// JSON array of headline objects
+    #[default]
+    Json,
+    /// Comma-separated values with a header row
+    Csv,
+    /// Markdown bullet list with links
+    Markdown,
+}
+
+impl ExportFormat {
+    /// File extension used when exporting to a file
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Json => "json",
+            ExportFormat::Csv => "csv",
+            ExportFormat::Markdown => "md",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum BackendKind {
+    /// Poll the feeds in the feeds file
+    #[default]
+    Feeds,
+    /// Unread entries from a Miniflux server
+    Miniflux,
+    /// Unread entries from FreshRSS (Google Reader API)
+    Freshrss,
+    /// Unread entries from Feedbin
+    Feedbin,
+    /// Unread items from the Nextcloud News app
+    Nextcloud,
+}
+
+/// When headlines are marked read on an aggregator backend
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum MarkRead {
+    /// Leave read state alone
+    #[default]
+    Never,
+    /// When opened (click, open key, or click_command)
+    Clicked,
+    /// When opened, or once they've fully scrolled past
+    Scrolled,
+}
+
+/// `[server]` table from the config file: the aggregator backend's address and login
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ServerConfig {
+    pub url: Option<String>,
+    /// API token (Miniflux), or a ClientLogin auth token (FreshRSS)
+    pub token: Option<String>,
+    /// Account name or email (FreshRSS, Feedbin, Nextcloud), with its password
+    pub username: Option<String>,
+    pub password: Option<String>,
+    /// Command printing the token, instead of writing it in the file
+    pub token_cmd: Option<String>,
+    /// Command printing the password, e.g. "pass show feeds/miniflux"
+    pub password_cmd: Option<String>,
+}
+
+impl ServerConfig {
+    /// The login with its secrets filled in: `${NAME}` values read from the environment,
+    /// and `token_cmd` and `password_cmd` run for theirs
+    pub fn resolve_secrets(&self, env: &dyn Fn(&str) -> Option<String>) -> Result<Self> {
+        let secret = |key: &str, value: &Option<String>, command: &Option<String>| {
+            if let Some(command) = command {
+                return secret_command(command)
+                    .with_context(|| format!("[server] {}_cmd failed", key))
+                    .map(Some);
+            }
+            match value.as_deref().and_then(env_reference) {
+                Some(name) => env(name).map(Some).with_context(|| {
+                    format!("[server] {} refers to ${{{}}}, which isn't set", key, name)
+                }),
+                None => Ok(value.clone()),
+            }
+        };
+        Ok(Self {
+            token: secret("token", &self.token, &self.token_cmd)?,
+            password: secret("password", &self.password, &self.password_cmd)?,
+            ..self.clone()
+        })
+    }
+}
+
+/// The environment variable a `${NAME}` setting refers to
+fn env_reference(value: &str) -> Option<&str> {
+    value.strip_prefix("${")?.strip_suffix('}').filter(|name| !name.is_empty())
+}
+
+/// Run a command line (split on whitespace, no shell, leading `~/` expanded) and return
+/// the first line it prints
+fn secret_command(command: &str) -> Result<String> {
+    let mut words = command.split_whitespace();
+    let program = expand_home(words.next().context("command is empty")?)?;
+    let output = std::process::Command::new(&program)
+        .args(words)
+        .output()
+        .with_context(|| format!("Couldn't run {}", program.display()))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let reason = stderr.lines().map(str::trim).find(|l| !l.is_empty());
+        bail!(
+            "{} exited with {}: {}",
+            program.display(),
+            output.status,
+            reason.unwrap_or("no error output")
+        );
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    match stdout.lines().next().map(str::trim_end) {
+        Some(secret) if !secret.is_empty() => Ok(secret.to_string()),
+        _ => bail!("{} printed nothing", program.display()),
+    }
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Fetch feeds and print the current rotation
+    Export {
+        /// Output format (default: export_format from config, or json)
+        #[arg(long, value_enum)]
+        format: Option<ExportFormat>,
+
+        /// Write to this file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Fetch feeds and print the rotation in order, with each headline's source and age, and
+    /// the headlines left out and why
+    Preview,
+    /// Measure ticker tick and render throughput on a synthetic rotation
+    Bench {
+        /// Number of synthetic headlines
+        #[arg(long, default_value_t = 1000)]
+        headlines: usize,
+
+        /// Approximate length of each headline in chars
+        #[arg(long, default_value_t = 80)]
+        length: usize,
+
+        /// Mix wide (CJK/emoji) characters into headlines
+        #[arg(long)]
+        wide: bool,
+
+        /// Frames to measure per stage
+        #[arg(long, default_value_t = 10000)]
+        frames: usize,
+
+        /// Simulated terminal width in columns
+        #[arg(long, default_value_t = 120)]
+        width: u16,
+    },
+    /// Print recently opened headlines, newest first
+    History {
+        /// Maximum number of entries to print
+        #[arg(short = 'n', long, default_value_t = 20)]
+        limit: usize,
+
+        /// Print entries as JSON lines
+        #[arg(long)]
+        json: bool,
+    },
+    /// Print a shell completion script (e.g. `chyron completions zsh > ~/.zfunc/_chyron`)
+    Completions {
+        /// Shell to generate completions for
+        #[arg(value_enum)]
+        shell: clap_complete::Shell,
+    },
+    /// Check terminal, config, storage, network, and clock, and print a pass/fail report
+    Doctor {
+        /// Number of feeds to fetch for the network check
+        #[arg(long, default_value_t = 2)]
+        fetch: usize,
+    },
+    /// Print lifetime headline statistics (fetched, shown, clicked per source)
+    Stats {
+        /// Print the raw stats as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
 #[derive(Parser, Debug)]
 #[command(name = "chyron")]
 #[command(about = "A TUI news ticker displaying RSS headlines like a stock ticker")]
 pub struct CliArgs {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
     /// Path to config file (default: ~/.config/chyron/config.toml)
     #[arg(short, long)]
     pub config: Option<PathBuf>,
 
-    /// Path to feeds file (default: ~/.newsboat/urls or ~/.config/chyron/urls)
+    /// Path to feeds file or directory of them, repeatable (default: ~/.newsboat/urls or
+    /// ~/.config/chyron/urls)
     #[arg(short, long)]
-    pub feeds: Option<PathBuf>,
+    pub feeds: Vec<PathBuf>,
 
     /// Delimiter between headlines
     #[arg(short, long)]
@@ -75,6 +411,18 @@ pub struct CliArgs {
     #[arg(short, long)]
     pub speed: Option<u32>,
 
+    /// Show sub-cell motion with partial blocks at the crawl edges
+    #[arg(long)]
+    pub smooth: bool,
+
+    /// Display: crawl (scrolling line) or flip (one headline at a time)
+    #[arg(long, value_enum)]
+    pub display: Option<DisplayMode>,
+
+    /// Seconds each headline is held in flip display
+    #[arg(long)]
+    pub flip_seconds: Option<u64>,
+
     /// Sorting mode for headlines
     #[arg(long, value_enum)]
     pub sort: Option<SortMode>,
@@ -111,6 +459,10 @@ pub struct CliArgs {
     #[arg(long)]
     pub validate: bool,
 
+    /// Print the effective config, noting where each setting came from, and exit
+    #[arg(long)]
+    pub print_config: bool,
+
     /// Show status bar with controls and state
     #[arg(long)]
     pub status_bar: bool,
@@ -127,50 +479,339 @@ pub struct CliArgs {
     #[arg(long, value_enum)]
     pub rotation: Option<RotationMode>,
 
+    /// Layout: single (one crawl line), broadcast (latest headline above the crawl), lower-third
+    #[arg(long, value_enum)]
+    pub layout: Option<LayoutMode>,
+
+    /// Fullscreen lower-third band at the bottom of the terminal (same as --layout lower-third)
+    #[arg(long, conflicts_with = "layout")]
+    pub lower_third: bool,
+
     /// Date format: strftime string (e.g. "%b %d %H:%M"), "relative", or "none"
     #[arg(long)]
     pub date_format: Option<String>,
+
+    /// Headline template with {source}, {title}, {age}, {date}, {icon}, {index}
+    #[arg(long)]
+    pub template: Option<String>,
+
+    /// Only show headlines that haven't been shown or clicked yet
+    #[arg(long)]
+    pub unread_only: bool,
+
+    /// Append every fetched headline to a JSONL archive
+    #[arg(long)]
+    pub archive: bool,
+
+    /// Replay headlines from an archive file instead of fetching feeds
+    #[arg(long, value_name = "ARCHIVE")]
+    pub replay: Option<PathBuf>,
+
+    /// Replay speed relative to real time (e.g. 60 replays an hour per minute)
+    #[arg(long, default_value_t = 1.0, requires = "replay")]
+    pub speed_factor: f64,
+
+    /// Print headlines as lines for a status bar instead of running the TUI:
+    /// polybar, plain, screen
+    #[arg(long, value_enum, value_name = "MODE")]
+    pub output: Option<OutputMode>,
+
+    /// Cut status bar lines to this many characters (screen output defaults to 80)
+    #[arg(long, value_name = "CHARS", requires = "output")]
+    pub max_width: Option<usize>,
+
+    /// Publish this instance's rotation and crawl position for --follow instances
+    #[arg(long, conflicts_with = "follow")]
+    pub lead: bool,
+
+    /// Mirror a --lead instance instead of fetching, optionally OFFSET headlines ahead
+    /// (negative: behind)
+    #[arg(
+        long,
+        value_name = "OFFSET",
+        num_args = 0..=1,
+        default_missing_value = "0",
+        allow_negative_numbers = true
+    )]
+    pub follow: Option<isize>,
+
+    /// Record all feed responses to a file for reproducible bug reports
+    #[arg(long, value_name = "FILE", conflicts_with = "playback")]
+    pub record: Option<PathBuf>,
+
+    /// Serve feed responses from a recording instead of the network
+    #[arg(long, value_name = "FILE")]
+    pub playback: Option<PathBuf>,
+
+    /// Ask for confirmation in the status bar before opening links
+    #[arg(long)]
+    pub confirm_open: bool,
+
+    /// Run the TUI even when stdout isn't a terminal, without input if there's none to read
+    /// (a kiosk console under systemd)
+    #[arg(long)]
+    pub no_tty_check: bool,
+
+    /// Set the terminal title to the headline entering the crawl
+    #[arg(long)]
+    pub terminal_title: bool,
+
+    /// Screen-share safe mode: titles only, without sources, links, or notifications
+    #[arg(long)]
+    pub safe_mode: bool,
+
+    /// Left-click action: open, copy, preview, command, none
+    #[arg(long, value_enum)]
+    pub click_action: Option<ClickAction>,
 }
 
 /// TOML config file structure
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct FileConfig {
-    pub feeds: Option<String>,
+    pub feeds: Option<FeedsSetting>,
     pub delimiter: Option<String>,
+    /// Delimiters cycled between headlines, overriding delimiter
+    pub delimiters: Option<Vec<String>>,
     pub speed: Option<u32>,
+    /// Speeds for the 1–9 keys, in order
+    pub speed_presets: Option<Vec<u32>>,
+    pub smooth: Option<bool>,
+    pub display: Option<DisplayMode>,
+    pub flip_seconds: Option<u64>,
+    pub wrap_lines: Option<u16>,
     pub sort: Option<SortMode>,
+    pub undated: Option<UndatedMode>,
     pub pause: Option<PauseMode>,
     pub refresh_minutes: Option<u64>,
+    /// Seconds each refresh may come early or late, with fetches spread over as long
+    pub refresh_jitter_seconds: Option<u64>,
+    pub offline: Option<OfflineMode>,
+    /// Daily "HH:MM-HH:MM" span without fetching
+    pub quiet_hours: Option<String>,
+    pub quiet_display: Option<QuietDisplay>,
     pub max_age_hours: Option<u64>,
+    /// Days without a new entry before a feed is flagged stale (0: never)
+    pub stale_after_days: Option<u64>,
     pub max_per_feed: Option<usize>,
     pub max_total: Option<usize>,
     pub show_source: Option<bool>,
     pub status_bar: Option<bool>,
     pub click_modifier: Option<ClickModifier>,
     pub rotation: Option<RotationMode>,
+    pub layout: Option<LayoutMode>,
+    pub logos: Option<bool>,
     pub date_format: Option<String>,
+    pub timezone: Option<String>,
+    pub locale: Option<String>,
+    pub template: Option<String>,
+    pub unread_only: Option<bool>,
+    /// Show headlines about the same story from several sources in one rotating slot
+    pub group_stories: Option<bool>,
+    /// Headlines from other sources kept between two from the same one, where possible
+    pub min_source_gap: Option<usize>,
+    pub blocked_domains: Option<Vec<String>>,
+    /// Hours a title is remembered for spotting reposts under a new URL (0: off)
+    pub duplicate_window_hours: Option<u64>,
+    pub notify: Option<Vec<String>>,
+    pub breaking_tag: Option<String>,
+    pub dnd_message: Option<String>,
+    pub archive: Option<bool>,
+    pub archive_path: Option<String>,
+    pub export_format: Option<ExportFormat>,
+    pub max_shown_entries: Option<usize>,
+    pub memory_budget_mb: Option<u64>,
+    pub fallback_user_agent: Option<String>,
+    pub compression: Option<bool>,
+    pub http2_prior_knowledge: Option<bool>,
+    pub pool_idle_timeout_seconds: Option<u64>,
+    pub pool_max_idle_per_host: Option<usize>,
+    pub dns_cache_seconds: Option<u64>,
+    pub confirm_open: Option<bool>,
+    pub terminal_title: Option<bool>,
+    pub safe_mode: Option<bool>,
+    pub remember_disabled_feeds: Option<bool>,
+    pub click_action: Option<ClickAction>,
+    pub right_click_action: Option<ClickAction>,
+    pub middle_click_action: Option<ClickAction>,
+    pub click_command: Option<String>,
+    /// Icons for the {icon} placeholder, keyed by source name
+    pub icons: Option<HashMap<String, String>>,
+    /// Addresses for host names, used instead of looking them up
+    pub hosts: Option<HashMap<String, String>>,
+    pub theme: Option<ThemeSetting>,
+    /// `[[style]]` rules for headlines by source, feed tag, or title
+    pub style: Option<Vec<StyleRuleConfig>>,
+    /// `[[titles]]` clean-up passes for headline titles by source or feed tag
+    pub titles: Option<Vec<TitleRule>>,
+    /// `[[schedule]]` windows for feeds by source or tag
+    pub schedule: Option<Vec<ScheduleConfig>>,
+    pub backend: Option<BackendKind>,
+    pub server: Option<ServerConfig>,
+    pub mark_read: Option<MarkRead>,
+    pub plugin_timeout_seconds: Option<u64>,
+    /// Config files read first, in order, for this one to override
+    pub include: Option<Vec<String>>,
+}
+
+/// `feeds` in the config file: a feeds file or directory of them, or a list of either
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum FeedsSetting {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl FeedsSetting {
+    fn paths(&self) -> &[String] {
+        match self {
+            Self::One(path) => std::slice::from_ref(path),
+            Self::Many(paths) => paths,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct Config {
-    pub feeds_path: PathBuf,
+    /// Feeds files, and directories of them, read in order
+    pub feeds_paths: Vec<PathBuf>,
     pub delimiter: String,
+    /// Delimiters cycled between headlines (empty: always use delimiter)
+    pub delimiters: Vec<String>,
+    pub theme: Theme,
+    /// Headline styles by source, feed tag, or title, applied in order
+    pub style_rules: Vec<StyleRule>,
+    /// Title clean-up passes by source or feed tag
+    pub title_rules: Vec<TitleRule>,
+    /// When feeds are in the rotation, by source or feed tag
+    pub schedules: Vec<Schedule>,
     pub speed: u32,
+    /// Speeds for the 1–9 keys
+    pub speed_presets: Vec<u32>,
+    /// Sub-cell motion cues at the crawl edges
+    pub smooth: bool,
+    pub display: DisplayMode,
+    /// How long each headline is held in flip display
+    pub flip_interval: Duration,
+    /// Most rows a headline may wrap over when shown on its own
+    pub wrap_lines: u16,
     pub sort: SortMode,
+    /// Handling of headlines without a date (or with one in the future)
+    pub undated: UndatedMode,
     pub pause_mode: PauseMode,
     pub refresh_interval: Duration,
+    /// How far each refresh may move from the interval, and how long its fetches spread over
+    pub refresh_jitter: Duration,
+    /// What to show when every feed fails
+    pub offline: OfflineMode,
+    /// Daily span in which feeds aren't fetched
+    pub quiet_hours: Option<TimeSpan>,
+    /// What the screen shows during quiet hours
+    pub quiet_display: QuietDisplay,
     pub max_age: Duration,
+    /// How long a feed may go without a new entry before it's flagged stale (zero: never)
+    pub stale_after: Duration,
     pub max_per_feed: usize,
     pub max_total: usize,
     pub show_source: bool,
     pub validate_only: bool,
+    /// Skip the terminal check and tolerate having no input (--no-tty-check)
+    pub no_tty_check: bool,
     pub show_status_bar: bool,
     pub click_modifier: ClickModifier,
     pub rotation: RotationMode,
+    pub layout: LayoutMode,
+    /// Source logos in the lower-third bug, on terminals that draw images
+    pub logos: bool,
     /// Date format: strftime format string, "relative", or "none"
     pub date_format: Option<String>,
-    /// Path to config file for reloading
+    /// Zone and locale for dates and clocks (default: system local time and LC_TIME)
+    pub dates: DateStyle,
+    /// Headline template; None keeps the default "[source] date - title"
+    pub template: Option<String>,
+    /// Icons for the {icon} placeholder, keyed by source name
+    pub icons: HashMap<String, String>,
+    /// Only show headlines that haven't been shown or clicked yet
+    pub unread_only: bool,
+    /// Fold headlines about the same story from several sources into one rotating slot
+    pub group_stories: bool,
+    /// Headlines from other sources kept between two from the same one, where there are
+    /// enough (0: off)
+    pub min_source_gap: usize,
+    /// Headlines linking to these domains (or their subdomains) are dropped
+    pub blocked_domains: Vec<String>,
+    /// How long titles are remembered to drop reposts of them under new URLs (zero: off)
+    pub duplicate_window: Duration,
+    /// New headlines whose titles mention any of these raise a terminal notification
+    pub notify: Vec<String>,
+    /// Feed tag marking priority feeds, whose new headlines take over the ticker row
+    pub breaking_tag: Option<String>,
+    /// Shown instead of the ticker in do-not-disturb mode (None: a clock)
+    pub dnd_message: Option<String>,
+    /// Archive file for fetched headlines, if archiving is enabled
+    pub archive_path: Option<PathBuf>,
+    /// Format used by the export key and `chyron export`
+    pub export_format: ExportFormat,
+    /// Archive file to replay instead of fetching feeds
+    pub replay: Option<PathBuf>,
+    /// Replay speed relative to real time
+    pub speed_factor: f64,
+    /// Where headlines go: the TUI, or lines on stdout for a status bar
+    pub output: OutputMode,
+    /// Longest status bar line, in characters
+    pub max_width: Option<usize>,
+    /// Publish the rotation for followers
+    pub lead: bool,
+    /// Mirror a leader's rotation, this many headlines ahead of it
+    pub follow: Option<isize>,
+    /// File to record feed responses to
+    pub record: Option<PathBuf>,
+    /// Recording to serve feed responses from
+    pub playback: Option<PathBuf>,
+    /// Max entries in the shown cache before the least recently marked are evicted
+    pub max_shown_entries: usize,
+    /// Bytes headlines and caches may take up before the oldest are evicted (None: no cap)
+    pub memory_budget: Option<usize>,
+    /// User-Agent to retry with once when a feed answers 403
+    pub fallback_user_agent: Option<String>,
+    /// Ask for gzip, deflate, and brotli compressed responses
+    pub compression: bool,
+    /// Speak HTTP/2 from the start instead of negotiating it, for servers known to support it
+    pub http2_prior_knowledge: bool,
+    /// How long idle connections are kept open for reuse
+    pub pool_idle_timeout: Duration,
+    /// Idle connections kept open per host (None: no limit)
+    pub pool_max_idle_per_host: Option<usize>,
+    /// How long host name lookups are cached (zero: not cached)
+    pub dns_cache: Duration,
+    /// Addresses for host names, used instead of looking them up
+    pub hosts: HashMap<String, IpAddr>,
+    /// Ask "Open …? [y/N]" before launching the browser
+    pub confirm_open: bool,
+    /// Keep the terminal title on the headline entering the crawl
+    pub terminal_title: bool,
+    /// Show titles only, without sources, links, or notifications (screen sharing)
+    pub safe_mode: bool,
+    /// Keep feeds switched off in the feed list disabled across sessions
+    pub remember_disabled_feeds: bool,
+    /// Aggregator server to show unread entries from instead of polling feeds
+    pub backend: Option<Backend>,
+    /// When to mark headlines read on the backend
+    pub mark_read: MarkRead,
+    /// How long a `plugin:` command may run before it is killed
+    pub plugin_timeout: Duration,
+    /// Action for left, right, and middle clicks on a headline
+    pub click_action: ClickAction,
+    pub right_click_action: ClickAction,
+    pub middle_click_action: ClickAction,
+    /// Command template for the "command" click action
+    pub click_command: Option<String>,
+    /// Path to the config file, reloaded if it exists
     pub config_path: Option<PathBuf>,
+    /// Keys from the config file and environment as last read, to tell what a reload
+    /// changed
+    pub loaded: toml::Table,
+    /// Problems that didn't stop the config loading, like unknown keys
+    pub warnings: Vec<String>,
 }
 
 impl Config {
@@ -180,28 +821,32 @@ impl Config {
             get_config_dir().join("config.toml")
         });
 
-        let file_config = if config_path.exists() {
-            let content = fs::read_to_string(&config_path)
-                .with_context(|| format!("Failed to read config file: {}", config_path.display()))?;
-            toml::from_str(&content)
-                .with_context(|| format!("Failed to parse config file: {}", config_path.display()))?
-        } else {
-            FileConfig::default()
-        };
+        let mut layers = Layers::read(&config_path)?;
+        let file_config = layers.merged()?;
 
         // CLI args override file config, file config overrides defaults
-        let feeds_path = if let Some(path) = args.feeds {
-            path
-        } else if let Some(path) = &file_config.feeds {
-            PathBuf::from(path)
+        let feeds_paths = if !args.feeds.is_empty() {
+            args.feeds
+        } else if let Some(feeds) = &file_config.feeds {
+            feeds.paths().iter().map(|path| expand_home(path)).collect::<Result<_>>()?
         } else {
-            discover_feeds_file()?
+            vec![discover_feeds_file()?]
         };
 
+        // A delimiter given on the command line beats a list from the file
+        let delimiters = match &args.delimiter {
+            Some(_) => Vec::new(),
+            None => file_config.delimiters.clone().unwrap_or_default(),
+        };
         let delimiter = args.delimiter
             .or(file_config.delimiter)
             .unwrap_or_else(|| " ••• ".to_string());
 
+        let theme_config = file_config.theme.clone().map(ThemeConfig::from).unwrap_or_default();
+        let mut theme = Theme::from_config(&theme_config)?;
+        layers.warnings.extend(theme.guard_contrast(&theme_config));
+        let hosts = parse_hosts(file_config.hosts.clone().unwrap_or_default())?;
+
         let speed = args.speed
             .or(file_config.speed)
             .unwrap_or(8);
@@ -255,73 +900,265 @@ impl Config {
             .or(file_config.rotation)
             .unwrap_or_default();
 
+        let layout = args
+            .lower_third
+            .then_some(LayoutMode::LowerThird)
+            .or(args.layout)
+            .or(file_config.layout)
+            .unwrap_or_default();
+
         let date_format = args.date_format
             .or(file_config.date_format)
             .and_then(|s| if s == "none" { None } else { Some(s) });
 
-        let config_path_for_reload = if config_path.exists() {
-            Some(config_path)
+        let dates =
+            DateStyle::parse(file_config.timezone.as_deref(), file_config.locale.as_deref())?;
+        let quiet_hours = file_config.quiet_hours.as_deref().map(TimeSpan::parse).transpose()?;
+
+        let backend_kind = file_config.backend.unwrap_or_default();
+        // Secret commands only run when there's a server to log in to
+        let server = if backend_kind == BackendKind::Feeds {
+            ServerConfig::default()
+        } else {
+            let server = file_config.server.clone().unwrap_or_default();
+            server.resolve_secrets(&|name| std::env::var(name).ok())?
+        };
+        let backend = Backend::from_config(backend_kind, &server)?;
+
+        let unread_only = args.unread_only || file_config.unread_only.unwrap_or(false);
+
+        let archive_path = if args.archive || file_config.archive.unwrap_or(false) {
+            Some(match &file_config.archive_path {
+                Some(path) => expand_home(path)?,
+                None => get_data_dir().join("archive.jsonl"),
+            })
         } else {
             None
         };
 
+        // Kept even if missing, so the file can be created later and reloaded
+        let config_path_for_reload = Some(config_path);
+
         Ok(Self {
-            feeds_path,
+            feeds_paths,
             delimiter,
+            delimiters,
+            theme,
+            style_rules: style_rules(file_config.style.as_deref().unwrap_or_default())?,
+            title_rules: file_config.titles.unwrap_or_default(),
+            schedules: schedules(file_config.schedule.as_deref().unwrap_or_default())?,
             speed,
+            speed_presets: file_config
+                .speed_presets
+                .unwrap_or_else(|| DEFAULT_SPEED_PRESETS.to_vec()),
+            smooth: args.smooth || file_config.smooth.unwrap_or(false),
+            display: args.display.or(file_config.display).unwrap_or_default(),
+            flip_interval: Duration::from_secs(
+                args.flip_seconds.or(file_config.flip_seconds).unwrap_or(8).max(1),
+            ),
+            wrap_lines: file_config.wrap_lines.unwrap_or(3).max(1),
             sort,
+            undated: file_config.undated.unwrap_or_default(),
             pause_mode,
             refresh_interval: Duration::from_secs(refresh_minutes * 60),
+            refresh_jitter: Duration::from_secs(file_config.refresh_jitter_seconds.unwrap_or(0)),
+            offline: file_config.offline.unwrap_or_default(),
+            quiet_hours,
+            quiet_display: file_config.quiet_display.unwrap_or_default(),
             max_age: Duration::from_secs(max_age_hours * 3600),
+            stale_after: Duration::from_secs(file_config.stale_after_days.unwrap_or(14) * 86400),
             max_per_feed,
             max_total,
             show_source,
             validate_only: args.validate,
+            no_tty_check: args.no_tty_check,
             show_status_bar,
             click_modifier,
             rotation,
+            layout,
+            logos: file_config.logos.unwrap_or(false),
             date_format,
+            dates,
+            template: args.template.or(file_config.template),
+            icons: file_config.icons.unwrap_or_default(),
+            unread_only,
+            group_stories: file_config.group_stories.unwrap_or(false),
+            min_source_gap: file_config.min_source_gap.unwrap_or(0),
+            blocked_domains: file_config.blocked_domains.unwrap_or_default(),
+            duplicate_window: Duration::from_secs(
+                file_config.duplicate_window_hours.unwrap_or(0) * 3600,
+            ),
+            notify: file_config.notify.unwrap_or_default(),
+            breaking_tag: file_config.breaking_tag,
+            dnd_message: file_config.dnd_message,
+            archive_path,
+            export_format: file_config.export_format.unwrap_or_default(),
+            replay: args.replay,
+            speed_factor: args.speed_factor,
+            output: args.output.unwrap_or_default(),
+            max_width: args.max_width,
+            lead: args.lead,
+            follow: args.follow,
+            record: args.record,
+            playback: args.playback,
+            max_shown_entries: file_config.max_shown_entries.unwrap_or(10_000),
+            memory_budget: file_config.memory_budget_mb.map(|mb| mb as usize * 1024 * 1024),
+            fallback_user_agent: file_config.fallback_user_agent,
+            compression: file_config.compression.unwrap_or(true),
+            http2_prior_knowledge: file_config.http2_prior_knowledge.unwrap_or(false),
+            pool_idle_timeout: Duration::from_secs(
+                file_config.pool_idle_timeout_seconds.unwrap_or(90),
+            ),
+            pool_max_idle_per_host: file_config.pool_max_idle_per_host,
+            dns_cache: Duration::from_secs(file_config.dns_cache_seconds.unwrap_or(300)),
+            hosts,
+            confirm_open: args.confirm_open || file_config.confirm_open.unwrap_or(false),
+            terminal_title: args.terminal_title || file_config.terminal_title.unwrap_or(false),
+            safe_mode: args.safe_mode || file_config.safe_mode.unwrap_or(false),
+            remember_disabled_feeds: file_config.remember_disabled_feeds.unwrap_or(false),
+            backend,
+            mark_read: file_config.mark_read.unwrap_or_default(),
+            plugin_timeout: Duration::from_secs(
+                file_config.plugin_timeout_seconds.unwrap_or(30).max(1),
+            ),
+            click_action: args.click_action.or(file_config.click_action).unwrap_or_default(),
+            right_click_action: file_config.right_click_action.unwrap_or(ClickAction::Menu),
+            middle_click_action: file_config.middle_click_action.unwrap_or(ClickAction::None),
+            click_command: file_config.click_command,
             config_path: config_path_for_reload,
+            loaded: layers.table(),
+            warnings: layers.warnings,
         })
     }
 
+    /// The feeds file that edits go to (the feed manager, U, and setup): the first local one
+    pub fn feeds_file(&self) -> Option<PathBuf> {
+        let files = feeds::feeds_files(&self.feeds_paths).ok()?;
+        files.into_iter().find(|file| feeds::remote_list(file).is_none())
+    }
+
+    /// Whether a feeds list is fetched from a server, and so can change between refreshes
+    pub fn remote_feeds(&self) -> bool {
+        self.feeds_paths.iter().any(|path| feeds::remote_list(path).is_some())
+    }
+
+    /// The feeds paths, for messages
+    pub fn feeds_label(&self) -> String {
+        let paths: Vec<String> = self.feeds_paths.iter().map(|p| p.display().to_string()).collect();
+        paths.join(", ")
+    }
+
     /// Reload config from file, updating only file-configurable settings
-    /// Returns Ok(true) if config was reloaded, Ok(false) if no config file
-    pub fn reload(&mut self) -> Result<bool> {
+    /// Returns the keys whose values changed since the last read, or None if there's no
+    /// config file
+    pub fn reload(&mut self) -> Result<Option<Vec<String>>> {
         let config_path = match &self.config_path {
             Some(p) => p.clone(),
-            None => return Ok(false),
+            None => return Ok(None),
         };
 
         if !config_path.exists() {
-            return Ok(false);
+            return Ok(None);
         }
 
-        let content = fs::read_to_string(&config_path)
-            .with_context(|| format!("Failed to read config file: {}", config_path.display()))?;
-        let file_config: FileConfig = toml::from_str(&content)
-            .with_context(|| format!("Failed to parse config file: {}", config_path.display()))?;
+        let layers = Layers::read(&config_path)?;
+        let file_config = layers.merged()?;
+        let table = layers.table();
+        let changed = table
+            .iter()
+            .filter(|(key, value)| self.loaded.get(*key) != Some(*value))
+            .map(|(key, _)| key.clone())
+            .collect();
+
+        // Parse whatever can fail before changing anything, so a bad value leaves every
+        // setting, and the table the next reload compares against, as it was
+        let mut warnings = layers.warnings;
+        let theme = match file_config.theme.map(ThemeConfig::from) {
+            Some(config) => {
+                let mut theme = Theme::from_config(&config)?;
+                warnings.extend(theme.guard_contrast(&config));
+                Some(theme)
+            }
+            None => None,
+        };
+        let style = file_config.style.as_deref().map(style_rules).transpose()?;
+        let schedule = file_config.schedule.as_deref().map(schedules).transpose()?;
+        let quiet_hours = file_config.quiet_hours.as_deref().map(TimeSpan::parse).transpose()?;
+        let (timezone, locale) = (file_config.timezone.as_deref(), file_config.locale.as_deref());
+        let dates = (timezone.is_some() || locale.is_some())
+            .then(|| DateStyle::parse(timezone, locale))
+            .transpose()?;
+        self.loaded = table;
+        self.warnings = warnings;
 
         // Update settings from file (CLI args were applied at startup and take precedence,
         // but we can't track which settings came from CLI vs defaults, so we reload all)
         if let Some(delimiter) = file_config.delimiter {
             self.delimiter = delimiter;
         }
+        if let Some(delimiters) = file_config.delimiters {
+            self.delimiters = delimiters;
+        }
+        if let Some(theme) = theme {
+            self.theme = theme;
+        }
+        if let Some(style_rules) = style {
+            self.style_rules = style_rules;
+        }
+        if let Some(titles) = file_config.titles {
+            self.title_rules = titles;
+        }
+        if let Some(schedules) = schedule {
+            self.schedules = schedules;
+        }
         if let Some(speed) = file_config.speed {
             self.speed = speed;
         }
+        if let Some(speed_presets) = file_config.speed_presets {
+            self.speed_presets = speed_presets;
+        }
+        if let Some(smooth) = file_config.smooth {
+            self.smooth = smooth;
+        }
+        if let Some(display) = file_config.display {
+            self.display = display;
+        }
+        if let Some(flip_seconds) = file_config.flip_seconds {
+            self.flip_interval = Duration::from_secs(flip_seconds.max(1));
+        }
+        if let Some(wrap_lines) = file_config.wrap_lines {
+            self.wrap_lines = wrap_lines.max(1);
+        }
         if let Some(sort) = file_config.sort {
             self.sort = sort;
         }
+        if let Some(undated) = file_config.undated {
+            self.undated = undated;
+        }
         if let Some(pause) = file_config.pause {
             self.pause_mode = pause;
         }
         if let Some(refresh_minutes) = file_config.refresh_minutes {
             self.refresh_interval = Duration::from_secs(refresh_minutes * 60);
         }
+        if let Some(seconds) = file_config.refresh_jitter_seconds {
+            self.refresh_jitter = Duration::from_secs(seconds);
+        }
+        if let Some(offline) = file_config.offline {
+            self.offline = offline;
+        }
+        if let Some(span) = quiet_hours {
+            self.quiet_hours = Some(span);
+        }
+        if let Some(quiet_display) = file_config.quiet_display {
+            self.quiet_display = quiet_display;
+        }
         if let Some(max_age_hours) = file_config.max_age_hours {
             self.max_age = Duration::from_secs(max_age_hours * 3600);
         }
+        if let Some(days) = file_config.stale_after_days {
+            self.stale_after = Duration::from_secs(days * 86400);
+        }
         if let Some(max_per_feed) = file_config.max_per_feed {
             self.max_per_feed = max_per_feed;
         }
@@ -340,14 +1177,435 @@ impl Config {
         if let Some(rotation) = file_config.rotation {
             self.rotation = rotation;
         }
+        if let Some(layout) = file_config.layout {
+            self.layout = layout;
+        }
+        if let Some(logos) = file_config.logos {
+            self.logos = logos;
+        }
         if let Some(date_format) = file_config.date_format {
             self.date_format = if date_format == "none" { None } else { Some(date_format) };
         }
+        if let Some(dates) = dates {
+            self.dates = dates;
+        }
+        if let Some(mark_read) = file_config.mark_read {
+            self.mark_read = mark_read;
+        }
+        if let Some(seconds) = file_config.plugin_timeout_seconds {
+            self.plugin_timeout = Duration::from_secs(seconds.max(1));
+        }
+        if let Some(template) = file_config.template {
+            self.template = Some(template);
+        }
+        if let Some(icons) = file_config.icons {
+            self.icons = icons;
+        }
+        if let Some(unread_only) = file_config.unread_only {
+            self.unread_only = unread_only;
+        }
+        if let Some(group_stories) = file_config.group_stories {
+            self.group_stories = group_stories;
+        }
+        if let Some(min_source_gap) = file_config.min_source_gap {
+            self.min_source_gap = min_source_gap;
+        }
+        if let Some(blocked_domains) = file_config.blocked_domains {
+            self.blocked_domains = blocked_domains;
+        }
+        if let Some(mb) = file_config.memory_budget_mb {
+            self.memory_budget = Some(mb as usize * 1024 * 1024);
+        }
+        if let Some(hours) = file_config.duplicate_window_hours {
+            self.duplicate_window = Duration::from_secs(hours * 3600);
+        }
+        if let Some(notify) = file_config.notify {
+            self.notify = notify;
+        }
+        if let Some(tag) = file_config.breaking_tag {
+            self.breaking_tag = Some(tag);
+        }
+        if let Some(message) = file_config.dnd_message {
+            self.dnd_message = Some(message);
+        }
+        if let Some(export_format) = file_config.export_format {
+            self.export_format = export_format;
+        }
+        if let Some(confirm_open) = file_config.confirm_open {
+            self.confirm_open = confirm_open;
+        }
+        if let Some(terminal_title) = file_config.terminal_title {
+            self.terminal_title = terminal_title;
+        }
+        if let Some(safe_mode) = file_config.safe_mode {
+            self.safe_mode = safe_mode;
+        }
+        if let Some(remember) = file_config.remember_disabled_feeds {
+            self.remember_disabled_feeds = remember;
+        }
+        if let Some(click_action) = file_config.click_action {
+            self.click_action = click_action;
+        }
+        if let Some(action) = file_config.right_click_action {
+            self.right_click_action = action;
+        }
+        if let Some(action) = file_config.middle_click_action {
+            self.middle_click_action = action;
+        }
+        if let Some(click_command) = file_config.click_command {
+            self.click_command = Some(click_command);
+        }
+
+        Ok(Some(changed))
+    }
+
+    /// The settings in effect, in config file form; `raw` (the file and environment
+    /// layers) supplies what isn't kept resolved: theme, style rules, server, and dates
+    pub fn effective(&self, raw: FileConfig) -> FileConfig {
+        // References to environment variables stay, being no secret themselves
+        let redact = |secret: Option<String>| {
+            secret.map(|s| match env_reference(&s) {
+                Some(_) => s,
+                None => "<redacted>".to_string(),
+            })
+        };
+        let server = raw.server.map(|server| ServerConfig {
+            token: redact(server.token),
+            password: redact(server.password),
+            ..server
+        });
+        FileConfig {
+            feeds: Some({
+                let mut paths: Vec<String> =
+                    self.feeds_paths.iter().map(|p| p.display().to_string()).collect();
+                match paths.len() {
+                    1 => FeedsSetting::One(paths.remove(0)),
+                    _ => FeedsSetting::Many(paths),
+                }
+            }),
+            delimiter: Some(self.delimiter.clone()),
+            delimiters: Some(self.delimiters.clone()).filter(|d| !d.is_empty()),
+            speed: Some(self.speed),
+            speed_presets: Some(self.speed_presets.clone()),
+            smooth: Some(self.smooth),
+            display: Some(self.display),
+            flip_seconds: Some(self.flip_interval.as_secs()),
+            wrap_lines: Some(self.wrap_lines),
+            sort: Some(self.sort),
+            undated: Some(self.undated),
+            pause: Some(self.pause_mode),
+            refresh_minutes: Some(self.refresh_interval.as_secs() / 60),
+            refresh_jitter_seconds: Some(self.refresh_jitter.as_secs()),
+            offline: Some(self.offline),
+            quiet_hours: self.quiet_hours.map(|q| q.to_string()),
+            quiet_display: Some(self.quiet_display),
+            max_age_hours: Some(self.max_age.as_secs() / 3600),
+            stale_after_days: Some(self.stale_after.as_secs() / 86400),
+            max_per_feed: Some(self.max_per_feed),
+            max_total: Some(self.max_total),
+            show_source: Some(self.show_source),
+            status_bar: Some(self.show_status_bar),
+            click_modifier: Some(self.click_modifier),
+            rotation: Some(self.rotation),
+            layout: Some(self.layout),
+            logos: Some(self.logos),
+            date_format: Some(self.date_format.clone().unwrap_or_else(|| "none".to_string())),
+            timezone: raw.timezone,
+            locale: raw.locale,
+            template: self.template.clone(),
+            unread_only: Some(self.unread_only),
+            group_stories: Some(self.group_stories),
+            min_source_gap: Some(self.min_source_gap),
+            blocked_domains: Some(self.blocked_domains.clone()),
+            duplicate_window_hours: Some(self.duplicate_window.as_secs() / 3600),
+            notify: Some(self.notify.clone()),
+            breaking_tag: self.breaking_tag.clone(),
+            dnd_message: self.dnd_message.clone(),
+            archive: Some(self.archive_path.is_some()),
+            archive_path: self.archive_path.as_ref().map(|p| p.display().to_string()),
+            export_format: Some(self.export_format),
+            max_shown_entries: Some(self.max_shown_entries),
+            memory_budget_mb: self.memory_budget.map(|bytes| (bytes / (1024 * 1024)) as u64),
+            fallback_user_agent: self.fallback_user_agent.clone(),
+            compression: Some(self.compression),
+            http2_prior_knowledge: Some(self.http2_prior_knowledge),
+            pool_idle_timeout_seconds: Some(self.pool_idle_timeout.as_secs()),
+            pool_max_idle_per_host: self.pool_max_idle_per_host,
+            dns_cache_seconds: Some(self.dns_cache.as_secs()),
+            confirm_open: Some(self.confirm_open),
+            terminal_title: Some(self.terminal_title),
+            safe_mode: Some(self.safe_mode),
+            remember_disabled_feeds: Some(self.remember_disabled_feeds),
+            click_action: Some(self.click_action),
+            right_click_action: Some(self.right_click_action),
+            middle_click_action: Some(self.middle_click_action),
+            click_command: self.click_command.clone(),
+            icons: Some(self.icons.clone()).filter(|i| !i.is_empty()),
+            hosts: Some(self.hosts.iter().map(|(host, ip)| (host.clone(), ip.to_string())))
+                .filter(|_| !self.hosts.is_empty())
+                .map(HashMap::from_iter),
+            theme: raw.theme,
+            style: raw.style,
+            titles: raw.titles,
+            schedule: raw.schedule,
+            backend: raw.backend,
+            server,
+            mark_read: Some(self.mark_read),
+            plugin_timeout_seconds: Some(self.plugin_timeout.as_secs()),
+            include: None,
+        }
+    }
+}
+
+/// Config keys from the sources beneath the command line
+#[derive(Debug, Default)]
+pub struct Layers {
+    /// Keys in the config file and the files it includes (none if there isn't one)
+    pub file: toml::Table,
+    /// File each key in `file` was last set by
+    pub origins: HashMap<String, PathBuf>,
+    /// Keys from CHYRON_* environment variables, which override the file
+    pub env: toml::Table,
+    /// Keys nothing reads, most likely typos
+    pub warnings: Vec<String>,
+}
+
+impl Layers {
+    pub fn read(path: &Path) -> Result<Self> {
+        let mut layers = Layers {
+            env: env_table(std::env::vars()),
+            ..Default::default()
+        };
+        if path.exists() {
+            layers.read_file(path, &mut Vec::new())?;
+        }
+        for (key, near) in unknown_keys(&layers.env, FileConfig::fields(), "") {
+            let name = |key: &str| format!("{}{}", ENV_PREFIX, key.to_uppercase());
+            layers.warnings.push(format!(
+                "Unknown setting {}{}",
+                name(&key),
+                did_you_mean(near.map(|near| name(&near)))
+            ));
+        }
+        Ok(layers)
+    }
+
+    /// Lay a config file over what's been read so far, after the files it includes
+    /// (in order, each over the last); `chain` holds the files including this one
+    fn read_file(&mut self, path: &Path, chain: &mut Vec<PathBuf>) -> Result<()> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config file: {}", path.display()))?;
+        // Parsed as FileConfig first so mistakes are reported with their line
+        let mut table: toml::Table = toml::from_str::<FileConfig>(&content)
+            .and_then(|_| toml::from_str(&content))
+            .with_context(|| format!("Failed to parse config file: {}", path.display()))?;
+
+        for (key, near) in unknown_file_keys(&table) {
+            self.warnings.push(format!(
+                "Unknown key {} in {}{}",
+                key,
+                path.display(),
+                did_you_mean(near)
+            ));
+        }
+
+        let canonical = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        if chain.contains(&canonical) {
+            bail!("Config file {} includes itself", path.display());
+        }
+        chain.push(canonical);
+        let includes = match table.remove("include") {
+            Some(value) => Vec::<String>::deserialize(value)
+                .with_context(|| format!("include in {} must be a list of paths", path.display()))?,
+            None => Vec::new(),
+        };
+        for include in includes {
+            let included = resolve_include(path, &include)?;
+            self.read_file(&included, chain)
+                .with_context(|| format!("Included from {}", path.display()))?;
+        }
+        chain.pop();
+
+        for key in table.keys() {
+            self.origins.insert(key.clone(), path.to_path_buf());
+        }
+        merge_tables(&mut self.file, table);
+        Ok(())
+    }
+
+    /// Keys from the file with the environment's laid over them
+    pub fn table(&self) -> toml::Table {
+        let mut table = self.file.clone();
+        table.extend(self.env.clone());
+        table
+    }
+
+    /// The file's settings with the environment's laid over them
+    pub fn merged(&self) -> Result<FileConfig> {
+        toml::Value::Table(self.table())
+            .try_into()
+            .with_context(|| format!("Invalid config from {}* environment variables", ENV_PREFIX))
+    }
+}
+
+impl FileConfig {
+    /// Every key the config file can set
+    fn fields() -> &'static [&'static str] {
+        field_names::<FileConfig>()
+    }
+}
+
+/// Names of the fields a config struct reads, taken from its Deserialize impl
+fn field_names<'de, T: Deserialize<'de>>() -> &'static [&'static str] {
+    use serde::de::{Error, Visitor};
+
+    /// Deserializer that only records the field list it's asked for
+    struct Fields<'a>(&'a mut &'static [&'static str]);
+
+    impl<'de> serde::Deserializer<'de> for Fields<'_> {
+        type Error = serde::de::value::Error;
+
+        fn deserialize_any<V: Visitor<'de>>(self, _: V) -> Result<V::Value, Self::Error> {
+            Err(Error::custom("not a struct"))
+        }
+
+        fn deserialize_struct<V: Visitor<'de>>(
+            self,
+            _name: &'static str,
+            fields: &'static [&'static str],
+            _visitor: V,
+        ) -> Result<V::Value, Self::Error> {
+            *self.0 = fields;
+            Err(Error::custom("fields recorded"))
+        }
+
+        serde::forward_to_deserialize_any! {
+            bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes
+            byte_buf option unit unit_struct newtype_struct seq tuple tuple_struct map enum
+            identifier ignored_any
+        }
+    }
+
+    let mut fields: &'static [&'static str] = &[];
+    let _ = T::deserialize(Fields(&mut fields));
+    fields
+}
+
+/// Keys in a config file table that no setting reads, down into [theme], [server], and
+/// [[style]], [[titles]], and [[schedule]], each with the known key it's closest to
+fn unknown_file_keys(table: &toml::Table) -> Vec<(String, Option<String>)> {
+    let mut unknown = unknown_keys(table, FileConfig::fields(), "");
+    if let Some(toml::Value::Table(theme)) = table.get("theme") {
+        unknown.extend(unknown_keys(theme, field_names::<ThemeConfig>(), "theme."));
+    }
+    if let Some(toml::Value::Table(server)) = table.get("server") {
+        unknown.extend(unknown_keys(server, field_names::<ServerConfig>(), "server."));
+    }
+    if let Some(toml::Value::Array(rules)) = table.get("style") {
+        for rule in rules.iter().filter_map(toml::Value::as_table) {
+            unknown.extend(unknown_keys(rule, field_names::<StyleRuleConfig>(), "style."));
+        }
+    }
+    if let Some(toml::Value::Array(rules)) = table.get("titles") {
+        for rule in rules.iter().filter_map(toml::Value::as_table) {
+            unknown.extend(unknown_keys(rule, field_names::<TitleRule>(), "titles."));
+        }
+    }
+    if let Some(toml::Value::Array(entries)) = table.get("schedule") {
+        for entry in entries.iter().filter_map(toml::Value::as_table) {
+            unknown.extend(unknown_keys(entry, field_names::<ScheduleConfig>(), "schedule."));
+        }
+    }
+    unknown
+}
 
-        Ok(true)
+/// Keys of `table` not in `known`, prefixed, with the nearest known key if it's close
+/// enough to be a likely typo
+fn unknown_keys(
+    table: &toml::Table,
+    known: &[&str],
+    prefix: &str,
+) -> Vec<(String, Option<String>)> {
+    table
+        .keys()
+        .filter(|key| !known.contains(&key.as_str()))
+        .map(|key| {
+            let near = known
+                .iter()
+                .map(|known| (strsim::levenshtein(key, known), known))
+                .filter(|(distance, known)| *distance <= (known.len() / 3).max(2))
+                .min_by_key(|(distance, _)| *distance)
+                .map(|(_, known)| format!("{}{}", prefix, known));
+            (format!("{}{}", prefix, key), near)
+        })
+        .collect()
+}
+
+/// Parse `[hosts]` addresses
+fn parse_hosts(hosts: HashMap<String, String>) -> Result<HashMap<String, IpAddr>> {
+    hosts
+        .into_iter()
+        .map(|(host, ip)| {
+            let ip = ip
+                .trim()
+                .parse()
+                .with_context(|| format!("Invalid address for {} in [hosts]: {}", host, ip))?;
+            Ok((host, ip))
+        })
+        .collect()
+}
+
+fn did_you_mean(near: Option<String>) -> String {
+    near.map(|near| format!(" (did you mean {}?)", near)).unwrap_or_default()
+}
+
+/// Path of an included file: `~/` is the home directory, and relative paths start from
+/// the including file's directory
+fn resolve_include(from: &Path, include: &str) -> Result<PathBuf> {
+    let path = expand_home(include)?;
+    Ok(match from.parent() {
+        Some(dir) if path.is_relative() => dir.join(path),
+        _ => path,
+    })
+}
+
+/// A path with a leading `~/` standing for the home directory
+pub(crate) fn expand_home(path: &str) -> Result<PathBuf> {
+    Ok(match path.strip_prefix("~/") {
+        Some(rest) => dirs_next::home_dir()
+            .context("Could not determine home directory")?
+            .join(rest),
+        None => PathBuf::from(path),
+    })
+}
+
+/// Lay `over` on `base`, merging tables key by key so an override can change part of one
+fn merge_tables(base: &mut toml::Table, over: toml::Table) {
+    for (key, value) in over {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(existing)), toml::Value::Table(value)) => {
+                merge_tables(existing, value)
+            }
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
     }
 }
 
+/// Config keys set by CHYRON_* variables, e.g. CHYRON_MAX_TOTAL=50; values are read as
+/// TOML where they parse (numbers, booleans, arrays) and as strings otherwise
+fn env_table(vars: impl Iterator<Item = (String, String)>) -> toml::Table {
+    vars.filter_map(|(name, value)| {
+        let key = name.strip_prefix(ENV_PREFIX)?.to_lowercase();
+        let parsed = toml::from_str::<toml::Table>(&format!("value = {}", value))
+            .ok()
+            .and_then(|mut table| table.remove("value"));
+        Some((key, parsed.unwrap_or(toml::Value::String(value))))
+    })
+    .collect()
+}
+
 fn get_config_dir() -> PathBuf {
     // Always use ~/.config/chyron for consistency across platforms
     dirs_next::home_dir()
@@ -356,6 +1614,15 @@ fn get_config_dir() -> PathBuf {
         .join("chyron")
 }
 
+/// Directory for persistent data such as the headline archive
+pub fn get_data_dir() -> PathBuf {
+    dirs_next::home_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(".local")
+        .join("share")
+        .join("chyron")
+}
+
 /// Discover feeds file in priority order:
 /// 1. ~/.newsboat/urls
 /// 2. ~/.config/chyron/urls
@@ -382,27 +1649,79 @@ fn discover_feeds_file() -> Result<PathBuf> {
 pub fn example_config() -> &'static str {
     r#"# Chyron configuration
 
-# Path to feeds file (default: ~/.newsboat/urls or ~/.config/chyron/urls)
+# Other config files to read first, in order (later ones override earlier ones, and
+# this file overrides them all); relative paths start from this file's directory
+# include = ["work-feeds.toml", "theme-dark.toml"]
+
+# Path to feeds file (default: ~/.newsboat/urls or ~/.config/chyron/urls). Also a
+# directory, whose files are read in name order, or a list of files and directories, to
+# keep work and personal feeds apart; the feed manager (F) and U edit the first file.
+# An http(s) URL is a centrally managed list, fetched at each refresh and saved to
+# ~/.cache/chyron/feeds/ for when the server can't be reached
 # feeds = "~/.config/chyron/urls"
+# feeds = ["~/.config/chyron/urls.d/", "~/work/feeds"]
+# feeds = ["https://intranet.example/chyron/urls", "~/.config/chyron/urls"]
 
 # Delimiter between headlines
 delimiter = " ••• "
 
+# Or cycle through several (overrides delimiter; include any spacing you want)
+# delimiters = [" ••• ", " ◆ ", " ——— "]
+
 # Scroll speed in characters per second
 speed = 8
 
-# Sort mode: random, by_source, by_date, by_date_asc
+# Speeds for the number keys: 1 picks the first, 2 the second, and so on (up to 9)
+speed_presets = [2, 4, 6, 8, 12, 16, 24, 32, 48]
+
+# Smoother motion at low speeds: partial blocks at the crawl edges show sub-cell
+# progress, and characters step exactly on cell boundaries
+smooth = false
+
+# Display: crawl (scrolling line) or flip (one headline at a time, held for
+# flip_seconds). Long headlines shown on their own wrap over up to wrap_lines rows
+display = "crawl"
+flip_seconds = 8
+wrap_lines = 3
+
+# Sort mode: random, by_source, by_date, by_date_asc, by_first_seen (newest arrivals first)
 sort = "by_date"
 
+# Headlines without a date (or dated over an hour ahead): first_seen dates them by when
+# chyron first fetched them; top or bottom puts them ahead of or after dated ones when
+# sorting by date; exclude leaves them out
+undated = "first_seen"
+
 # Pause mode: hover (pause on mouse hover), focus (pause when window focused), never
 pause = "hover"
 
-# Feed refresh interval in minutes
+# Feed refresh interval in minutes. A feed whose server asks for longer, with Retry-After
+# (on 429/503) or Cache-Control max-age, is skipped until then and keeps its headlines
 refresh_minutes = 5
 
-# Maximum age of headlines in hours
+# Move each refresh up to this many seconds earlier or later (at most half the interval),
+# and spread its fetches over as long, so many instances don't hit servers all at once
+# refresh_jitter_seconds = 60
+
+# When every feed fails (network down): cached keeps the last headlines crawling, banner
+# replaces them with an error banner. Either way the status bar says "offline since 12:04",
+# feeds are retried every 30 seconds, and "back online" shows once they answer again
+offline = "cached"
+
+# Stop fetching feeds between these times each day (in the timezone below), picking up
+# again once they're over, and what the screen does meanwhile: normal, dim, or blank
+# (the crawl stops too)
+# quiet_hours = "23:00-07:00"
+# quiet_display = "normal"
+
+# Maximum age of headlines in hours. If every feed's headlines are older (usually a
+# wrong clock), the newest are shown anyway, with a warning
 max_age_hours = 24
 
+# Flag feeds whose newest entry is older than this many days ("stale: nothing in 30 days")
+# in the feed list (f) and --validate, to spot dead subscriptions; 0 turns it off
+stale_after_days = 14
+
 # Maximum headlines per feed
 max_per_feed = 10
 
@@ -412,7 +1731,7 @@ max_total = 100
 # Show source prefix on headlines [Source Name]
 show_source = true
 
-# Show status bar at bottom
+# Show status bar at bottom, naming the headline at the crawl's left edge ("now: ...")
 status_bar = false
 
 # Modifier key required to open links: none, ctrl, shift, alt
@@ -422,12 +1741,296 @@ click_modifier = "none"
 # Rotation mode: fair (prioritize unshown headlines), continuous (simple loop)
 rotation = "fair"
 
+# Layout: single (one crawl line), broadcast (latest headline held above the crawl),
+# lower_third (band across the bottom rows with source bug and clock, for kiosks/streams)
+layout = "single"
+
+# Logos in the lower-third source bug: each site's icon, drawn on terminals with the kitty
+# or iTerm2 image protocol (kitty, Ghostty, WezTerm, iTerm2). Elsewhere, and for sites
+# without a PNG icon, the source's entry in [icons] shows instead
+# logos = true
+
 # Date format before headlines: strftime format, "relative", or "none"
 # Examples: "%b %d" (Dec 09), "%H:%M" (15:45), "%b %d %H:%M" (Dec 09 15:45)
 date_format = "none"
+
+# Zone for dates and the lower-third clock: "local", "UTC", or a name like
+# "Europe/Berlin". Locale for month and day names (default: from LC_TIME/LANG)
+# timezone = "local"
+# locale = "de_DE"
+
+# Headline template (overrides show_source): {source}, {title}, {age} (e.g. 3h),
+# {date} (per date_format), {icon} (from [icons] below), {index} (position)
+# template = "{icon} {source}: {title} ({age})"
+
+# Only show headlines that haven't been shown or clicked yet
+unread_only = false
+
+# Drop headlines linking to these domains (subdomains included), from any feed
+blocked_domains = []
+
+# Drop headlines whose title closely matches one seen within this many hours under
+# another URL, so edited reposts don't come round again (0: off)
+# duplicate_window_hours = 48
+
+# Show headlines about the same developing story from several sources (sharing the
+# significant words of their titles) as one slot, "Story: … (5 sources)", that moves
+# on to the next source's headline each pass, so one big event doesn't take over the
+# crawl. g lists the stories with their headlines
+group_stories = false
+
+# Headlines from other sources kept between two from the same source, so one feed's
+# headlines don't run together (1: never adjacent). When there aren't enough others,
+# they're spread as far as they go. Doesn't apply with sort = "by_source" (0: off)
+min_source_gap = 0
+
+# New headlines mentioning any of these words (case-insensitive) raise an OSC 9
+# terminal notification (kitty, WezTerm, iTerm2, Windows Terminal), which works over SSH
+notify = []
+
+# Feeds given this tag in the feeds file are priority feeds: each new headline from one
+# interrupts the crawl with a full-width BREAKING slate, then joins the rotation. At most
+# three slates queue up; any more just join the rotation
+# breaking_tag = "breaking"
+
+# What the do-not-disturb key (b) shows in place of the ticker, which also holds back
+# notifications and breaking slates until it's pressed again (default: a clock)
+# dnd_message = "Back in five"
+
+# Append every fetched headline to a JSONL archive
+archive = false
+# archive_path = "~/.local/share/chyron/archive.jsonl"
+
+# Format for the export key and `chyron export`: json, csv, markdown
+export_format = "json"
+
+# Max headlines remembered as shown; least recently marked are evicted first
+max_shown_entries = 10000
+
+# Cap in MB on what headlines and caches take up (roughly), for long-running kiosks.
+# Over it, shown keys are trimmed to max_shown_entries, then the oldest headlines are
+# dropped. The stats overlay (s) shows current usage
+# memory_budget_mb = 16
+
+# Retry feeds that answer 403 once with this User-Agent (some publishers block
+# unknown agents but allow browser-like ones)
+# fallback_user_agent = "Mozilla/5.0 (X11; Linux x86_64; rv:128.0) Gecko/20100101 Firefox/128.0"
+
+# Ask for gzip, deflate, and brotli compressed responses; turn off behind proxies that
+# mangle them (feeds then fail with "Couldn't decompress the response")
+compression = true
+
+# Connection reuse: HTTP/2 is negotiated where servers offer it, and idle connections
+# are kept this long so feeds sharing a host skip fresh TLS handshakes. Raise the
+# timeout past refresh_minutes to keep them between refreshes too
+pool_idle_timeout_seconds = 90
+# pool_max_idle_per_host = 4
+# Speak HTTP/2 without negotiating it (every feed server must support it)
+http2_prior_knowledge = false
+
+# Seconds host name lookups are cached (0: look up every time). When a lookup fails,
+# the last answer is used however old, so flaky DNS doesn't blank feeds out
+dns_cache_seconds = 300
+
+# Ask "Open …? [y/N]" in the status bar before launching the browser
+confirm_open = false
+
+# Set the terminal (tab) title to the headline entering the crawl, to glance at
+# from the tab bar when the pane is small or hidden
+terminal_title = false
+
+# Screen-share safe mode, for presentations: headlines show titles only, without sources,
+# links, logos, or notifications, so feed subscriptions don't end up on the projector.
+# Also toggled with S
+safe_mode = false
+
+# Feeds switched off in the feed list (f, then space) stay off after a restart
+remember_disabled_feeds = false
+
+# Where headlines come from: feeds (poll the feeds file), or an aggregator that
+# already polls them: miniflux, freshrss, feedbin, nextcloud (see [server] below)
+backend = "feeds"
+
+# Mark headlines read on the backend server: never, clicked (when opened), or
+# scrolled (when opened or once fully scrolled past)
+mark_read = "never"
+
+# Seconds a plugin: source in the feeds file may run before it is killed
+plugin_timeout_seconds = 30
+
+# Click actions: open, copy (OSC 52 clipboard), preview, command, bookmark,
+# mute (hide source for the session), skip, pin, center (pause with the headline
+# glided to the middle), menu, none
+click_action = "open"
+right_click_action = "menu"
+middle_click_action = "none"
+
+# Command for the "command" action; {url}, {title}, {source} are substituted
+# Runs directly (no shell), split on whitespace
+# click_command = "mpv {url}"
+
+# Crawl styles: modifiers (bold, dim, italic, underlined, reversed) and colors
+# (names like dark_gray, indexes like 244, or #rrggbb), "on <color>" for background.
+# import starts from a base16 color scheme; the styles below then override it.
+# theme = "base16:PATH" on its own imports a scheme without a table
+[theme]
+# import = "base16:~/.config/base16/ocean.yaml"
+delimiter = "dim"
+# headline = "white"
+# hover = "cyan underlined"
+# badge = "bold black on yellow"
+# status = "dark_gray"
+# The ticker row as a band across the terminal (single and broadcast layouts): its
+# style, columns kept clear at each end, and characters over the crawl's ends
+# (outermost first, mirrored on the right, in the delimiter style)
+# band = "on #1c1c1c"
+# padding = 2
+# fade = "░▒▓"
+# Text whose contrast with its background (where both colors are set) is under this
+# WCAG ratio is reported as hard to read (0 = don't check); adjust_contrast brightens or
+# darkens it instead
+# min_contrast = 4.5
+# adjust_contrast = false
+
+# Styles for particular headlines, matched on source name, feed tag, or a title
+# regular expression (every one given must match); later rules override earlier ones
+# [[style]]
+# source = "SecurityFeed"
+# style = "bold red"
+#
+# [[style]]
+# title = '(?i)\bAI\b'
+# style = "italic"
+
+# Clean-up passes for headline titles, by source name or feed tag (neither: every
+# feed): collapse_whitespace, strip_site_name (a trailing " - Site Name" of up to four
+# words), and title_case (for headlines written entirely in capitals). transforms lists
+# more passes, run in order after those: any of the above, strip_emoji, strip_prefix
+# (leading labels like "[Sponsored]"), and sentence_case
+# [[titles]]
+# tag = "shouty"
+# strip_site_name = true
+# title_case = true
+# [[titles]]
+# source = "Deals Daily"
+# transforms = ["strip_prefix", "strip_emoji", "sentence_case"]
+
+# Times of day (and days of the week) when feeds with a source name or tag are in the
+# rotation; outside them their headlines are left out. Hours may run past midnight
+# [[schedule]]
+# tag = "markets"
+# hours = "09:00-17:30"
+# days = "mon-fri"
+
+# Icons for the {icon} template placeholder, keyed by source name
+[icons]
+# "Hacker News" = "Y"
+
+# Addresses for host names, used instead of DNS (e.g. to pin intranet feeds)
+[hosts]
+# "news.intranet.example" = "10.0.0.5"
+
+# Aggregator server for the miniflux, freshrss, feedbin, and nextcloud backends.
+# Miniflux takes an API token; FreshRSS a username and API password (or a ClientLogin
+# token); Feedbin the account email and password (url defaults to
+# https://api.feedbin.com); Nextcloud the Nextcloud username and an app password.
+# Rather than writing secrets here, token and password can name an environment variable
+# ("${MINIFLUX_TOKEN}"), or token_cmd and password_cmd a command printing them
+[server]
+# url = "https://rss.example.com"
+# token = "…"
+# username = "me"
+# password = "…"
+# token_cmd = "pass show feeds/miniflux"
+# password_cmd = "secret-tool lookup service freshrss"
 "#
 }
 
+/// Config with fixed defaults for unit tests
+#[cfg(test)]
+pub fn test_config() -> Config {
+    Config {
+        feeds_paths: Vec::new(),
+        delimiter: " | ".to_string(),
+        delimiters: Vec::new(),
+        theme: Theme::default(),
+        style_rules: Vec::new(),
+        title_rules: Vec::new(),
+        schedules: Vec::new(),
+        smooth: false,
+        display: DisplayMode::Crawl,
+        flip_interval: Duration::from_secs(8),
+        wrap_lines: 3,
+        speed: 10,
+        speed_presets: DEFAULT_SPEED_PRESETS.to_vec(),
+        sort: SortMode::ByDate,
+        undated: UndatedMode::FirstSeen,
+        pause_mode: PauseMode::Hover,
+        refresh_interval: Duration::from_secs(300),
+        refresh_jitter: Duration::ZERO,
+        offline: OfflineMode::Cached,
+        quiet_hours: None,
+        quiet_display: QuietDisplay::Normal,
+        max_age: Duration::from_secs(86400),
+        stale_after: Duration::from_secs(14 * 86400),
+        max_per_feed: 10,
+        max_total: 100,
+        show_source: false,
+        validate_only: false,
+        no_tty_check: false,
+        show_status_bar: false,
+        click_modifier: ClickModifier::None,
+        rotation: RotationMode::Continuous,
+        layout: LayoutMode::Single,
+        logos: false,
+        date_format: None,
+        dates: DateStyle::default(),
+        template: None,
+        icons: HashMap::new(),
+        unread_only: false,
+        group_stories: false,
+        min_source_gap: 0,
+        blocked_domains: Vec::new(),
+        duplicate_window: Duration::ZERO,
+        notify: Vec::new(),
+        breaking_tag: None,
+        dnd_message: None,
+        archive_path: None,
+        export_format: ExportFormat::Json,
+        replay: None,
+        speed_factor: 1.0,
+        output: OutputMode::Tui,
+        max_width: None,
+        lead: false,
+        follow: None,
+        record: None,
+        playback: None,
+        max_shown_entries: 10_000,
+        memory_budget: None,
+        fallback_user_agent: None,
+        compression: true,
+        http2_prior_knowledge: false,
+        pool_idle_timeout: Duration::from_secs(90),
+        pool_max_idle_per_host: None,
+        dns_cache: Duration::from_secs(300),
+        hosts: HashMap::new(),
+        confirm_open: false,
+        terminal_title: false,
+        safe_mode: false,
+        remember_disabled_feeds: false,
+        backend: None,
+        mark_read: MarkRead::Never,
+        plugin_timeout: Duration::from_secs(30),
+        click_action: ClickAction::Open,
+        right_click_action: ClickAction::Menu,
+        middle_click_action: ClickAction::None,
+        click_command: None,
+        config_path: None,
+        loaded: toml::Table::new(),
+        warnings: Vec::new(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -449,11 +2052,162 @@ mod tests {
             speed = 12
             sort = "random"
             pause = "focus"
+            rotation = "fair"
+            click_modifier = "ctrl"
         "#;
         let config: FileConfig = toml::from_str(toml).unwrap();
         assert_eq!(config.delimiter, Some(" | ".to_string()));
         assert_eq!(config.speed, Some(12));
         assert_eq!(config.sort, Some(SortMode::Random));
         assert_eq!(config.pause, Some(PauseMode::Focus));
+        assert_eq!(config.rotation, Some(RotationMode::Fair));
+        assert_eq!(config.click_modifier, Some(ClickModifier::Ctrl));
+
+        let args = CliArgs::parse_from(["chyron", "--rotation", "fair", "--click-modifier", "alt"]);
+        assert_eq!(args.rotation, Some(RotationMode::Fair));
+        assert_eq!(args.click_modifier, Some(ClickModifier::Alt));
+    }
+
+    #[test]
+    fn test_parse_theme() {
+        let config: FileConfig = toml::from_str(r#"theme = "base16:ocean.yaml""#).unwrap();
+        let theme = ThemeConfig::from(config.theme.unwrap());
+        assert_eq!(theme.import.as_deref(), Some("base16:ocean.yaml"));
+
+        let config: FileConfig = toml::from_str("[theme]\nhover = \"bold\"").unwrap();
+        let theme = ThemeConfig::from(config.theme.unwrap());
+        assert_eq!((theme.import, theme.hover.as_deref()), (None, Some("bold")));
+    }
+
+    #[test]
+    fn test_env_layer() {
+        let vars = [
+            ("CHYRON_SPEED", "12"),
+            ("CHYRON_SORT", "random"),
+            ("CHYRON_BLOCKED_DOMAINS", r#"["ads.example"]"#),
+            ("HOME", "/home/someone"),
+        ];
+        let layers = Layers {
+            file: toml::from_str("speed = 4\nmax_total = 20").unwrap(),
+            env: env_table(vars.iter().map(|(k, v)| (k.to_string(), v.to_string()))),
+            ..Default::default()
+        };
+        assert_eq!(layers.env.len(), 3);
+        let config = layers.merged().unwrap();
+        assert_eq!(config.speed, Some(12));
+        assert_eq!(config.max_total, Some(20));
+        assert_eq!(config.sort, Some(SortMode::Random));
+        assert_eq!(config.blocked_domains, Some(vec!["ads.example".to_string()]));
+    }
+
+    #[test]
+    fn test_include() {
+        let dir = tempfile::tempdir().unwrap();
+        let write = |name: &str, content: &str| fs::write(dir.path().join(name), content).unwrap();
+        write("shared.toml", "speed = 4\nmax_total = 20\n[theme]\ndelimiter = \"dim\"\n");
+        write("dark.toml", "max_total = 30\n[theme]\nhover = \"bold\"\n");
+        write(
+            "config.toml",
+            "include = [\"shared.toml\", \"dark.toml\"]\nspeed = 12\n[theme]\nbadge = \"red\"\n",
+        );
+
+        let layers = Layers::read(&dir.path().join("config.toml")).unwrap();
+        assert_eq!(layers.file["speed"].as_integer(), Some(12));
+        assert_eq!(layers.file["max_total"].as_integer(), Some(30));
+        assert_eq!(layers.file["theme"].as_table().unwrap().len(), 3);
+        assert_eq!(layers.origins["max_total"], dir.path().join("dark.toml"));
+        assert!(!layers.file.contains_key("include"));
+
+        write("shared.toml", "include = [\"config.toml\"]");
+        assert!(Layers::read(&dir.path().join("config.toml")).is_err());
+    }
+
+    #[test]
+    fn test_reload_reports_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        let mut config = test_config();
+        config.config_path = Some(path.clone());
+        assert_eq!(config.reload().unwrap(), None);
+
+        fs::write(&path, "speed = 12\nsort = \"random\"\n").unwrap();
+        assert_eq!(config.reload().unwrap().unwrap(), ["sort", "speed"]);
+        fs::write(&path, "speed = 12\nsort = \"by_source\"\n").unwrap();
+        assert_eq!(config.reload().unwrap().unwrap(), ["sort"]);
+        assert_eq!(config.sort, SortMode::BySource);
+
+        // A bad value changes nothing, so fixing it reports everything that then changed
+        fs::write(&path, "speed = 20
+quiet_hours = \"late\"\n").unwrap();
+        assert!(config.reload().is_err());
+        assert_eq!(config.speed, 12);
+        fs::write(&path, "speed = 20
+quiet_hours = \"22:00-07:00\"\n").unwrap();
+        assert_eq!(config.reload().unwrap().unwrap(), ["quiet_hours", "speed"]);
+        assert_eq!(config.speed, 20);
+    }
+
+    #[test]
+    fn test_server_secrets() {
+        let env = |name: &str| (name == "MINIFLUX_TOKEN").then(|| "from-env".to_string());
+        let server = ServerConfig {
+            token: Some("${MINIFLUX_TOKEN}".to_string()),
+            password: Some("plain".to_string()),
+            ..Default::default()
+        };
+        let resolved = server.resolve_secrets(&env).unwrap();
+        assert_eq!(resolved.token.as_deref(), Some("from-env"));
+        assert_eq!(resolved.password.as_deref(), Some("plain"));
+
+        let server = ServerConfig {
+            token: Some("${UNSET_TOKEN}".to_string()),
+            ..Default::default()
+        };
+        let error = server.resolve_secrets(&env).unwrap_err();
+        assert!(format!("{:#}", error).contains("${UNSET_TOKEN}"));
+
+        #[cfg(unix)]
+        {
+            let server = ServerConfig {
+                password: Some("ignored".to_string()),
+                password_cmd: Some("echo hunter2".to_string()),
+                ..Default::default()
+            };
+            let resolved = server.resolve_secrets(&env).unwrap();
+            assert_eq!(resolved.password.as_deref(), Some("hunter2"));
+            let server = ServerConfig {
+                token_cmd: Some("false".to_string()),
+                ..Default::default()
+            };
+            assert!(server.resolve_secrets(&env).is_err());
+        }
+    }
+
+    #[test]
+    fn test_unknown_keys() {
+        let table: toml::Table = toml::from_str(
+            "refresh_minuets = 5\nspeed = 8\nfrobnicate = true\n[theme]\nhovr = \"bold\"",
+        )
+        .unwrap();
+        let unknown = unknown_file_keys(&table);
+        assert_eq!(
+            unknown,
+            [
+                ("frobnicate".to_string(), None),
+                ("refresh_minuets".to_string(), Some("refresh_minutes".to_string())),
+                ("theme.hovr".to_string(), Some("theme.hover".to_string())),
+            ]
+        );
+        assert!(FileConfig::fields().contains(&"plugin_timeout_seconds"));
+    }
+
+    #[test]
+    fn test_parse_blocked_domains() {
+        let toml = r#"blocked_domains = ["example-spam.com", "ads.example.org"]"#;
+        let config: FileConfig = toml::from_str(toml).unwrap();
+        assert_eq!(
+            config.blocked_domains,
+            Some(vec!["example-spam.com".to_string(), "ads.example.org".to_string()])
+        );
     }
 }
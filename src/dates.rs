@@ -0,0 +1,331 @@
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Datelike, Local, Locale, NaiveDateTime, NaiveTime, Utc, Weekday};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::time::Duration;
+
+/// Zone that timestamps are shown in
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Zone {
+    /// The system's local time
+    Local,
+    /// An IANA zone such as "Europe/Berlin", or "UTC"
+    Named(Tz),
+}
+
+/// How timestamps are rendered: in which zone, with whose month and day names
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DateStyle {
+    pub zone: Zone,
+    pub locale: Locale,
+}
+
+impl Default for DateStyle {
+    fn default() -> Self {
+        Self {
+            zone: Zone::Local,
+            locale: system_locale(),
+        }
+    }
+}
+
+impl DateStyle {
+    /// Build from the `timezone` and `locale` settings; None means the system's
+    pub fn parse(timezone: Option<&str>, locale: Option<&str>) -> Result<Self> {
+        let zone = match timezone {
+            None => Zone::Local,
+            Some(name) if name.eq_ignore_ascii_case("local") => Zone::Local,
+            Some(name) if name.eq_ignore_ascii_case("utc") => Zone::Named(Tz::UTC),
+            Some(name) => Zone::Named(name.parse::<Tz>().ok().with_context(|| {
+                format!("Unknown timezone: {} (try e.g. \"Europe/Berlin\")", name)
+            })?),
+        };
+        let locale = match locale {
+            None => system_locale(),
+            Some(name) => parse_locale(name)
+                .with_context(|| format!("Unknown locale: {} (try e.g. \"de_DE\")", name))?,
+        };
+        Ok(Self { zone, locale })
+    }
+
+    /// Format a timestamp with a strftime format, in this zone and locale
+    pub fn format(&self, date: DateTime<Utc>, format: &str) -> String {
+        match self.zone {
+            Zone::Local => {
+                date.with_timezone(&Local).format_localized(format, self.locale).to_string()
+            }
+            Zone::Named(tz) => {
+                date.with_timezone(&tz).format_localized(format, self.locale).to_string()
+            }
+        }
+    }
+
+    /// The current time, formatted
+    pub fn now(&self, format: &str) -> String {
+        self.format(Utc::now(), format)
+    }
+
+    /// Calendar days from `earlier` to `later` as seen on a wall clock in this zone
+    pub fn days_between(&self, earlier: DateTime<Utc>, later: DateTime<Utc>) -> i64 {
+        let (a, b) = match self.zone {
+            Zone::Local => (
+                earlier.with_timezone(&Local).date_naive(),
+                later.with_timezone(&Local).date_naive(),
+            ),
+            Zone::Named(tz) => (
+                earlier.with_timezone(&tz).date_naive(),
+                later.with_timezone(&tz).date_naive(),
+            ),
+        };
+        (b - a).num_days()
+    }
+
+    /// Date and time of a timestamp on a wall clock in this zone
+    pub fn wall_clock(&self, date: DateTime<Utc>) -> NaiveDateTime {
+        match self.zone {
+            Zone::Local => date.with_timezone(&Local).naive_local(),
+            Zone::Named(tz) => date.with_timezone(&tz).naive_local(),
+        }
+    }
+}
+
+/// A daily span of wall-clock time such as "23:00-07:00", which may run past midnight
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeSpan {
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+}
+
+impl TimeSpan {
+    pub fn parse(span: &str) -> Result<Self> {
+        let (start, end) = span
+            .split_once('-')
+            .with_context(|| format!("Invalid hours: {} (try \"23:00-07:00\")", span))?;
+        let time = |t: &str| {
+            NaiveTime::parse_from_str(t.trim(), "%H:%M")
+                .with_context(|| format!("Invalid time: {} (expected HH:MM)", t))
+        };
+        Ok(Self {
+            start: time(start)?,
+            end: time(end)?,
+        })
+    }
+
+    /// Whether a wall-clock time falls in the span (start included, end not)
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        if self.start <= self.end {
+            self.start <= time && time < self.end
+        } else {
+            time >= self.start || time < self.end
+        }
+    }
+}
+
+impl fmt::Display for TimeSpan {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}-{}", self.start.format("%H:%M"), self.end.format("%H:%M"))
+    }
+}
+
+/// Days of the week such as "mon-fri" or "sat,sun"; ranges may wrap ("fri-mon")
+fn parse_days(days: &str) -> Result<Vec<Weekday>> {
+    let day = |d: &str| {
+        d.trim().parse::<Weekday>().ok().with_context(|| format!("Invalid day: {}", d.trim()))
+    };
+    let mut parsed = Vec::new();
+    for part in days.split(',') {
+        let (first, last) = match part.split_once('-') {
+            Some((first, last)) => (day(first)?, day(last)?),
+            None => (day(part)?, day(part)?),
+        };
+        let mut d = first;
+        parsed.push(d);
+        while d != last {
+            d = d.succ();
+            parsed.push(d);
+        }
+    }
+    Ok(parsed)
+}
+
+/// `[[schedule]]` entry from the config file: when feeds with this source name or tag are
+/// in the rotation
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ScheduleConfig {
+    pub source: Option<String>,
+    pub tag: Option<String>,
+    /// Daily "HH:MM-HH:MM" window
+    pub hours: Option<String>,
+    /// Days of the week, e.g. "mon-fri"
+    pub days: Option<String>,
+}
+
+/// A resolved `[[schedule]]` window
+#[derive(Debug, Clone)]
+pub struct Schedule {
+    source: Option<String>,
+    tag: Option<String>,
+    hours: Option<TimeSpan>,
+    days: Option<Vec<Weekday>>,
+}
+
+impl Schedule {
+    /// Whether the schedule covers headlines from this source, fetched from a feed with
+    /// these tags
+    fn matches(&self, source: &str, tags: &[String]) -> bool {
+        self.source.as_ref().is_none_or(|s| s.eq_ignore_ascii_case(source))
+            && self.tag.as_ref().is_none_or(|t| tags.iter().any(|tag| t.eq_ignore_ascii_case(tag)))
+    }
+
+    /// Whether the window is open at a wall-clock time (days are those the time falls on)
+    pub fn open(&self, at: NaiveDateTime) -> bool {
+        self.hours.is_none_or(|hours| hours.contains(at.time()))
+            && self.days.as_ref().is_none_or(|days| days.contains(&at.weekday()))
+    }
+}
+
+/// Resolve `[[schedule]]` entries
+pub fn schedules(configs: &[ScheduleConfig]) -> Result<Vec<Schedule>> {
+    configs
+        .iter()
+        .enumerate()
+        .map(|(i, config)| {
+            let schedule = || -> Result<Schedule> {
+                if config.source.is_none() && config.tag.is_none() {
+                    bail!("needs source or tag to match on");
+                }
+                if config.hours.is_none() && config.days.is_none() {
+                    bail!("needs hours or days");
+                }
+                Ok(Schedule {
+                    source: config.source.clone(),
+                    tag: config.tag.clone(),
+                    hours: config.hours.as_deref().map(TimeSpan::parse).transpose()?,
+                    days: config.days.as_deref().map(parse_days).transpose()?,
+                })
+            };
+            schedule().with_context(|| format!("Invalid [[schedule]] {}", i + 1))
+        })
+        .collect()
+}
+
+/// Whether headlines from this source and feed belong in the rotation at a wall-clock time:
+/// every schedule covering them must be open
+pub fn scheduled(
+    schedules: &[Schedule],
+    source: &str,
+    tags: &[String],
+    at: NaiveDateTime,
+) -> bool {
+    schedules.iter().filter(|s| s.matches(source, tags)).all(|s| s.open(at))
+}
+
+/// When headlines start being older than `max_age`: the earliest date there is if max_age
+/// reaches back further than chrono can count, so nothing is too old
+pub fn age_cutoff(max_age: Duration) -> DateTime<Utc> {
+    chrono::Duration::from_std(max_age)
+        .ok()
+        .and_then(|age| Utc::now().checked_sub_signed(age))
+        .unwrap_or(DateTime::<Utc>::MIN_UTC)
+}
+
+/// "de_DE", "de_DE.UTF-8", or "de_DE@euro"; C and POSIX map to the default
+fn parse_locale(name: &str) -> Option<Locale> {
+    let name = name.split(['.', '@']).next().unwrap_or_default();
+    match name {
+        "" | "C" | "POSIX" => Some(Locale::POSIX),
+        _ => Locale::try_from(name).ok(),
+    }
+}
+
+/// Locale for dates from LC_ALL, LC_TIME, or LANG, falling back to POSIX (English)
+fn system_locale() -> Locale {
+    ["LC_ALL", "LC_TIME", "LANG"]
+        .iter()
+        .find_map(|var| std::env::var(var).ok().filter(|v| !v.is_empty()))
+        .and_then(|name| parse_locale(&name))
+        .unwrap_or(Locale::POSIX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_zone_and_locale() {
+        let style = DateStyle::parse(Some("Asia/Tokyo"), Some("de_DE.UTF-8")).unwrap();
+        let date = Utc.with_ymd_and_hms(2024, 3, 9, 20, 30, 0).unwrap();
+        assert_eq!(style.format(date, "%a %d %b %H:%M"), "So 10 Mär 05:30");
+        assert_eq!(style.days_between(date, date + chrono::Duration::hours(4)), 0);
+        assert_eq!(style.days_between(date, date + chrono::Duration::hours(20)), 1);
+
+        let utc = DateStyle::parse(Some("utc"), Some("C")).unwrap();
+        assert_eq!(utc.format(date, "%a %H:%M"), "Sat 20:30");
+        assert_eq!(utc.days_between(date, date + chrono::Duration::hours(4)), 1);
+        assert_eq!(utc.days_between(date, date + chrono::Duration::hours(3)), 0);
+
+        assert!(DateStyle::parse(Some("Mars/Olympus"), None).is_err());
+        assert!(DateStyle::parse(None, Some("xx_YY")).is_err());
+        assert_eq!(style.wall_clock(date).format("%a %H:%M").to_string(), "Sun 05:30");
+    }
+
+    #[test]
+    fn test_age_cutoff() {
+        let cutoff = age_cutoff(Duration::from_secs(3600));
+        assert!((Utc::now() - cutoff - chrono::Duration::hours(1)).num_seconds().abs() < 5);
+        assert_eq!(age_cutoff(Duration::from_secs(u64::MAX)), DateTime::<Utc>::MIN_UTC);
+        // Fits a chrono::Duration, but not once taken from now
+        let never = Duration::from_secs(100_000_000_000 * 3600);
+        assert_eq!(age_cutoff(never), DateTime::<Utc>::MIN_UTC);
+    }
+
+    #[test]
+    fn test_quiet_hours() {
+        let at = |t: &str| NaiveTime::parse_from_str(t, "%H:%M").unwrap();
+        let night = TimeSpan::parse("23:00-07:00").unwrap();
+        assert!(night.contains(at("23:00")));
+        assert!(night.contains(at("03:15")));
+        assert!(!night.contains(at("07:00")));
+        assert!(!night.contains(at("12:00")));
+        assert_eq!(night.to_string(), "23:00-07:00");
+
+        let lunch = TimeSpan::parse("12:00 - 13:30").unwrap();
+        assert!(lunch.contains(at("12:45")));
+        assert!(!lunch.contains(at("23:30")));
+
+        assert!(TimeSpan::parse("23:00").is_err());
+        assert!(TimeSpan::parse("11pm-7am").is_err());
+    }
+
+    #[test]
+    fn test_schedules() {
+        let long_weekend = [Weekday::Fri, Weekday::Sat, Weekday::Sun, Weekday::Mon];
+        assert_eq!(parse_days("fri-mon").unwrap(), long_weekend);
+        assert_eq!(parse_days("sat, sun").unwrap(), [Weekday::Sat, Weekday::Sun]);
+        assert!(parse_days("mon-funday").is_err());
+
+        let config = ScheduleConfig {
+            tag: Some("markets".to_string()),
+            hours: Some("09:00-17:30".to_string()),
+            days: Some("mon-fri".to_string()),
+            ..Default::default()
+        };
+        let windows = schedules(&[config]).unwrap();
+        let at = |t: &str| NaiveDateTime::parse_from_str(t, "%Y-%m-%d %H:%M").unwrap();
+        let markets = ["Markets".to_string()];
+        // 2024-03-08 is a Friday
+        assert!(scheduled(&windows, "Wire", &markets, at("2024-03-08 09:00")));
+        assert!(!scheduled(&windows, "Wire", &markets, at("2024-03-08 17:30")));
+        assert!(!scheduled(&windows, "Wire", &markets, at("2024-03-09 12:00")));
+        assert!(scheduled(&windows, "Wire", &[], at("2024-03-09 12:00")));
+
+        assert!(schedules(&[ScheduleConfig::default()]).is_err());
+        let unbounded = ScheduleConfig {
+            source: Some("Wire".to_string()),
+            ..Default::default()
+        };
+        assert!(schedules(&[unbounded]).is_err());
+    }
+}
@@ -0,0 +1,106 @@
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Host name lookups for the HTTP client: answers are cached for `ttl`, kept past it when
+/// a fresh lookup fails, and `[hosts]` overrides skip DNS entirely
+pub struct CachingResolver {
+    ttl: Duration,
+    overrides: HashMap<String, IpAddr>,
+    cache: Arc<Mutex<HashMap<String, CachedLookup>>>,
+}
+
+#[derive(Debug, Clone)]
+struct CachedLookup {
+    addrs: Vec<SocketAddr>,
+    at: Instant,
+}
+
+impl CachingResolver {
+    pub fn new(ttl: Duration, overrides: HashMap<String, IpAddr>) -> Self {
+        Self {
+            ttl,
+            overrides: overrides
+                .into_iter()
+                .map(|(host, ip)| (host.to_ascii_lowercase(), ip))
+                .collect(),
+            cache: Arc::default(),
+        }
+    }
+
+    /// A cached answer for `host`, if there is one younger than `max_age`
+    fn cached(&self, host: &str, max_age: Duration) -> Option<Vec<SocketAddr>> {
+        let cache = self.cache.lock().unwrap_or_else(|e| e.into_inner());
+        cache
+            .get(host)
+            .filter(|lookup| lookup.at.elapsed() < max_age)
+            .map(|lookup| lookup.addrs.clone())
+    }
+}
+
+impl Resolve for CachingResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let host = name.as_str().to_ascii_lowercase();
+        // The port is filled in by the client
+        if let Some(ip) = self.overrides.get(&host) {
+            let addrs: Addrs = Box::new(std::iter::once(SocketAddr::new(*ip, 0)));
+            return Box::pin(async move { Ok(addrs) });
+        }
+        if let Some(addrs) = self.cached(&host, self.ttl) {
+            return Box::pin(async move { Ok(Box::new(addrs.into_iter()) as Addrs) });
+        }
+
+        let stale = self.cached(&host, Duration::MAX);
+        let cache = Arc::clone(&self.cache);
+        Box::pin(async move {
+            let found = tokio::net::lookup_host((host.as_str(), 0))
+                .await
+                .map(|addrs| addrs.collect::<Vec<_>>());
+            match (found, stale) {
+                (Ok(addrs), _) => {
+                    let lookup = CachedLookup {
+                        addrs: addrs.clone(),
+                        at: Instant::now(),
+                    };
+                    cache.lock().unwrap_or_else(|e| e.into_inner()).insert(host, lookup);
+                    Ok(Box::new(addrs.into_iter()) as Addrs)
+                }
+                // Better the last known address than blanking the feed out
+                (Err(_), Some(stale)) => Ok(Box::new(stale.into_iter()) as Addrs),
+                (Err(e), None) => Err(e.into()),
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn lookup(resolver: &CachingResolver, host: &str) -> Option<Vec<SocketAddr>> {
+        let name: Name = host.parse().unwrap();
+        resolver.resolve(name).await.ok().map(|addrs| addrs.collect())
+    }
+
+    #[tokio::test]
+    async fn test_overrides_and_stale_answers() {
+        let overrides = HashMap::from([("Intranet.Example".to_string(), [10, 0, 0, 5].into())]);
+        let resolver = CachingResolver::new(Duration::ZERO, overrides);
+        let addrs = lookup(&resolver, "intranet.example").await.unwrap();
+        assert_eq!(addrs, vec![SocketAddr::from(([10, 0, 0, 5], 0))]);
+
+        // A failing lookup falls back to the last answer, however old
+        let last = vec![SocketAddr::from(([192, 0, 2, 1], 0))];
+        resolver.cache.lock().unwrap().insert(
+            "feeds.invalid".to_string(),
+            CachedLookup {
+                addrs: last.clone(),
+                at: Instant::now(),
+            },
+        );
+        assert_eq!(lookup(&resolver, "feeds.invalid").await, Some(last));
+        assert_eq!(lookup(&resolver, "other.invalid").await, None);
+    }
+}
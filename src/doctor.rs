@@ -0,0 +1,411 @@
+use crate::cache::cache_dir;
+use crate::config::{get_data_dir, CliArgs, Config};
+use crate::feeds::{self, parse_feeds_files, FeedStatus};
+use crate::platform;
+use crate::urls::PLUGIN_PREFIX;
+use anyhow::Result;
+use chrono::{DateTime, Datelike, Utc};
+use feed_rs::parser;
+use std::fs;
+use std::io::{self, IsTerminal};
+use std::path::Path;
+use std::time::Instant;
+
+/// Clock difference from a server's Date header worth failing over
+const MAX_CLOCK_SKEW_SECS: i64 = 300;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Outcome {
+    Pass,
+    Warn,
+    Fail,
+}
+
+/// One line of the report
+#[derive(Debug)]
+struct Check {
+    name: &'static str,
+    outcome: Outcome,
+    detail: String,
+}
+
+impl Check {
+    fn new(name: &'static str, outcome: Outcome, detail: impl Into<String>) -> Self {
+        Self { name, outcome, detail: detail.into() }
+    }
+
+    fn print(&self) {
+        let mark = match self.outcome {
+            Outcome::Pass => "✓",
+            Outcome::Warn => "!",
+            Outcome::Fail => "✗",
+        };
+        println!("  {} {}: {}", mark, self.name, self.detail);
+    }
+}
+
+/// Run every check, print a pass/fail report, and exit non-zero if anything failed
+pub async fn run(args: CliArgs, fetch: usize) -> Result<()> {
+    let mut checks = Vec::new();
+
+    println!("Terminal");
+    let env = |name: &str| std::env::var(name).ok().filter(|v| !v.is_empty());
+    let tty = io::stdout().is_terminal();
+    checks.push(report(Check::new(
+        "tty",
+        if tty { Outcome::Pass } else { Outcome::Fail },
+        if tty { "stdout is a terminal" } else { "stdout is not a terminal" },
+    )));
+    checks.extend(terminal_checks(&env).into_iter().map(report));
+
+    println!();
+    println!("Config");
+    let config = match Config::load(args) {
+        Ok(config) => {
+            let detail = match &config.config_path {
+                Some(path) if path.exists() => format!("{} parsed", path.display()),
+                _ => "no config file, using defaults".to_string(),
+            };
+            let check = if config.warnings.is_empty() {
+                Check::new("config", Outcome::Pass, detail)
+            } else {
+                Check::new("config", Outcome::Warn, config.warnings.join("; "))
+            };
+            checks.push(report(check));
+            Some(config)
+        }
+        Err(e) => {
+            checks.push(report(Check::new("config", Outcome::Fail, format!("{:#}", e))));
+            None
+        }
+    };
+    let client = config.as_ref().map(feeds::create_http_client).transpose()?;
+    let mut urls = Vec::new();
+    if let Some(backend) = config.as_ref().and_then(|c| c.backend.as_ref()) {
+        let detail = format!("{} at {}", backend.name(), backend.url());
+        checks.push(report(Check::new("backend", Outcome::Pass, detail)));
+    } else if let (Some(config), Some(client)) = (&config, &client) {
+        let check = match parse_feeds_files(client, &config.feeds_paths).await {
+            Ok(entries) if entries.is_empty() => Check::new(
+                "feeds",
+                Outcome::Fail,
+                format!("no feed URLs in {}", config.feeds_label()),
+            ),
+            Ok(entries) => {
+                urls = entries.into_iter().map(|e| e.url).collect();
+                let detail = format!("{} feed(s) in {}", urls.len(), config.feeds_label());
+                Check::new("feeds", Outcome::Pass, detail)
+            }
+            Err(e) => Check::new("feeds", Outcome::Fail, format!("{:#}", e)),
+        };
+        checks.push(report(check));
+    }
+
+    println!();
+    println!("Storage");
+    checks.push(report(writable("cache", &cache_dir())));
+    checks.push(report(writable("data", &get_data_dir())));
+
+    println!();
+    println!("Network");
+    let mut server_time = None;
+    match (&config, &client) {
+        (Some(config @ Config { backend: Some(backend), .. }), Some(client)) => {
+            let started = Instant::now();
+            let check = match backend.fetch_entries(client, fetch.max(1), config.max_age).await.1 {
+                FeedStatus::Ok { item_count, .. } => Check::new(
+                    "server",
+                    Outcome::Pass,
+                    format!("{} unread fetched ({} ms)", item_count, started.elapsed().as_millis()),
+                ),
+                FeedStatus::Error(e) => Check::new("server", Outcome::Fail, e),
+            };
+            checks.push(report(check));
+        }
+        (Some(_), Some(client)) if !urls.is_empty() => {
+            for url in urls.iter().filter(|u| !u.starts_with(PLUGIN_PREFIX)).take(fetch) {
+                let started = Instant::now();
+                let check = match client.get(url).await {
+                    Ok(response) => {
+                        let elapsed = started.elapsed().as_millis();
+                        if let Some(date) = response.header("date") {
+                            server_time = server_time.or(DateTime::parse_from_rfc2822(date).ok());
+                        }
+                        if !response.is_success() {
+                            let detail = format!("HTTP {} from {}", response.status, url);
+                            Check::new("feed", Outcome::Fail, detail)
+                        } else {
+                            match parser::parse(&response.body[..]) {
+                                Ok(_) => Check::new(
+                                    "feed",
+                                    Outcome::Pass,
+                                    format!("{} ({} ms)", url, elapsed),
+                                ),
+                                Err(_) => Check::new(
+                                    "feed",
+                                    Outcome::Fail,
+                                    format!("{} did not return a valid feed", url),
+                                ),
+                            }
+                        }
+                    }
+                    Err(e) => Check::new("feed", Outcome::Fail, format!("{}: {:#}", url, e)),
+                };
+                checks.push(report(check));
+            }
+        }
+        _ => println!("  skipped: no feeds to fetch"),
+    }
+
+    println!();
+    println!("Clock");
+    let server_time = server_time.map(|t| t.with_timezone(&Utc));
+    checks.push(report(clock_check(Utc::now(), server_time)));
+
+    let failed = checks.iter().filter(|c| c.outcome == Outcome::Fail).count();
+    let warned = checks.iter().filter(|c| c.outcome == Outcome::Warn).count();
+    println!();
+    println!(
+        "Summary: {} ok, {} warning(s), {} failed",
+        checks.len() - failed - warned,
+        warned,
+        failed
+    );
+    if failed > 0 {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+fn report(check: Check) -> Check {
+    check.print();
+    check
+}
+
+/// Truecolor, hyperlink, mouse, and focus support, judged from the environment
+/// (terminals can't be queried for all of these without taking over the screen)
+fn terminal_checks(env: &dyn Fn(&str) -> Option<String>) -> Vec<Check> {
+    let term = env("TERM").unwrap_or_default();
+    let tmux = env("TMUX").is_some();
+    let screen = env("STY").is_some() || term.starts_with("screen") && !tmux;
+    let zellij = env("ZELLIJ").is_some();
+    let console = term == "linux";
+    let conhost = platform::is_conhost(env);
+    let terminal = terminal_name(env);
+    let mut checks = Vec::new();
+
+    let colorterm = env("COLORTERM").unwrap_or_default();
+    checks.push(if colorterm == "truecolor" || colorterm == "24bit" {
+        Check::new("truecolor", Outcome::Pass, format!("COLORTERM={}", colorterm))
+    } else {
+        Check::new(
+            "truecolor",
+            Outcome::Warn,
+            "COLORTERM is not truecolor; #rrggbb theme colors may look wrong, \
+             named or indexed colors are safe",
+        )
+    });
+
+    checks.push(if screen {
+        Check::new("hyperlinks", Outcome::Fail, "GNU screen drops OSC 8 links")
+    } else if console {
+        Check::new("hyperlinks", Outcome::Fail, "the Linux console has no OSC 8 support")
+    } else if conhost {
+        Check::new(
+            "hyperlinks",
+            Outcome::Warn,
+            "the Windows console has no OSC 8 support, so links are left out; clicks still \
+             open them (Windows Terminal renders them)",
+        )
+    } else if tmux {
+        Check::new(
+            "hyperlinks",
+            Outcome::Warn,
+            "tmux passes OSC 8 links only from 3.4 with \
+             `set -as terminal-features ',*:hyperlinks'`",
+        )
+    } else if zellij {
+        Check::new(
+            "hyperlinks",
+            Outcome::Warn,
+            "zellij passes OSC 8 links only in recent versions; \
+             clicks still open links through click_action",
+        )
+    } else {
+        match terminal {
+            Some((name, true)) => {
+                Check::new("hyperlinks", Outcome::Pass, format!("{} supports OSC 8", name))
+            }
+            Some((name, false)) => Check::new(
+                "hyperlinks",
+                Outcome::Fail,
+                format!("{} does not support OSC 8 links", name),
+            ),
+            None => Check::new(
+                "hyperlinks",
+                Outcome::Warn,
+                format!("unrecognized terminal (TERM={}); links need OSC 8 support", term),
+            ),
+        }
+    });
+
+    checks.push(if tmux {
+        Check::new("mouse", Outcome::Warn, "tmux forwards clicks only with `set -g mouse on`")
+    } else if screen {
+        Check::new("mouse", Outcome::Warn, "screen forwards clicks only with `mousetrack on`")
+    } else if zellij {
+        Check::new("mouse", Outcome::Pass, "zellij forwards clicks to the pane (mouse_mode on)")
+    } else if console {
+        Check::new("mouse", Outcome::Warn, "the Linux console needs gpm for mouse input")
+    } else {
+        Check::new("mouse", Outcome::Pass, "xterm mouse reporting expected")
+    });
+
+    checks.push(if tmux {
+        Check::new(
+            "focus events",
+            Outcome::Warn,
+            "tmux forwards focus only with `set -g focus-events on` (pause = \"focus\")",
+        )
+    } else if screen || console || zellij {
+        Check::new(
+            "focus events",
+            Outcome::Warn,
+            "not reported here; pause = \"focus\" won't work",
+        )
+    } else {
+        Check::new("focus events", Outcome::Pass, "xterm focus reporting expected")
+    });
+
+    checks
+}
+
+/// The terminal emulator, if recognizable, and whether it renders OSC 8 links
+fn terminal_name(env: &dyn Fn(&str) -> Option<String>) -> Option<(String, bool)> {
+    if let Some(program) = env("TERM_PROGRAM") {
+        let known = match program.as_str() {
+            "iTerm.app" => Some(("iTerm2", true)),
+            "WezTerm" => Some(("WezTerm", true)),
+            "vscode" => Some(("VS Code", true)),
+            "ghostty" => Some(("Ghostty", true)),
+            "Hyper" => Some(("Hyper", true)),
+            "Apple_Terminal" => Some(("Terminal.app", false)),
+            _ => None,
+        };
+        if let Some((name, links)) = known {
+            return Some((name.to_string(), links));
+        }
+    }
+    let term = env("TERM").unwrap_or_default();
+    if env("KITTY_WINDOW_ID").is_some() || term == "xterm-kitty" {
+        return Some(("kitty".to_string(), true));
+    }
+    if env("WT_SESSION").is_some() {
+        return Some(("Windows Terminal".to_string(), true));
+    }
+    if env("ALACRITTY_WINDOW_ID").is_some() || term == "alacritty" {
+        return Some(("Alacritty".to_string(), true));
+    }
+    if term.starts_with("foot") {
+        return Some(("foot".to_string(), true));
+    }
+    if let Some(version) = env("KONSOLE_VERSION").and_then(|v| v.parse::<u32>().ok()) {
+        return Some(("Konsole".to_string(), version >= 201200));
+    }
+    if let Some(version) = env("VTE_VERSION").and_then(|v| v.parse::<u32>().ok()) {
+        return Some(("VTE-based terminal".to_string(), version >= 5000));
+    }
+    None
+}
+
+/// Create the directory if needed and write and remove a probe file in it
+fn writable(name: &'static str, dir: &Path) -> Check {
+    let probe = dir.join(".doctor-probe");
+    let result = fs::create_dir_all(dir)
+        .and_then(|_| fs::write(&probe, b"ok"))
+        .and_then(|_| fs::remove_file(&probe));
+    match result {
+        Ok(()) => Check::new(name, Outcome::Pass, format!("{} is writable", dir.display())),
+        Err(e) => Check::new(name, Outcome::Fail, format!("{}: {}", dir.display(), e)),
+    }
+}
+
+/// Compare the local clock against a server's, or at least rule out an unset clock
+fn clock_check(now: DateTime<Utc>, server: Option<DateTime<Utc>>) -> Check {
+    match server {
+        Some(server) => {
+            let skew = (now - server).num_seconds();
+            if skew.abs() > MAX_CLOCK_SKEW_SECS {
+                Check::new(
+                    "clock",
+                    Outcome::Fail,
+                    format!(
+                        "{}s {} the feed server; headline ages and max_age_hours will be off",
+                        skew.abs(),
+                        if skew > 0 { "ahead of" } else { "behind" },
+                    ),
+                )
+            } else {
+                let detail = format!("within {}s of the feed server", skew.abs());
+                Check::new("clock", Outcome::Pass, detail)
+            }
+        }
+        None if now.year() < 2024 => Check::new(
+            "clock",
+            Outcome::Fail,
+            format!("system date is {}; is the clock set?", now.format("%Y-%m-%d")),
+        ),
+        None => Check::new("clock", Outcome::Warn, "no server Date header to compare against"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn checks_for(vars: &[(&str, &str)]) -> HashMap<&'static str, Outcome> {
+        let vars: HashMap<String, String> =
+            vars.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        let env = |name: &str| vars.get(name).cloned();
+        terminal_checks(&env).into_iter().map(|c| (c.name, c.outcome)).collect()
+    }
+
+    #[test]
+    fn test_terminal_checks() {
+        let kitty = checks_for(&[("TERM", "xterm-kitty"), ("COLORTERM", "truecolor")]);
+        assert!(kitty.values().all(|o| *o == Outcome::Pass));
+
+        let tmux = checks_for(&[("TERM", "tmux-256color"), ("TMUX", "/tmp/tmux-1000/default")]);
+        assert_eq!(tmux["hyperlinks"], Outcome::Warn);
+        assert_eq!(tmux["mouse"], Outcome::Warn);
+        assert_eq!(tmux["truecolor"], Outcome::Warn);
+
+        let zellij = checks_for(&[("TERM", "xterm-256color"), ("ZELLIJ", "0")]);
+        assert_eq!(zellij["hyperlinks"], Outcome::Warn);
+        assert_eq!(zellij["mouse"], Outcome::Pass);
+
+        let screen = checks_for(&[("TERM", "screen"), ("STY", "123.pts-0")]);
+        assert_eq!(screen["hyperlinks"], Outcome::Fail);
+
+        let apple = checks_for(&[("TERM_PROGRAM", "Apple_Terminal")]);
+        assert_eq!(apple["hyperlinks"], Outcome::Fail);
+
+        let conhost = checks_for(&[("OS", "Windows_NT")]);
+        assert_eq!(conhost["hyperlinks"], Outcome::Warn);
+        let windows_terminal = checks_for(&[("OS", "Windows_NT"), ("WT_SESSION", "0f3c")]);
+        assert_eq!(windows_terminal["hyperlinks"], Outcome::Pass);
+    }
+
+    #[test]
+    fn test_clock_check() {
+        let now = Utc::now();
+        let near = now - chrono::Duration::seconds(20);
+        assert_eq!(clock_check(now, Some(near)).outcome, Outcome::Pass);
+        let far = now - chrono::Duration::hours(2);
+        let check = clock_check(now, Some(far));
+        assert_eq!(check.outcome, Outcome::Fail);
+        assert!(check.detail.contains("ahead of"));
+        assert_eq!(clock_check(now, None).outcome, Outcome::Warn);
+    }
+}
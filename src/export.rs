@@ -0,0 +1,73 @@
+use crate::config::ExportFormat;
+use crate::dates::DateStyle;
+use crate::feeds::Headline;
+use anyhow::Result;
+use std::fmt::Write;
+
+/// Render headlines in the given export format
+/// (JSON and CSV keep UTC RFC 3339 dates; Markdown uses the configured zone and locale)
+pub fn render(headlines: &[Headline], format: ExportFormat, dates: &DateStyle) -> Result<String> {
+    match format {
+        ExportFormat::Json => Ok(serde_json::to_string_pretty(headlines)? + "\n"),
+        ExportFormat::Csv => Ok(render_csv(headlines)),
+        ExportFormat::Markdown => Ok(render_markdown(headlines, dates)),
+    }
+}
+
+fn render_csv(headlines: &[Headline]) -> String {
+    let mut out = String::from("title,url,source,published\n");
+    for h in headlines {
+        let published = h.published.map(|d| d.to_rfc3339()).unwrap_or_default();
+        let _ = writeln!(
+            out,
+            "{},{},{},{}",
+            csv_field(&h.title),
+            csv_field(h.url.as_deref().unwrap_or("")),
+            csv_field(&h.source),
+            csv_field(&published)
+        );
+    }
+    out
+}
+
+/// Quote a CSV field if it contains separators, quotes, or newlines
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn render_markdown(headlines: &[Headline], dates: &DateStyle) -> String {
+    let mut out = format!("# Headlines ({})\n\n", dates.now("%Y-%m-%d %H:%M"));
+    for h in headlines {
+        let title = h.title.replace('[', "\\[").replace(']', "\\]");
+        let item = match &h.url {
+            Some(url) => format!("[{}]({})", title, url),
+            None => title,
+        };
+        let published = h
+            .published
+            .map(|d| format!(", {}", dates.format(d, "%b %d %H:%M")))
+            .unwrap_or_default();
+        let _ = writeln!(out, "- {} ({}{})", item, h.source, published);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::feeds::headline;
+
+    #[test]
+    fn test_csv_escaping() {
+        let headlines = vec![headline("Quotes \"here\", and commas", "Test", None)];
+        let csv = render(&headlines, ExportFormat::Csv, &DateStyle::default()).unwrap();
+        assert_eq!(
+            csv,
+            "title,url,source,published\n\"Quotes \"\"here\"\", and commas\",,Test,\n"
+        );
+    }
+}
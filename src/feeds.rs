@@ -1,17 +1,44 @@
+use crate::cache::cache_dir;
+use crate::config::Config;
+use crate::dates;
+use crate::dns::CachingResolver;
+use crate::http::{HttpClient, HttpResponse};
+use crate::urls::FeedEntry;
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use feed_rs::parser;
-use std::path::Path;
+use quick_xml::events::Event;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::HashSet;
+use std::fmt;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use std::time::Duration;
 use tokio::fs;
 
 /// A single headline from an RSS/Atom feed
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Headline {
     pub title: String,
     pub url: Option<String>,
     pub source: String,
     pub published: Option<DateTime<Utc>>,
+    /// Homepage of the publication (the feed's channel link)
+    pub source_url: Option<String>,
+}
+
+/// Undated headline for unit tests
+#[cfg(test)]
+pub fn headline(title: &str, source: &str, url: Option<&str>) -> Headline {
+    Headline {
+        title: title.to_string(),
+        url: url.map(str::to_string),
+        source: source.to_string(),
+        published: None,
+        source_url: None,
+    }
 }
 
 /// Result of validating/fetching a single feed
@@ -20,144 +47,946 @@ pub struct FeedResult {
     pub status: FeedStatus,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum FeedStatus {
-    Ok { title: String, item_count: usize },
+    Ok {
+        title: String,
+        /// Entries recent enough to show
+        item_count: usize,
+        /// Entries skipped as older than max_age
+        expired: usize,
+        /// Date of the newest entry, old ones included
+        newest: Option<DateTime<Utc>>,
+        /// Fallback User-Agent that got past a 403, if one was needed
+        user_agent: Option<String>,
+    },
     Error(String),
 }
 
+/// "12 recent items (37 older than 24h)", leaving out the old ones if there are none
+pub fn item_summary(item_count: usize, expired: usize, max_age: Duration) -> String {
+    let recent = format!("{} recent item{}", item_count, if item_count == 1 { "" } else { "s" });
+    if expired == 0 {
+        return recent;
+    }
+    let secs = max_age.as_secs();
+    let (hours, rest) = (secs / 3600, secs % 3600);
+    let age = if rest == 0 {
+        format!("{}h", hours)
+    } else {
+        format!("{}m", secs.div_ceil(60))
+    };
+    format!("{} ({} older than {})", recent, expired, age)
+}
+
+/// "stale: nothing in 30 days" when a feed's newest entry is older than `stale_after`
+/// (zero: never stale)
+pub fn stale_note(newest: Option<DateTime<Utc>>, stale_after: Duration) -> Option<String> {
+    let age = Utc::now() - newest?;
+    if stale_after.is_zero() || age.to_std().ok()? < stale_after {
+        return None;
+    }
+    let days = age.num_days();
+    Some(format!("stale: nothing in {} day{}", days, if days == 1 { "" } else { "s" }))
+}
+
+/// Something off about a feed that didn't stop it being read
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FeedWarning {
+    /// The feed has no title, so its URL stands in for it
+    NoTitle,
+    /// Entries dropped for having no title
+    UntitledEntries(usize),
+    /// Entries kept without a link, so clicking them opens nothing
+    UnlinkedEntries(usize),
+    /// A large feed read only as far as this many entries
+    CutShort(usize),
+    /// Parsed as a feed despite being served with this Content-Type
+    ContentType(String),
+}
+
+impl fmt::Display for FeedWarning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::NoTitle => write!(f, "no feed title, named by its URL"),
+            Self::UntitledEntries(n) => write!(f, "{} untitled entries skipped", n),
+            Self::UnlinkedEntries(n) => write!(f, "{} entries without a link", n),
+            Self::CutShort(n) => write!(f, "large feed, stopped reading after {} entries", n),
+            Self::ContentType(kind) => write!(f, "served as {} rather than a feed type", kind),
+        }
+    }
+}
+
+/// Where feed warnings are logged
+pub fn warnings_log_path() -> PathBuf {
+    crate::config::get_data_dir().join("warnings.log")
+}
+
+/// Size past which the warnings log is moved aside to `warnings.log.1`
+const WARNINGS_LOG_BYTES: u64 = 256 * 1024;
+
+/// Append a feed's warnings to the log, one line each, starting a fresh log once it's
+/// grown past WARNINGS_LOG_BYTES
+pub fn log_warnings(path: &Path, url: &str, warnings: &[FeedWarning]) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    if std::fs::metadata(path).is_ok_and(|m| m.len() > WARNINGS_LOG_BYTES) {
+        std::fs::rename(path, path.with_extension("log.1"))?;
+    }
+    let mut file = std::fs::OpenOptions::new().create(true).append(true).open(path)?;
+    let now = Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+    for warning in warnings {
+        writeln!(file, "{}  {}  {}", now, url, warning)?;
+    }
+    Ok(())
+}
+
+/// A successfully fetched and parsed feed
+#[derive(Debug)]
+pub struct FetchedFeed {
+    pub title: String,
+    pub headlines: Vec<Headline>,
+    /// Newest entries older than max_age, if none were recent enough
+    pub outdated: Vec<Headline>,
+    /// Entries skipped as older than max_age
+    pub expired: usize,
+    /// Date of the newest entry, old ones included
+    pub newest: Option<DateTime<Utc>>,
+    /// Fallback User-Agent that got past a 403, if one was needed
+    pub user_agent: Option<String>,
+    pub warnings: Vec<FeedWarning>,
+}
+
+/// Most recent fetch outcome for a feed, kept for health reporting
+#[derive(Debug, Clone)]
+pub struct FeedHealth {
+    pub status: FeedStatus,
+    /// When the feed last fetched successfully
+    pub last_success: Option<DateTime<Utc>>,
+    /// Warnings from the latest fetch
+    pub warnings: Vec<FeedWarning>,
+}
+
+impl FeedHealth {
+    /// Fold a new fetch outcome into the previous health record (if any)
+    pub fn update(
+        previous: Option<&FeedHealth>,
+        status: FeedStatus,
+        warnings: Vec<FeedWarning>,
+    ) -> Self {
+        let now = Utc::now();
+        let last_success = match status {
+            FeedStatus::Ok { .. } => Some(now),
+            FeedStatus::Error(_) => previous.and_then(|h| h.last_success),
+        };
+        Self {
+            status,
+            last_success,
+            warnings,
+        }
+    }
+
+    pub fn is_ok(&self) -> bool {
+        matches!(self.status, FeedStatus::Ok { .. })
+    }
+}
+
 /// Parse a newsboat-style URLs file
-/// Format: one URL per line, optional tags after whitespace (ignored)
-pub async fn parse_feeds_file(path: &Path) -> Result<Vec<String>> {
+/// Format: one URL per line, optional tags and a "~Display Name" after whitespace
+pub async fn parse_feeds_file(path: &Path) -> Result<Vec<FeedEntry>> {
     let content = fs::read_to_string(path)
         .await
         .with_context(|| format!("Failed to read feeds file: {}", path.display()))?;
 
-    let urls: Vec<String> = content
-        .lines()
-        .map(|line| line.trim())
-        .filter(|line| !line.is_empty() && !line.starts_with('#'))
-        .map(|line| {
-            // Take only the URL part (before any whitespace/tags)
-            line.split_whitespace()
-                .next()
-                .unwrap_or(line)
-                .to_string()
-        })
-        .filter(|url| url.starts_with("http://") || url.starts_with("https://"))
-        .collect();
+    Ok(content.lines().filter_map(FeedEntry::parse).collect())
+}
+
+/// The URL of a feeds list served over HTTP(S), when `path` is one rather than a file
+pub fn remote_list(path: &Path) -> Option<&str> {
+    let path = path.to_str()?;
+    (path.starts_with("https://") || path.starts_with("http://")).then_some(path)
+}
+
+/// Where the last copy of a remote feeds list is kept
+fn remote_list_cache(url: &str) -> PathBuf {
+    let name: String =
+        url.chars().map(|c| if c.is_ascii_alphanumeric() { c } else { '_' }).collect();
+    cache_dir().join("feeds").join(name)
+}
+
+/// Fetch a remote feeds list, saving it to `cache`; while the server can't be reached (or
+/// answers with an error) the saved copy is read instead
+async fn fetch_feeds_list(client: &HttpClient, url: &str, cache: &Path) -> Result<String> {
+    let fetched = async {
+        let response = client.get(url).await?;
+        check_response(&response)?;
+        Ok::<_, anyhow::Error>(String::from_utf8_lossy(&response.body).into_owned())
+    }
+    .await;
+    match fetched {
+        Ok(content) => {
+            if let Some(parent) = cache.parent() {
+                let _ = fs::create_dir_all(parent).await;
+            }
+            let _ = fs::write(cache, &content).await; // The next start fetches it again
+            Ok(content)
+        }
+        Err(e) => fs::read_to_string(cache)
+            .await
+            .map_err(|_| e)
+            .with_context(|| format!("Failed to fetch feeds list: {}", url)),
+    }
+}
 
-    Ok(urls)
+/// The feeds files to read: the paths given, with a directory standing for the files in
+/// it (in name order, leaving out hidden files and editor backups)
+pub fn feeds_files(paths: &[PathBuf]) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for path in paths {
+        if !path.is_dir() {
+            files.push(path.clone());
+            continue;
+        }
+        let entries = std::fs::read_dir(path)
+            .with_context(|| format!("Failed to read feeds directory: {}", path.display()))?;
+        let mut listed: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|file| {
+                let name = file.file_name().and_then(|n| n.to_str()).unwrap_or(".");
+                file.is_file() && !name.starts_with('.') && !name.ends_with('~')
+            })
+            .collect();
+        listed.sort();
+        files.extend(listed);
+    }
+    Ok(files)
+}
+
+/// Parse every feeds file in `paths` (fetching remote lists) into one list; a feed listed
+/// twice keeps its first entry
+pub async fn parse_feeds_files(client: &HttpClient, paths: &[PathBuf]) -> Result<Vec<FeedEntry>> {
+    let mut entries = Vec::new();
+    let mut seen = HashSet::new();
+    for file in feeds_files(paths)? {
+        let listed = match remote_list(&file) {
+            Some(url) => {
+                let content = fetch_feeds_list(client, url, &remote_list_cache(url)).await?;
+                content.lines().filter_map(FeedEntry::parse).collect()
+            }
+            None => parse_feeds_file(&file).await?,
+        };
+        for entry in listed {
+            if seen.insert(entry.url.clone()) {
+                entries.push(entry);
+            }
+        }
+    }
+    Ok(entries)
 }
 
 /// Fetch and parse a single feed, returning headlines
 pub async fn fetch_feed(
-    client: &reqwest::Client,
+    client: &HttpClient,
     url: &str,
     max_items: usize,
     max_age: Duration,
-) -> Result<(String, Vec<Headline>)> {
+) -> Result<FetchedFeed> {
+    let cutoff = dates::age_cutoff(max_age);
+
+    // Large feeds are read only as far as the entries that will be kept
+    let mut scan = EntryScan::new(max_items, cutoff);
     let response = client
-        .get(url)
-        .timeout(Duration::from_secs(30))
-        .send()
+        .get_until(url, &mut |body| scan.end(body).is_some())
         .await
         .with_context(|| format!("Failed to fetch feed: {}", url))?;
 
-    let bytes = response
-        .bytes()
-        .await
-        .with_context(|| format!("Failed to read feed body: {}", url))?;
+    check_response(&response)?;
 
-    let feed = parser::parse(&bytes[..])
+    let mut warnings = Vec::new();
+    let body = match EntryScan::new(max_items, cutoff).end(&response.body) {
+        Some(end) => {
+            warnings.push(FeedWarning::CutShort(max_items));
+            Cow::Owned(close_document(&response.body[..end]))
+        }
+        None => Cow::Borrowed(&response.body[..]),
+    };
+    let feed = parser::parse(&body[..])
         .with_context(|| format!("Failed to parse feed: {}", url))?;
+    if let Some(kind) = unexpected_content_type(&response) {
+        warnings.push(FeedWarning::ContentType(kind));
+    }
 
-    let source = feed
-        .title
-        .map(|t| t.content)
-        .unwrap_or_else(|| url.to_string());
+    let source = match feed.title {
+        Some(title) => title.content,
+        None => {
+            warnings.push(FeedWarning::NoTitle);
+            url.to_string()
+        }
+    };
 
-    let now = Utc::now();
-    let max_age_chrono = chrono::Duration::from_std(max_age).unwrap_or(chrono::Duration::hours(24));
-    let cutoff = now - max_age_chrono;
+    // Channel link, skipping Atom's rel="self" pointer back to the feed itself
+    let source_url = feed
+        .links
+        .iter()
+        .find(|l| l.rel.as_deref() != Some("self"))
+        .map(|l| l.href.clone());
 
+    let newest = feed.entries.iter().filter_map(|e| e.published.or(e.updated)).max();
+    let mut outdated = Vec::new();
+    let mut untitled = 0;
     let headlines: Vec<Headline> = feed
         .entries
         .into_iter()
         .filter_map(|entry| {
-            let title = entry.title.map(|t| t.content)?;
+            let title = entry.title.map(|t| t.content).unwrap_or_default();
             if title.trim().is_empty() {
+                untitled += 1;
                 return None;
             }
 
             let published = entry.published.or(entry.updated);
-
-            // Filter by age if we have a date
-            if let Some(pub_date) = published {
-                if pub_date < cutoff {
-                    return None;
-                }
-            }
-
             let url = entry.links.first().map(|l| l.href.clone());
-
-            Some(Headline {
+            let headline = Headline {
                 title,
                 url,
                 source: source.clone(),
                 published,
-            })
+                source_url: source_url.clone(),
+            };
+
+            // Filter by age if we have a date
+            if published.is_some_and(|date| date < cutoff) {
+                outdated.push(headline);
+                return None;
+            }
+            Some(headline)
         })
         .take(max_items)
         .collect();
 
-    Ok((source, headlines))
+    if untitled > 0 {
+        warnings.push(FeedWarning::UntitledEntries(untitled));
+    }
+    let unlinked = headlines.iter().filter(|h| h.url.is_none()).count();
+    if unlinked > 0 {
+        warnings.push(FeedWarning::UnlinkedEntries(unlinked));
+    }
+
+    // Only needed when nothing is recent enough
+    let expired = outdated.len();
+    if headlines.is_empty() {
+        outdated.sort_by_key(|h| std::cmp::Reverse(h.published));
+        outdated.truncate(max_items);
+    } else {
+        outdated.clear();
+    }
+
+    Ok(FetchedFeed {
+        title: source,
+        headlines,
+        outdated,
+        expired,
+        newest,
+        user_agent: response.user_agent,
+        warnings,
+    })
 }
 
-/// Validate a feed and return status
-pub async fn validate_feed(client: &reqwest::Client, url: &str) -> FeedResult {
-    let status = match fetch_feed_status(client, url).await {
-        Ok((title, count)) => FeedStatus::Ok {
-            title,
-            item_count: count,
-        },
-        Err(e) => FeedStatus::Error(e.to_string()),
-    };
+/// The Content-Type a feed was served with, if it isn't one feeds use
+fn unexpected_content_type(response: &HttpResponse) -> Option<String> {
+    let kind = response.header("content-type")?.split(';').next()?.trim().to_ascii_lowercase();
+    let feedlike = ["xml", "rss", "atom", "json"].iter().any(|t| kind.contains(t));
+    (!feedlike && !kind.is_empty()).then_some(kind)
+}
 
-    FeedResult { status }
+impl FetchedFeed {
+    pub fn status(&self) -> FeedStatus {
+        FeedStatus::Ok {
+            title: self.title.clone(),
+            item_count: self.headlines.len(),
+            expired: self.expired,
+            newest: self.newest,
+            user_agent: self.user_agent.clone(),
+        }
+    }
+}
+
+/// Feeds bigger than this stop being read once they have enough entries
+const LARGE_FEED: usize = 1024 * 1024;
+
+/// Finds where a large feed's first `max` entries that will be kept (titled, and not older
+/// than the cutoff) end, scanning the body as it arrives
+struct EntryScan {
+    max: usize,
+    cutoff: DateTime<Utc>,
+    count: usize,
+    scanned: usize,
+    /// Offset just past the last entry's end tag, where the next entry begins
+    entry_from: usize,
+}
+
+impl EntryScan {
+    const START_TAGS: [&'static [u8]; 2] = [b"<item", b"<entry"];
+    const END_TAGS: [&'static [u8]; 2] = [b"</item>", b"</entry>"];
+
+    fn new(max: usize, cutoff: DateTime<Utc>) -> Self {
+        Self {
+            max,
+            cutoff,
+            count: 0,
+            scanned: 0,
+            entry_from: 0,
+        }
+    }
+
+    /// Offset just past the `</item>` or `</entry>` of the `max`-th kept entry, once the
+    /// body is past LARGE_FEED and that tag has arrived
+    fn end(&mut self, body: &[u8]) -> Option<usize> {
+        // A shorter body is a new attempt (a retry) starting over
+        if body.len() < self.scanned {
+            *self = Self::new(self.max, self.cutoff);
+        }
+        while self.count < self.max {
+            let rest = &body[self.scanned..];
+            let Some(open) = rest.iter().position(|&b| b == b'<') else {
+                self.scanned = body.len();
+                break;
+            };
+            let rest = &rest[open..];
+            if let Some(tag) = Self::END_TAGS.iter().find(|tag| rest.starts_with(tag)) {
+                let end = self.scanned + open + tag.len();
+                if Self::kept(&body[self.entry_from..end], self.cutoff) {
+                    self.count += 1;
+                }
+                self.scanned = end;
+                self.entry_from = end;
+            } else if Self::END_TAGS.iter().any(|tag| tag.starts_with(rest)) {
+                // Possibly a tag split across chunks: wait for the rest of it
+                self.scanned += open;
+                break;
+            } else {
+                self.scanned += open + 1;
+            }
+        }
+        let done = self.max > 0 && self.count == self.max && body.len() >= LARGE_FEED;
+        done.then_some(self.scanned)
+    }
+
+    /// Whether the entry at the end of `entry` would be kept: it has a title, and its date (if
+    /// it has one) isn't before the cutoff. One whose date can't be read doesn't count,
+    /// so the feed is read further rather than cut too soon
+    fn kept(entry: &[u8], cutoff: DateTime<Utc>) -> bool {
+        let start = Self::START_TAGS
+            .iter()
+            .filter_map(|tag| {
+                let at = entry.windows(tag.len()).rposition(|w| w == *tag)?;
+                let next = entry.get(at + tag.len())?;
+                (*next == b'>' || next.is_ascii_whitespace()).then_some(at)
+            })
+            .max();
+        let Some(start) = start else {
+            return false;
+        };
+        let mut reader = quick_xml::Reader::from_reader(&entry[start..]);
+        let mut buf = Vec::new();
+        let mut element = Vec::new();
+        let (mut title, mut published, mut updated) = (String::new(), None, None);
+        loop {
+            match reader.read_event_into(&mut buf) {
+                Ok(Event::Start(start)) => element = start.local_name().as_ref().to_vec(),
+                Ok(Event::End(_)) => element.clear(),
+                Ok(Event::Text(text)) => {
+                    let text = String::from_utf8_lossy(&text).trim().to_string();
+                    match element.as_slice() {
+                        b"title" if title.is_empty() => title = text,
+                        b"pubDate" | b"published" | b"date" if published.is_none() => {
+                            published = Some(text)
+                        }
+                        b"updated" if updated.is_none() => updated = Some(text),
+                        _ => {}
+                    }
+                }
+                Ok(Event::CData(text)) if element == b"title" && title.is_empty() => {
+                    title = String::from_utf8_lossy(&text).trim().to_string();
+                }
+                Ok(Event::Eof) | Err(_) => break,
+                Ok(_) => {}
+            }
+            buf.clear();
+        }
+        let date = match published.or(updated) {
+            Some(date) => match DateTime::parse_from_rfc2822(&date)
+                .or_else(|_| DateTime::parse_from_rfc3339(&date))
+            {
+                Ok(date) => Some(date.with_timezone(&Utc)),
+                Err(_) => return false,
+            },
+            None => None,
+        };
+        !title.is_empty() && date.is_none_or(|date| date >= cutoff)
+    }
+}
+
+/// Close the elements still open at the end of a feed cut short, so it parses
+fn close_document(partial: &[u8]) -> Vec<u8> {
+    let mut reader = quick_xml::Reader::from_reader(partial);
+    let mut open: Vec<Vec<u8>> = Vec::new();
+    let mut buf = Vec::new();
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(start)) => open.push(start.name().as_ref().to_vec()),
+            Ok(Event::End(_)) => {
+                open.pop();
+            }
+            Ok(Event::Eof) | Err(_) => break,
+            Ok(_) => {}
+        }
+        buf.clear();
+    }
+    let mut document = partial.to_vec();
+    for name in open.iter().rev() {
+        document.extend_from_slice(b"</");
+        document.extend_from_slice(name);
+        document.push(b'>');
+    }
+    document
+}
+
+/// Fetch one feed, returning its headlines (none on failure) and its status
+pub async fn fetch_one(
+    client: &HttpClient,
+    url: &str,
+    max_per_feed: usize,
+    max_age: Duration,
+) -> (Vec<Headline>, FeedStatus) {
+    match fetch_feed(client, url, max_per_feed, max_age).await {
+        Ok(fetched) => {
+            let status = fetched.status();
+            (fetched.headlines, status)
+        }
+        Err(e) => (Vec::new(), FeedStatus::Error(format!("{:#}", e))),
+    }
 }
 
-async fn fetch_feed_status(client: &reqwest::Client, url: &str) -> Result<(String, usize)> {
+/// Resolve a page or feed URL to a feed URL: the URL itself if it serves a feed,
+/// otherwise the first feed the page advertises with <link rel="alternate">
+pub async fn discover_feed(client: &HttpClient, url: &str) -> Result<String> {
     let response = client
         .get(url)
-        .timeout(Duration::from_secs(30))
-        .send()
         .await
-        .with_context(|| "Connection failed")?;
+        .with_context(|| format!("Failed to fetch {}", url))?;
+    if !response.is_success() {
+        anyhow::bail!("HTTP {}", status_line(response.status));
+    }
+    if parser::parse(&response.body[..]).is_ok() {
+        return Ok(url.to_string());
+    }
+    let html = String::from_utf8_lossy(&response.body);
+    find_feed_link(&html, url).with_context(|| format!("No feed found at {}", url))
+}
+
+/// The first RSS/Atom <link rel="alternate"> in a page, resolved against the page URL
+fn find_feed_link(html: &str, base: &str) -> Option<String> {
+    // ASCII lowercasing keeps byte offsets, so positions carry over to the original
+    let lower = html.to_ascii_lowercase();
+    let mut from = 0;
+    while let Some(start) = lower[from..].find("<link").map(|i| from + i) {
+        let end = start + lower[start..].find('>')?;
+        from = end;
+        let tag = &lower[start..end];
+        let is_feed = tag.contains("alternate")
+            && (tag.contains("application/rss+xml") || tag.contains("application/atom+xml"));
+        if !is_feed {
+            continue;
+        }
+        if let Some(href) = attribute(&html[start..end], "href") {
+            let base = reqwest::Url::parse(base).ok()?;
+            return base.join(&href).ok().map(|url| url.to_string());
+        }
+    }
+    None
+}
 
-    if !response.status().is_success() {
-        anyhow::bail!("HTTP {}", response.status());
+/// URL of a page's PNG icon, from its `<link>` tags: an apple-touch-icon (always PNG, and
+/// the largest), else an icon marked as PNG
+pub fn find_icon_link(html: &str, base: &str) -> Option<String> {
+    let lower = html.to_ascii_lowercase();
+    let mut icon = None;
+    let mut from = 0;
+    while let Some(start) = lower[from..].find("<link").map(|i| from + i) {
+        let Some(end) = lower[start..].find('>').map(|i| start + i) else {
+            break;
+        };
+        from = end;
+        let tag = &html[start..end];
+        let rel = attribute(tag, "rel").unwrap_or_default().to_ascii_lowercase();
+        let Some(href) = attribute(tag, "href") else {
+            continue;
+        };
+        if rel.contains("apple-touch-icon") {
+            icon = Some(href);
+            break;
+        }
+        let png = attribute(tag, "type").is_some_and(|t| t.eq_ignore_ascii_case("image/png"))
+            || href.to_ascii_lowercase().split('?').next()?.ends_with(".png");
+        if icon.is_none() && rel.split_whitespace().any(|r| r == "icon") && png {
+            icon = Some(href);
+        }
     }
+    let base = reqwest::Url::parse(base).ok()?;
+    base.join(&icon?).ok().map(|url| url.to_string())
+}
+
+/// Value of a (quoted or bare) attribute in an HTML tag
+fn attribute(tag: &str, name: &str) -> Option<String> {
+    let lower = tag.to_ascii_lowercase();
+    let pattern = format!("{}=", name);
+    let mut from = 0;
+    while let Some(at) = lower[from..].find(&pattern).map(|i| from + i) {
+        from = at + pattern.len();
+        // Skip longer names ending in this one (data-href=)
+        if !tag[..at].ends_with(char::is_whitespace) {
+            continue;
+        }
+        let value = &tag[from..];
+        return Some(match value.chars().next()? {
+            quote @ ('"' | '\'') => value[1..].split(quote).next()?.to_string(),
+            _ => value.split(|c: char| c.is_whitespace() || c == '/').next()?.to_string(),
+        });
+    }
+    None
+}
+
+/// Drop headlines linking to blocked domains
+pub fn filter_blocked(headlines: &mut Vec<Headline>, blocked_domains: &[String]) {
+    if blocked_domains.is_empty() {
+        return;
+    }
+    headlines.retain(|h| {
+        h.url
+            .as_deref()
+            .map(|url| !is_blocked(url, blocked_domains))
+            .unwrap_or(true)
+    });
+}
+
+/// Check whether a headline URL points at one of the blocked domains
+/// Subdomains match too: blocking "example.com" also blocks "www.example.com"
+pub fn is_blocked(url: &str, blocked_domains: &[String]) -> bool {
+    let host = match reqwest::Url::parse(url) {
+        Ok(parsed) => match parsed.host_str() {
+            Some(host) => host.to_ascii_lowercase(),
+            None => return false,
+        },
+        Err(_) => return false,
+    };
+
+    blocked_domains.iter().any(|domain| {
+        let domain = domain.trim().trim_start_matches('.').to_ascii_lowercase();
+        !domain.is_empty()
+            && (host == domain || host.ends_with(&format!(".{}", domain)))
+    })
+}
 
-    let bytes = response.bytes().await.with_context(|| "Failed to read body")?;
+/// Check whether a title contains any of the words (case-insensitive)
+pub fn mentions_any(title: &str, words: &[String]) -> bool {
+    let title = title.to_lowercase();
+    words
+        .iter()
+        .map(|word| word.trim().to_lowercase())
+        .any(|word| !word.is_empty() && title.contains(&word))
+}
 
-    let feed = parser::parse(&bytes[..]).with_context(|| "Invalid feed format")?;
+/// A `[[titles]]` entry: clean-up passes for the titles of matching feeds
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TitleRule {
+    /// Source name to apply to (case-insensitive); with no source or tag, every feed
+    pub source: Option<String>,
+    pub tag: Option<String>,
+    /// Turn runs of whitespace into single spaces
+    pub collapse_whitespace: bool,
+    /// Drop a trailing " - Site Name" (or " | Site Name") of up to four words
+    pub strip_site_name: bool,
+    /// Title-case headlines written entirely in capitals
+    pub title_case: bool,
+    /// Further passes, applied in order after the ones above
+    pub transforms: Vec<TitleTransform>,
+}
 
-    let title = feed
-        .title
-        .map(|t| t.content)
-        .unwrap_or_else(|| "Untitled".to_string());
+/// A clean-up pass in a `[[titles]]` entry's `transforms` list
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TitleTransform {
+    CollapseWhitespace,
+    StripSiteName,
+    TitleCase,
+    /// Drop emoji (pictographs, flags, and the joiners and modifiers between them)
+    StripEmoji,
+    /// Drop leading bracketed labels like "[Sponsored]" or "(Video)"
+    StripPrefix,
+    /// Lowercase all but the first letter, keeping acronyms unless it's all capitals
+    SentenceCase,
+}
 
-    Ok((title, feed.entries.len()))
+impl TitleTransform {
+    fn apply(self, title: &str) -> String {
+        match self {
+            Self::CollapseWhitespace => title.split_whitespace().collect::<Vec<_>>().join(" "),
+            Self::StripSiteName => strip_site_name(title).to_string(),
+            Self::TitleCase if is_all_caps(title) => title_case(title),
+            Self::TitleCase => title.to_string(),
+            Self::StripEmoji => strip_emoji(title),
+            Self::StripPrefix => strip_prefix(title).to_string(),
+            Self::SentenceCase => sentence_case(title),
+        }
+    }
+}
+
+impl TitleRule {
+    fn matches(&self, headline: &Headline, tags: &[String]) -> bool {
+        self.source.as_ref().is_none_or(|s| s.eq_ignore_ascii_case(&headline.source))
+            && self.tag.as_ref().is_none_or(|t| tags.iter().any(|tag| t.eq_ignore_ascii_case(tag)))
+    }
+}
+
+/// Short words left lowercase inside title-cased headlines
+const MINOR_WORDS: &[&str] = &[
+    "a", "an", "and", "as", "at", "but", "by", "for", "in", "of", "on", "or", "the", "to",
+];
+
+/// Apply the passes of every rule matching a headline from a feed with these tags
+pub fn clean_title(headline: &mut Headline, rules: &[TitleRule], tags: &[String]) {
+    let matching: Vec<&TitleRule> = rules.iter().filter(|r| r.matches(headline, tags)).collect();
+    let switched_on = [
+        (TitleTransform::CollapseWhitespace, matching.iter().any(|r| r.collapse_whitespace)),
+        (TitleTransform::StripSiteName, matching.iter().any(|r| r.strip_site_name)),
+        (TitleTransform::TitleCase, matching.iter().any(|r| r.title_case)),
+    ];
+    let passes = switched_on.into_iter().filter(|(_, on)| *on).map(|(transform, _)| transform);
+    for transform in passes.chain(matching.iter().flat_map(|r| r.transforms.iter().copied())) {
+        headline.title = transform.apply(&headline.title);
+    }
+}
+
+fn strip_site_name(title: &str) -> &str {
+    [" - ", " | ", " – ", " — "]
+        .iter()
+        .filter_map(|separator| title.rfind(separator).map(|i| (i, separator.len())))
+        .max()
+        .filter(|(i, len)| {
+            let site = title[i + len..].trim();
+            *i > 0 && !site.is_empty() && site.split_whitespace().count() <= 4
+        })
+        .map_or(title, |(i, _)| title[..i].trim_end())
+}
+
+fn is_emoji(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x1F000..=0x1FAFF // Pictographs, emoticons, flags, skin tones
+            | 0x2600..=0x27BF // Symbols and dingbats
+            | 0x2B00..=0x2BFF // Stars, arrows
+            | 0xFE00..=0xFE0F // Variation selectors
+            | 0x200D // Zero-width joiner
+            | 0x20E3 // Keycap
+            | 0xE0020..=0xE007F // Tag sequences
+    )
+}
+
+/// The title without emoji, closing up the spaces they leave
+fn strip_emoji(title: &str) -> String {
+    if !title.chars().any(is_emoji) {
+        return title.to_string();
+    }
+    let kept: String = title.chars().filter(|&c| !is_emoji(c)).collect();
+    kept.split_whitespace().collect::<Vec<_>>().join(" ")
 }
 
-/// Create a configured HTTP client
-pub fn create_http_client() -> Result<reqwest::Client> {
-    reqwest::Client::builder()
+/// The title after any leading "[…]" and "(…)" labels, unless that leaves nothing
+fn strip_prefix(title: &str) -> &str {
+    let mut rest = title.trim_start();
+    loop {
+        let close = match rest.chars().next() {
+            Some('[') => ']',
+            Some('(') => ')',
+            _ => break,
+        };
+        match rest.find(close) {
+            Some(end) if !rest[end + 1..].trim().is_empty() => rest = rest[end + 1..].trim_start(),
+            _ => break,
+        }
+    }
+    rest
+}
+
+fn sentence_case(title: &str) -> String {
+    let shouting = is_all_caps(title);
+    let mut first = true;
+    title
+        .split(' ')
+        .map(|word| {
+            // Acronyms and names like iPhone keep their capitals
+            let mixed = word.chars().skip(1).any(char::is_uppercase);
+            let mut word = if shouting || !mixed { word.to_lowercase() } else { word.to_string() };
+            if first && word.chars().any(char::is_alphabetic) {
+                first = false;
+                if let Some((i, c)) = word.char_indices().find(|(_, c)| c.is_alphabetic()) {
+                    word.replace_range(i..i + c.len_utf8(), &c.to_uppercase().to_string());
+                }
+            }
+            word
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn is_all_caps(title: &str) -> bool {
+    title.chars().any(char::is_alphabetic) && !title.chars().any(char::is_lowercase)
+}
+
+fn title_case(title: &str) -> String {
+    title
+        .split(' ')
+        .enumerate()
+        .map(|(i, word)| {
+            let lower = word.to_lowercase();
+            if i > 0 && MINOR_WORDS.contains(&lower.as_str()) {
+                return lower;
+            }
+            let mut chars = lower.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => lower,
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Validate a feed and return status, counting every entry rather than max_per_feed
+pub async fn validate_feed(client: &HttpClient, url: &str, max_age: Duration) -> FeedResult {
+    let status = match fetch_feed(client, url, usize::MAX, max_age).await {
+        Ok(fetched) => fetched.status(),
+        Err(e) => FeedStatus::Error(format!("{:#}", e)),
+    };
+
+    FeedResult { status }
+}
+
+/// Reject error statuses and HTML pages with a message explaining what the server sent
+fn check_response(response: &HttpResponse) -> Result<()> {
+    let html = describe_html_page(response);
+    if !response.is_success() {
+        match html {
+            Some(page) => anyhow::bail!("HTTP {}: {}", status_line(response.status), page),
+            None => anyhow::bail!("HTTP {}", status_line(response.status)),
+        }
+    }
+    if let Some(page) = html {
+        anyhow::bail!("{}", page);
+    }
+    if let Some(encoding) = undecoded_encoding(response) {
+        anyhow::bail!(
+            "response is still {}-compressed (compression is off, or a proxy re-encoded it)",
+            encoding
+        );
+    }
+    Ok(())
+}
+
+/// Compression left on a body that wasn't decoded: a Content-Encoding header (dropped
+/// from responses that were) or gzip's magic bytes
+fn undecoded_encoding(response: &HttpResponse) -> Option<String> {
+    match response.header("content-encoding").map(str::trim) {
+        Some(encoding) if !encoding.eq_ignore_ascii_case("identity") => Some(encoding.to_string()),
+        _ => response.body.starts_with(&[0x1f, 0x8b]).then(|| "gzip".to_string()),
+    }
+}
+
+/// If the response is an HTML page (error page, login wall, bot challenge),
+/// describe it using the page's <title>
+fn describe_html_page(response: &HttpResponse) -> Option<String> {
+    let declared_html = response
+        .header("content-type")
+        .map(|ct| ct.to_ascii_lowercase())
+        .map(|ct| ct.contains("html") && !ct.contains("xhtml+xml"))
+        .unwrap_or(false);
+
+    let head = String::from_utf8_lossy(&response.body[..response.body.len().min(1024)]);
+    let head = head.trim_start_matches('\u{feff}').trim_start().to_ascii_lowercase();
+    let sniffed_html = head.starts_with("<!doctype html") || head.starts_with("<html");
+
+    if !declared_html && !sniffed_html {
+        return None;
+    }
+
+    match html_title(&String::from_utf8_lossy(&response.body)) {
+        Some(title) => Some(format!("server returned an HTML page: '{}'", title)),
+        None => Some("server returned an HTML page instead of a feed".to_string()),
+    }
+}
+
+/// Extract and clean up the contents of an HTML <title> element
+fn html_title(html: &str) -> Option<String> {
+    let lower = html.to_ascii_lowercase();
+    let open = lower.find("<title")?;
+    let start = open + lower[open..].find('>')? + 1;
+    let end = start + lower[start..].find("</title")?;
+
+    let title = html[start..end]
+        .replace("&amp;", "&")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    if title.is_empty() {
+        return None;
+    }
+    Some(title.chars().take(80).collect())
+}
+
+/// Format a status code with its reason phrase, e.g. "404 Not Found"
+pub(crate) fn status_line(status: u16) -> String {
+    reqwest::StatusCode::from_u16(status)
+        .map(|s| s.to_string())
+        .unwrap_or_else(|_| status.to_string())
+}
+
+/// Create a configured HTTP client, recording or replaying fetches if requested
+pub fn create_http_client(config: &Config) -> Result<HttpClient> {
+    let mut builder = reqwest::Client::builder()
         .user_agent("rss-ticker/0.1")
         .timeout(Duration::from_secs(30))
-        .build()
-        .context("Failed to create HTTP client")
+        .gzip(config.compression)
+        .deflate(config.compression)
+        .brotli(config.compression)
+        .pool_idle_timeout(config.pool_idle_timeout);
+    if let Some(max) = config.pool_max_idle_per_host {
+        builder = builder.pool_max_idle_per_host(max);
+    }
+    if config.http2_prior_knowledge {
+        builder = builder.http2_prior_knowledge();
+    }
+    if !config.dns_cache.is_zero() || !config.hosts.is_empty() {
+        let resolver = CachingResolver::new(config.dns_cache, config.hosts.clone());
+        builder = builder.dns_resolver(Arc::new(resolver));
+    }
+    let client = builder.build().context("Failed to create HTTP client")?;
+
+    let client = if let Some(path) = &config.playback {
+        HttpClient::playback(client, path)?
+    } else if let Some(path) = &config.record {
+        HttpClient::recording(client, path)?
+    } else {
+        HttpClient::live(client)
+    };
+    Ok(client.with_fallback_user_agent(config.fallback_user_agent.clone()))
 }
 
 #[cfg(test)]
@@ -172,13 +1001,339 @@ mod tests {
         writeln!(file, "https://example.com/feed.xml").unwrap();
         writeln!(file, "https://example.org/rss \"tag1\" \"tag2\"").unwrap();
         writeln!(file, "# comment").unwrap();
-        writeln!(file, "").unwrap();
+        writeln!(file).unwrap();
         writeln!(file, "https://example.net/atom.xml").unwrap();
 
-        let urls = parse_feeds_file(file.path()).await.unwrap();
-        assert_eq!(urls.len(), 3);
-        assert_eq!(urls[0], "https://example.com/feed.xml");
-        assert_eq!(urls[1], "https://example.org/rss");
-        assert_eq!(urls[2], "https://example.net/atom.xml");
+        let entries = parse_feeds_file(file.path()).await.unwrap();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].url, "https://example.com/feed.xml");
+        assert_eq!(entries[1].url, "https://example.org/rss");
+        assert_eq!(entries[1].tags, vec!["tag1", "tag2"]);
+        assert_eq!(entries[2].url, "https://example.net/atom.xml");
+
+        let dir = tempfile::tempdir().unwrap();
+        let urls_d = dir.path().join("urls.d");
+        std::fs::create_dir(&urls_d).unwrap();
+        std::fs::write(urls_d.join("work"), "https://b.example/rss\nhttps://c.example/rss\n")
+            .unwrap();
+        std::fs::write(urls_d.join("personal"), "https://a.example/rss\n").unwrap();
+        std::fs::write(urls_d.join("work~"), "https://old.example/rss\n").unwrap();
+        let extra = dir.path().join("extra");
+        std::fs::write(&extra, "https://c.example/rss tech\nhttps://d.example/rss\n").unwrap();
+
+        let client = HttpClient::live(reqwest::Client::new());
+        let entries = parse_feeds_files(&client, &[urls_d, extra]).await.unwrap();
+        let urls: Vec<&str> = entries.iter().map(|e| e.url.as_str()).collect();
+        let expected = ["a", "b", "c", "d"].map(|host| format!("https://{}.example/rss", host));
+        assert_eq!(urls, expected);
+        assert!(entries[2].tags.is_empty());
+    }
+
+    #[test]
+    fn test_find_feed_link() {
+        let html = r#"<html><head>
+            <link rel="stylesheet" href="/style.css">
+            <LINK rel="alternate" type="application/rss+xml" data-href="x" href='/feed.xml' />
+            </head></html>"#;
+        assert_eq!(
+            find_feed_link(html, "https://example.com/blog/").as_deref(),
+            Some("https://example.com/feed.xml")
+        );
+        assert_eq!(find_feed_link("<html></html>", "https://example.com/"), None);
+
+        let html = r#"<link rel="icon" href="/favicon.ico">
+            <link rel="shortcut icon" type="image/png" href="/icon-32.png">
+            <link rel="apple-touch-icon" sizes="180x180" href="https://cdn.example.com/t.png">"#;
+        assert_eq!(
+            find_icon_link(html, "https://example.com/").as_deref(),
+            Some("https://cdn.example.com/t.png")
+        );
+        let html = r#"<link rel="icon" href="/favicon.ico"><link rel=icon href="i.png?v=2">"#;
+        assert_eq!(
+            find_icon_link(html, "https://example.com/news/").as_deref(),
+            Some("https://example.com/news/i.png?v=2")
+        );
+        let html = r#"<link rel="icon" href="/favicon.ico">"#;
+        assert_eq!(find_icon_link(html, "https://example.com/"), None);
+    }
+
+    #[test]
+    fn test_is_blocked() {
+        let blocked = vec!["example-spam.com".to_string()];
+        assert!(is_blocked("https://example-spam.com/story", &blocked));
+        assert!(is_blocked("https://www.Example-Spam.com/story", &blocked));
+        assert!(!is_blocked("https://notexample-spam.com/story", &blocked));
+        assert!(!is_blocked("https://example.com/story", &blocked));
+        assert!(!is_blocked("not a url", &blocked));
+    }
+
+    #[test]
+    fn test_mentions_any() {
+        let words = vec!["Earthquake".to_string(), " ".to_string()];
+        assert!(mentions_any("Strong earthquake hits coast", &words));
+        assert!(!mentions_any("Markets calm", &words));
+        assert!(!mentions_any("Markets calm", &[]));
+    }
+
+    /// Serve one response with this body on a local port, returning its URL
+    async fn serve_once(body: &'static str) -> String {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/feed", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = [0u8; 4096];
+            let _ = socket.read(&mut request).await;
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            socket.write_all(response.as_bytes()).await.unwrap();
+        });
+        url
+    }
+
+    #[tokio::test]
+    async fn test_remote_feeds_list() {
+        let url = "https://hq.example/urls";
+        assert_eq!(remote_list(Path::new(url)), Some(url));
+        assert_eq!(remote_list(Path::new("/etc/chyron/urls")), None);
+
+        let url = serve_once("https://a.example/rss \"kiosk\"\nhttps://b.example/rss\n").await;
+        let client = HttpClient::live(reqwest::Client::builder().no_proxy().build().unwrap());
+        let dir = tempfile::tempdir().unwrap();
+        let cache = dir.path().join("feeds").join("urls");
+        let content = fetch_feeds_list(&client, &url, &cache).await.unwrap();
+        assert!(content.starts_with("https://a.example/rss"));
+        // Served once: the second fetch fails and falls back to the saved copy
+        assert_eq!(fetch_feeds_list(&client, &url, &cache).await.unwrap(), content);
+        std::fs::remove_file(&cache).unwrap();
+        assert!(fetch_feeds_list(&client, &url, &cache).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_outdated_entries_kept_aside() {
+        let url = serve_once(
+            r#"<rss version="2.0"><channel><title>Old</title>
+            <item><title>Older</title><pubDate>Mon, 01 Jan 2001 00:00:00 GMT</pubDate></item>
+            <item><title>Newer</title><pubDate>Tue, 02 Jan 2001 00:00:00 GMT</pubDate></item>
+            </channel></rss>"#,
+        )
+        .await;
+        let client = HttpClient::live(reqwest::Client::builder().no_proxy().build().unwrap());
+        let fetched = fetch_feed(&client, &url, 10, Duration::from_secs(3600)).await.unwrap();
+
+        assert!(fetched.headlines.is_empty());
+        let titles: Vec<_> = fetched.outdated.iter().map(|h| h.title.as_str()).collect();
+        assert_eq!(titles, ["Newer", "Older"]);
+        assert_eq!(fetched.expired, 2);
+        assert_eq!(
+            item_summary(0, fetched.expired, Duration::from_secs(24 * 3600)),
+            "0 recent items (2 older than 24h)"
+        );
+        assert_eq!(item_summary(1, 0, Duration::from_secs(90)), "1 recent item");
+
+        let newest = fetched.newest.unwrap();
+        assert_eq!(newest.to_rfc3339(), "2001-01-02T00:00:00+00:00");
+        let fortnight = Duration::from_secs(14 * 86400);
+        assert!(stale_note(Some(newest), fortnight).unwrap().starts_with("stale: nothing in "));
+        assert_eq!(stale_note(Some(newest), Duration::ZERO), None);
+        assert_eq!(stale_note(Some(Utc::now()), fortnight), None);
+        assert_eq!(stale_note(None, fortnight), None);
+    }
+
+    #[tokio::test]
+    async fn test_feed_warnings() {
+        let url = serve_once(
+            r#"<rss version="2.0"><channel>
+            <item><title> </title><link>https://example.com/1</link></item>
+            <item><title>No link</title></item>
+            </channel></rss>"#,
+        )
+        .await;
+        let client = HttpClient::live(reqwest::Client::builder().no_proxy().build().unwrap());
+        let fetched = fetch_feed(&client, &url, 10, Duration::from_secs(3600)).await.unwrap();
+
+        assert_eq!(fetched.title, url);
+        assert_eq!(
+            fetched.warnings,
+            [FeedWarning::NoTitle, FeedWarning::UntitledEntries(1), FeedWarning::UnlinkedEntries(1)]
+        );
+        assert_eq!(fetched.warnings[1].to_string(), "1 untitled entries skipped");
+    }
+
+    #[test]
+    fn test_large_feed_cut_short() {
+        let item = |i: usize| {
+            let text = "x".repeat(2000);
+            format!("<item><title>Story {}</title><description>{}</description></item>", i, text)
+        };
+        let mut feed = r#"<?xml version="1.0"?><rss version="2.0"><channel><title>Planet</title>"#
+            .to_string();
+        feed.extend((0..1000).map(item));
+        feed.push_str("</channel></rss>");
+
+        // Read in chunks that split tags, stopping once enough has arrived
+        let cutoff = Utc::now() - chrono::Duration::hours(24);
+        let mut scan = EntryScan::new(5, cutoff);
+        let mut read = Vec::new();
+        for chunk in feed.as_bytes().chunks(1000) {
+            read.extend_from_slice(chunk);
+            if scan.end(&read).is_some() {
+                break;
+            }
+        }
+        assert!(read.len() < feed.len());
+
+        let end = EntryScan::new(5, cutoff).end(&read).unwrap();
+        let parsed = parser::parse(&close_document(&read[..end])[..]).unwrap();
+        let titles: Vec<_> = parsed.entries.iter().filter_map(|e| e.title.as_ref()).collect();
+        assert_eq!(titles.len(), 5);
+        assert_eq!(titles[4].content, "Story 4");
+
+        // Small feeds are always read in full
+        assert_eq!(EntryScan::new(1, cutoff).end(item(0).as_bytes()), None);
+
+        // Oldest first, with untitled entries: only recent titled ones count toward the cut
+        let dated = |i: usize, date: chrono::DateTime<Utc>, title: &str| {
+            let text = "x".repeat(2000);
+            format!(
+                "<item><title>{}</title><pubDate>{}</pubDate><description>{}</description></item>",
+                title.replace("{}", &i.to_string()),
+                date.to_rfc2822(),
+                text
+            )
+        };
+        let old = Utc::now() - chrono::Duration::days(30);
+        let mut feed = r#"<?xml version="1.0"?><rss version="2.0"><channel><title>Log</title>"#
+            .to_string();
+        feed.extend((0..1000).map(|i| dated(i, old, "Old {}")));
+        feed.extend((0..10).map(|i| dated(i, Utc::now(), "")));
+        feed.extend((0..10).map(|i| dated(i, Utc::now(), "Recent {}")));
+        feed.push_str("</channel></rss>");
+        let end = EntryScan::new(5, cutoff).end(feed.as_bytes()).unwrap();
+        let parsed = parser::parse(&close_document(&feed.as_bytes()[..end])[..]).unwrap();
+        let titles: Vec<_> =
+            parsed.entries.iter().filter_map(|e| Some(e.title.as_ref()?.content.clone())).collect();
+        assert_eq!(titles.len(), 1015);
+        assert_eq!(titles.last().unwrap(), "Recent 4");
+    }
+
+    #[test]
+    fn test_log_warnings() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("warnings.log");
+        let warnings = [FeedWarning::UntitledEntries(2)];
+        log_warnings(&path, "https://a.example/rss", &warnings).unwrap();
+        log_warnings(&path, "https://b.example/rss", &warnings).unwrap();
+        let log = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(log.lines().count(), 2);
+        assert!(log.ends_with("  https://b.example/rss  2 untitled entries skipped\n"));
+
+        // Past the cap, the log moves aside and a fresh one starts
+        std::fs::write(&path, "x".repeat(WARNINGS_LOG_BYTES as usize + 1)).unwrap();
+        log_warnings(&path, "https://c.example/rss", &warnings).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap().lines().count(), 1);
+        assert!(dir.path().join("warnings.log.1").exists());
+    }
+
+    #[test]
+    fn test_clean_title() {
+        let rules: Vec<TitleRule> = toml::from_str::<toml::Table>(
+            r#"
+            [[titles]]
+            collapse_whitespace = true
+            [[titles]]
+            source = "Shouty"
+            strip_site_name = true
+            title_case = true
+            "#,
+        )
+        .unwrap()["titles"]
+            .clone()
+            .try_into()
+            .unwrap();
+        let clean = |title: &str, source: &str| {
+            let mut headline = headline(title, source, None);
+            clean_title(&mut headline, &rules, &[]);
+            headline.title
+        };
+
+        assert_eq!(clean("Rates  rise\n again - Shouty", "Other"), "Rates rise again - Shouty");
+        assert_eq!(clean("FIRE AT THE PLANT - Shouty News", "Shouty"), "Fire at the Plant");
+        assert_eq!(clean("Mixed Case Stays | Shouty", "shouty"), "Mixed Case Stays");
+        // Too long to be a site name
+        let title = "Q&A - what the vote means for the city";
+        assert_eq!(clean(title, "Shouty"), title);
+
+        let rules: Vec<TitleRule> = toml::from_str::<toml::Table>(
+            r#"
+            [[titles]]
+            tag = "promo"
+            transforms = ["strip_prefix", "strip_emoji", "sentence_case"]
+            "#,
+        )
+        .unwrap()["titles"]
+            .clone()
+            .try_into()
+            .unwrap();
+        let promo = ["promo".to_string()];
+        let title = "[Sponsored] (Video) 🚀 NASA Picks New Moon Lander 🇺🇸👍🏽";
+        let mut headline = headline(title, "Space", None);
+        clean_title(&mut headline, &rules, &promo);
+        assert_eq!(headline.title, "NASA picks new moon lander");
+        assert_eq!(TitleTransform::SentenceCase.apply("FED RAISES RATES"), "Fed raises rates");
+        assert_eq!(TitleTransform::StripPrefix.apply("[Sponsored]"), "[Sponsored]");
+    }
+
+    #[test]
+    fn test_html_error_page_diagnostics() {
+        let response = HttpResponse {
+            status: 403,
+            headers: vec![("Content-Type".to_string(), "text/html; charset=UTF-8".to_string())],
+            user_agent: None,
+            body: b"<!DOCTYPE html><html><head><title>Access  denied | example.com</title></head></html>"
+                .to_vec(),
+        };
+        let err = check_response(&response).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "HTTP 403 Forbidden: server returned an HTML page: 'Access denied | example.com'"
+        );
+
+        // Sniffed from the body even when served as 200 with a misleading type
+        let response = HttpResponse {
+            status: 200,
+            headers: vec![("Content-Type".to_string(), "application/xml".to_string())],
+            user_agent: None,
+            body: b"\n  <html><title>Just a moment...</title></html>".to_vec(),
+        };
+        let err = check_response(&response).unwrap_err();
+        assert_eq!(err.to_string(), "server returned an HTML page: 'Just a moment...'");
+
+        let response = HttpResponse {
+            status: 200,
+            headers: vec![("Content-Type".to_string(), "application/rss+xml".to_string())],
+            user_agent: None,
+            body: b"<?xml version=\"1.0\"?><rss/>".to_vec(),
+        };
+        assert!(check_response(&response).is_ok());
+
+        // Compressed bodies that weren't decoded, by header or by gzip's magic bytes
+        let response = HttpResponse {
+            status: 200,
+            headers: vec![("Content-Encoding".to_string(), "br".to_string())],
+            user_agent: None,
+            body: vec![0x0b, 0x02, 0x80],
+        };
+        let err = check_response(&response).unwrap_err();
+        assert!(err.to_string().starts_with("response is still br-compressed"));
+        let response = HttpResponse {
+            headers: Vec::new(),
+            body: vec![0x1f, 0x8b, 0x08, 0x00],
+            ..response
+        };
+        assert!(check_response(&response).unwrap_err().to_string().contains("gzip"));
     }
 }
@@ -0,0 +1,479 @@
+use anyhow::{Context, Result};
+use std::collections::{HashMap, VecDeque};
+use std::fs::File;
+use std::future::Future;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Magic header identifying a chyron fetch recording
+const RECORDING_MAGIC: &[u8] = b"CHYRONREC1";
+
+/// Longest a server's Retry-After or Cache-Control can hold off the next fetch of a URL
+const MAX_SERVER_DELAY: Duration = Duration::from_secs(24 * 3600);
+
+/// Remaining waits this short are treated as over, so a max-age matching the refresh
+/// interval doesn't skip every other refresh
+const DELAY_SLACK: Duration = Duration::from_secs(30);
+
+/// Callback deciding from the body read so far whether the rest of it is needed
+pub type Enough<'a> = dyn FnMut(&[u8]) -> bool + Send + 'a;
+
+/// An HTTP response with its body read (in full, unless the fetch stopped early)
+#[derive(Debug, Clone)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+    /// Fallback User-Agent used after the default one got a 403 (not recorded)
+    pub user_agent: Option<String>,
+}
+
+impl HttpResponse {
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+
+    /// Look up a header value by case-insensitive name
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// How long the server asks clients to wait before fetching again: Retry-After on
+    /// 429 and 503, Cache-Control max-age on success
+    pub fn fetch_delay(&self) -> Option<Duration> {
+        match self.status {
+            429 | 503 => parse_retry_after(self.header("retry-after")?),
+            _ if self.is_success() => parse_max_age(self.header("cache-control")?),
+            _ => None,
+        }
+    }
+}
+
+/// Retry-After as seconds or an HTTP date
+fn parse_retry_after(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    (date.with_timezone(&chrono::Utc) - chrono::Utc::now()).to_std().ok()
+}
+
+/// The max-age directive of a Cache-Control header
+fn parse_max_age(value: &str) -> Option<Duration> {
+    value
+        .split(',')
+        .filter_map(|directive| directive.trim().strip_prefix("max-age="))
+        .find_map(|seconds| seconds.trim_matches('"').parse().ok())
+        .map(Duration::from_secs)
+}
+
+/// One recorded request: the URL and either a response or the error message
+#[derive(Debug, Clone)]
+struct Exchange {
+    url: String,
+    outcome: std::result::Result<HttpResponse, String>,
+}
+
+enum Session {
+    /// Plain network fetches
+    Live,
+    /// Network fetches, with every exchange appended to a recording file
+    Record(Mutex<BufWriter<File>>),
+    /// No network: responses are served from a recording, per URL in order
+    Playback(Mutex<HashMap<String, VecDeque<Exchange>>>),
+}
+
+/// HTTP client used for all feed fetches, optionally recording or replaying them
+pub struct HttpClient {
+    client: reqwest::Client,
+    session: Session,
+    /// User-Agent to retry with once when a server answers 403
+    fallback_user_agent: Option<String>,
+    /// When each URL may next be fetched, as its server asked
+    not_before: Mutex<HashMap<String, Instant>>,
+}
+
+impl HttpClient {
+    pub fn live(client: reqwest::Client) -> Self {
+        Self {
+            client,
+            session: Session::Live,
+            fallback_user_agent: None,
+            not_before: Mutex::default(),
+        }
+    }
+
+    /// Retry 403 responses once with this User-Agent
+    pub fn with_fallback_user_agent(mut self, user_agent: Option<String>) -> Self {
+        self.fallback_user_agent = user_agent;
+        self
+    }
+
+    /// Record every exchange to `path`, replacing any existing recording
+    pub fn recording(client: reqwest::Client, path: &Path) -> Result<Self> {
+        let file = File::create(path)
+            .with_context(|| format!("Failed to create recording: {}", path.display()))?;
+        let mut writer = BufWriter::new(file);
+        writer.write_all(RECORDING_MAGIC)?;
+        writer.flush()?;
+        Ok(Self {
+            client,
+            session: Session::Record(Mutex::new(writer)),
+            fallback_user_agent: None,
+            not_before: Mutex::default(),
+        })
+    }
+
+    /// Serve responses from a recording made with `recording`
+    pub fn playback(client: reqwest::Client, path: &Path) -> Result<Self> {
+        let file = File::open(path)
+            .with_context(|| format!("Failed to open recording: {}", path.display()))?;
+        let mut reader = BufReader::new(file);
+
+        let mut magic = [0u8; RECORDING_MAGIC.len()];
+        reader.read_exact(&mut magic)?;
+        if magic != RECORDING_MAGIC {
+            anyhow::bail!("Not a chyron recording: {}", path.display());
+        }
+
+        let mut exchanges: HashMap<String, VecDeque<Exchange>> = HashMap::new();
+        while let Some(exchange) = read_exchange(&mut reader)? {
+            exchanges
+                .entry(exchange.url.clone())
+                .or_default()
+                .push_back(exchange);
+        }
+
+        Ok(Self {
+            client,
+            session: Session::Playback(Mutex::new(exchanges)),
+            fallback_user_agent: None,
+            not_before: Mutex::default(),
+        })
+    }
+
+    /// Time left before `url` should be fetched again, if its server asked for a pause
+    /// with Retry-After or Cache-Control max-age
+    pub fn wait_before(&self, url: &str) -> Option<Duration> {
+        let not_before = self.not_before.lock().unwrap_or_else(|e| e.into_inner());
+        not_before
+            .get(url)?
+            .checked_duration_since(Instant::now())
+            .filter(|wait| *wait > DELAY_SLACK)
+    }
+
+    /// GET a URL and read the whole body
+    pub async fn get(&self, url: &str) -> Result<HttpResponse> {
+        self.get_until(url, &mut |_| false).await
+    }
+
+    /// GET a URL, reading the body only until `enough` (given the body so far, after each
+    /// chunk) says the rest isn't needed
+    pub async fn get_until(&self, url: &str, enough: &mut Enough<'_>) -> Result<HttpResponse> {
+        self.exchange(url, self.fetch(url, enough)).await
+    }
+
+    /// An API call: any method, extra headers (e.g. auth tokens), and an optional body
+    /// with its content type. Recorded under "METHOD url" unless it's a GET; headers
+    /// and bodies are never written to recordings
+    pub async fn request(
+        &self,
+        method: reqwest::Method,
+        url: &str,
+        headers: &[(&str, &str)],
+        body: Option<(&str, Vec<u8>)>,
+    ) -> Result<HttpResponse> {
+        let key = if method == reqwest::Method::GET {
+            url.to_string()
+        } else {
+            format!("{} {}", method, url)
+        };
+        let mut request = self.client.request(method, url).timeout(Duration::from_secs(30));
+        for (name, value) in headers {
+            request = request.header(*name, *value);
+        }
+        if let Some((content_type, body)) = body {
+            request = request.header(reqwest::header::CONTENT_TYPE, content_type).body(body);
+        }
+        self.exchange(&key, read_response(request, &mut |_| false)).await
+    }
+
+    /// Run a fetch through the session: live, recorded, or served from a recording
+    async fn exchange(
+        &self,
+        url: &str,
+        fetch: impl Future<Output = Result<HttpResponse>>,
+    ) -> Result<HttpResponse> {
+        match &self.session {
+            Session::Live => fetch.await,
+            Session::Record(writer) => {
+                let result = fetch.await;
+                let exchange = Exchange {
+                    url: url.to_string(),
+                    outcome: match &result {
+                        Ok(response) => Ok(response.clone()),
+                        Err(e) => Err(format!("{:#}", e)),
+                    },
+                };
+                let mut writer = writer.lock().unwrap_or_else(|e| e.into_inner());
+                write_exchange(&mut *writer, &exchange)?;
+                writer.flush()?;
+                result
+            }
+            Session::Playback(exchanges) => {
+                let mut exchanges = exchanges.lock().unwrap_or_else(|e| e.into_inner());
+                let queue = exchanges
+                    .get_mut(url)
+                    .with_context(|| format!("No recorded response for {}", url))?;
+                // Keep serving the last response once the recording runs out
+                let exchange = if queue.len() > 1 {
+                    queue.pop_front().unwrap()
+                } else {
+                    queue.front().cloned().unwrap()
+                };
+                exchange.outcome.map_err(|e| anyhow::anyhow!(e))
+            }
+        }
+    }
+
+    /// Fetch with the default User-Agent, retrying a 403 with the fallback if configured
+    async fn fetch(&self, url: &str, enough: &mut Enough<'_>) -> Result<HttpResponse> {
+        let response = self.fetch_as(url, None, enough).await?;
+        self.note_delay(url, &response);
+        match &self.fallback_user_agent {
+            Some(agent) if response.status == 403 => {
+                let mut retry = self.fetch_as(url, Some(agent), enough).await?;
+                if retry.is_success() {
+                    retry.user_agent = Some(agent.clone());
+                    Ok(retry)
+                } else {
+                    Ok(response)
+                }
+            }
+            _ => Ok(response),
+        }
+    }
+
+    /// Remember how long the server asked us to wait before fetching `url` again
+    fn note_delay(&self, url: &str, response: &HttpResponse) {
+        let mut not_before = self.not_before.lock().unwrap_or_else(|e| e.into_inner());
+        match response.fetch_delay() {
+            Some(delay) => {
+                not_before.insert(url.to_string(), Instant::now() + delay.min(MAX_SERVER_DELAY));
+            }
+            None => {
+                not_before.remove(url);
+            }
+        }
+    }
+
+    async fn fetch_as(
+        &self,
+        url: &str,
+        user_agent: Option<&str>,
+        enough: &mut Enough<'_>,
+    ) -> Result<HttpResponse> {
+        let mut request = self.client.get(url).timeout(Duration::from_secs(30));
+        if let Some(agent) = user_agent {
+            request = request.header(reqwest::header::USER_AGENT, agent);
+        }
+        read_response(request, enough).await
+    }
+}
+
+/// Send a request and read the status, headers, and body (until `enough`)
+async fn read_response(
+    request: reqwest::RequestBuilder,
+    enough: &mut Enough<'_>,
+) -> Result<HttpResponse> {
+    let mut response = request.send().await?;
+
+    let status = response.status().as_u16();
+    let headers = response
+        .headers()
+        .iter()
+        .filter_map(|(k, v)| Some((k.to_string(), v.to_str().ok()?.to_string())))
+        .collect();
+    let mut body = Vec::new();
+    loop {
+        match response.chunk().await {
+            Ok(Some(chunk)) => body.extend_from_slice(&chunk),
+            Ok(None) => break,
+            Err(e) if e.is_decode() => {
+                return Err(anyhow::Error::new(e).context(
+                    "Couldn't decompress the response (if a proxy mangles compressed responses, \
+                     set compression = false)",
+                ));
+            }
+            Err(e) => return Err(e.into()),
+        }
+        if enough(&body) {
+            break;
+        }
+    }
+
+    Ok(HttpResponse {
+        status,
+        headers,
+        body,
+        user_agent: None,
+    })
+}
+
+// Recording format, after the magic header, is a sequence of exchanges:
+//   url: bytes | status: u16 (0 = error) | header count: u32 | (name, value): bytes...
+//   | body (or error message): bytes
+// where "bytes" is a little-endian u32 length followed by that many bytes.
+
+fn write_exchange(w: &mut impl Write, exchange: &Exchange) -> Result<()> {
+    write_bytes(w, exchange.url.as_bytes())?;
+    match &exchange.outcome {
+        Ok(response) => {
+            w.write_all(&response.status.to_le_bytes())?;
+            w.write_all(&(response.headers.len() as u32).to_le_bytes())?;
+            for (name, value) in &response.headers {
+                write_bytes(w, name.as_bytes())?;
+                write_bytes(w, value.as_bytes())?;
+            }
+            write_bytes(w, &response.body)?;
+        }
+        Err(message) => {
+            w.write_all(&0u16.to_le_bytes())?;
+            w.write_all(&0u32.to_le_bytes())?;
+            write_bytes(w, message.as_bytes())?;
+        }
+    }
+    Ok(())
+}
+
+fn read_exchange(r: &mut impl Read) -> Result<Option<Exchange>> {
+    let url = match read_bytes(r) {
+        Ok(bytes) => String::from_utf8(bytes)?,
+        Err(e) if is_eof(&e) => return Ok(None),
+        Err(e) => return Err(e),
+    };
+
+    let mut status = [0u8; 2];
+    r.read_exact(&mut status)?;
+    let status = u16::from_le_bytes(status);
+
+    let mut count = [0u8; 4];
+    r.read_exact(&mut count)?;
+    let mut headers = Vec::new();
+    for _ in 0..u32::from_le_bytes(count) {
+        let name = String::from_utf8(read_bytes(r)?)?;
+        let value = String::from_utf8(read_bytes(r)?)?;
+        headers.push((name, value));
+    }
+
+    let body = read_bytes(r)?;
+    let outcome = if status == 0 {
+        Err(String::from_utf8_lossy(&body).into_owned())
+    } else {
+        Ok(HttpResponse {
+            status,
+            headers,
+            body,
+            user_agent: None,
+        })
+    };
+
+    Ok(Some(Exchange { url, outcome }))
+}
+
+fn write_bytes(w: &mut impl Write, bytes: &[u8]) -> Result<()> {
+    w.write_all(&(bytes.len() as u32).to_le_bytes())?;
+    w.write_all(bytes)?;
+    Ok(())
+}
+
+fn read_bytes(r: &mut impl Read) -> Result<Vec<u8>> {
+    let mut len = [0u8; 4];
+    r.read_exact(&mut len)?;
+    let mut bytes = vec![0u8; u32::from_le_bytes(len) as usize];
+    r.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
+
+fn is_eof(e: &anyhow::Error) -> bool {
+    e.downcast_ref::<std::io::Error>()
+        .is_some_and(|e| e.kind() == std::io::ErrorKind::UnexpectedEof)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_exchange_roundtrip() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("session.bin");
+
+        let exchanges = vec![
+            Exchange {
+                url: "https://example.com/feed".to_string(),
+                outcome: Ok(HttpResponse {
+                    status: 200,
+                    headers: vec![("content-type".to_string(), "application/rss+xml".to_string())],
+                    body: b"<rss/>".to_vec(),
+                    user_agent: None,
+                }),
+            },
+            Exchange {
+                url: "https://example.org/feed".to_string(),
+                outcome: Err("connection refused".to_string()),
+            },
+        ];
+
+        let mut file = File::create(&path).unwrap();
+        for exchange in &exchanges {
+            write_exchange(&mut file, exchange).unwrap();
+        }
+        drop(file);
+
+        let mut reader = BufReader::new(File::open(&path).unwrap());
+        let first = read_exchange(&mut reader).unwrap().unwrap();
+        let response = first.outcome.unwrap();
+        assert_eq!(response.status, 200);
+        assert_eq!(response.header("Content-Type"), Some("application/rss+xml"));
+        assert_eq!(response.body, b"<rss/>");
+
+        let second = read_exchange(&mut reader).unwrap().unwrap();
+        assert_eq!(second.outcome.unwrap_err(), "connection refused");
+        assert!(read_exchange(&mut reader).unwrap().is_none());
+
+    }
+
+    #[test]
+    fn test_fetch_delay() {
+        let response = |status: u16, header: (&str, &str)| HttpResponse {
+            status,
+            headers: vec![(header.0.to_string(), header.1.to_string())],
+            body: Vec::new(),
+            user_agent: None,
+        };
+        let delay = |status, header| response(status, header).fetch_delay();
+
+        assert_eq!(delay(429, ("Retry-After", "120")), Some(Duration::from_secs(120)));
+        assert_eq!(delay(200, ("Retry-After", "120")), None);
+        assert_eq!(
+            delay(200, ("Cache-Control", "public, max-age=900, must-revalidate")),
+            Some(Duration::from_secs(900))
+        );
+        assert_eq!(delay(404, ("Cache-Control", "max-age=900")), None);
+
+        let later = chrono::Utc::now() + chrono::Duration::minutes(10);
+        let date = later.format("%a, %d %b %Y %H:%M:%S GMT").to_string();
+        let wait = delay(503, ("Retry-After", &date)).unwrap();
+        assert!(wait > Duration::from_secs(500) && wait <= Duration::from_secs(600));
+        let past = "Wed, 21 Oct 2015 07:28:00 GMT";
+        assert_eq!(delay(503, ("Retry-After", past)), None);
+    }
+}
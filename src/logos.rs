@@ -0,0 +1,209 @@
+use crate::feeds::{self, status_line};
+use crate::http::HttpClient;
+use anyhow::{bail, Context, Result};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Columns a logo takes (one row high)
+pub const LOGO_WIDTH: u16 = 2;
+/// Icons bigger than this aren't worth the bytes for two cells
+const MAX_LOGO_BYTES: usize = 256 * 1024;
+/// Id of the logo in kitty's image store, so the one on screen can be removed
+const KITTY_IMAGE_ID: u32 = 4070;
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+/// PNG by source name: None while it's fetched, or when the site has none
+type Images = HashMap<String, Option<Arc<[u8]>>>;
+
+/// Terminal protocols for drawing images in cells
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageProtocol {
+    /// kitty graphics protocol (kitty, Ghostty)
+    Kitty,
+    /// iTerm2 inline images (iTerm2, WezTerm)
+    Iterm2,
+}
+
+impl ImageProtocol {
+    /// The protocol this terminal draws images with, if any. Multiplexers don't pass them on
+    pub fn detect(env: &dyn Fn(&str) -> Option<String>) -> Option<Self> {
+        let term = env("TERM").unwrap_or_default();
+        if env("TMUX").is_some() || term.starts_with("screen") || term.starts_with("tmux") {
+            return None;
+        }
+        match env("TERM_PROGRAM").as_deref() {
+            Some("iTerm.app" | "WezTerm") => return Some(Self::Iterm2),
+            Some("ghostty") => return Some(Self::Kitty),
+            _ => {}
+        }
+        let kitty = env("KITTY_WINDOW_ID").is_some() || term == "xterm-kitty";
+        kitty.then_some(Self::Kitty)
+    }
+}
+
+/// Source logos for the lower-third bug: fetched in the background the first time a source
+/// leads the crawl, then drawn with the terminal's image protocol
+pub struct Logos {
+    protocol: Option<ImageProtocol>,
+    images: Arc<Mutex<Images>>,
+    /// Source and cell (column, row) of the logo on screen
+    shown: Option<(String, u16, u16)>,
+}
+
+impl Logos {
+    /// No protocol: logos are never drawn (nor fetched)
+    pub fn new(protocol: Option<ImageProtocol>) -> Self {
+        Self {
+            protocol,
+            images: Arc::default(),
+            shown: None,
+        }
+    }
+
+    /// Whether there's a logo to draw for `source`, starting its fetch from the source's
+    /// homepage the first time it's asked for
+    pub fn available(&self, client: &Arc<HttpClient>, source: &str, homepage: Option<&str>) -> bool {
+        if self.protocol.is_none() {
+            return false;
+        }
+        let mut images = self.images.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(image) = images.get(source) {
+            return image.is_some();
+        }
+        images.insert(source.to_string(), None);
+        if let Some(homepage) = homepage {
+            let client = Arc::clone(client);
+            let images = Arc::clone(&self.images);
+            let (source, homepage) = (source.to_string(), homepage.to_string());
+            tokio::spawn(async move {
+                // A source without a usable logo keeps its text icon
+                if let Ok(png) = fetch_logo(&client, &homepage).await {
+                    let mut images = images.lock().unwrap_or_else(|e| e.into_inner());
+                    images.insert(source, Some(png.into()));
+                }
+            });
+        }
+        false
+    }
+
+    /// Escapes drawing the logo for `source` at column x of row y, replacing the one shown;
+    /// nothing if it's already there
+    pub fn draw(&mut self, source: Option<&str>, x: u16, y: u16) -> Vec<u8> {
+        let image = source.and_then(|source| {
+            let images = self.images.lock().unwrap_or_else(|e| e.into_inner());
+            images.get(source).cloned().flatten()
+        });
+        let wanted = source.filter(|_| image.is_some()).map(|s| (s.to_string(), x, y));
+        if wanted == self.shown {
+            return Vec::new();
+        }
+        let mut out = self.hide();
+        if let (Some(protocol), Some(image)) = (self.protocol, image) {
+            out.extend(format!("\x1b[{};{}H", y + 1, x + 1).bytes());
+            out.extend(image_escape(protocol, &image));
+        }
+        self.shown = wanted;
+        out
+    }
+
+    /// Escapes removing the logo from the screen: kitty keeps images above text drawn later,
+    /// while iTerm2 images are simply drawn over
+    pub fn hide(&mut self) -> Vec<u8> {
+        match (self.shown.take(), self.protocol) {
+            (Some(_), Some(ImageProtocol::Kitty)) => {
+                format!("\x1b_Ga=d,d=I,i={},q=2\x1b\\", KITTY_IMAGE_ID).into_bytes()
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// The screen was cleared, taking the logo with it
+    pub fn forget(&mut self) {
+        self.shown = None;
+    }
+}
+
+/// A site's PNG icon: the one its homepage links to, else /apple-touch-icon.png
+async fn fetch_logo(client: &HttpClient, homepage: &str) -> Result<Vec<u8>> {
+    let page = client.get(homepage).await?;
+    let icon = feeds::find_icon_link(&String::from_utf8_lossy(&page.body), homepage)
+        .or_else(|| {
+            let url = reqwest::Url::parse(homepage).ok()?;
+            url.join("/apple-touch-icon.png").ok().map(String::from)
+        })
+        .with_context(|| format!("No icon for {}", homepage))?;
+    let response = client.get(&icon).await?;
+    if !response.is_success() {
+        bail!("HTTP {}", status_line(response.status));
+    }
+    if !response.body.starts_with(PNG_SIGNATURE) || response.body.len() > MAX_LOGO_BYTES {
+        bail!("{} isn't a PNG of at most {} KB", icon, MAX_LOGO_BYTES / 1024);
+    }
+    Ok(response.body)
+}
+
+/// Escape drawing a PNG over LOGO_WIDTH cells from the cursor, scaled to one row
+fn image_escape(protocol: ImageProtocol, png: &[u8]) -> Vec<u8> {
+    let data = BASE64.encode(png);
+    match protocol {
+        ImageProtocol::Iterm2 => format!(
+            "\x1b]1337;File=inline=1;size={};width={};height=1;preserveAspectRatio=1:{}\x07",
+            png.len(),
+            LOGO_WIDTH,
+            data
+        )
+        .into_bytes(),
+        ImageProtocol::Kitty => {
+            // Sent in chunks of at most 4096 bytes, each saying whether more follow (m=1).
+            // C=1 leaves the cursor where it was
+            let chunks: Vec<&[u8]> = data.as_bytes().chunks(4096).collect();
+            let mut out = Vec::with_capacity(data.len() + chunks.len() * 16 + 64);
+            for (i, chunk) in chunks.iter().enumerate() {
+                let more = (i + 1 < chunks.len()) as u8;
+                let control = match i {
+                    0 => format!(
+                        "a=T,f=100,i={},c={},r=1,C=1,q=2,m={}",
+                        KITTY_IMAGE_ID, LOGO_WIDTH, more
+                    ),
+                    _ => format!("m={}", more),
+                };
+                out.extend(format!("\x1b_G{};", control).bytes());
+                out.extend_from_slice(chunk);
+                out.extend(b"\x1b\\");
+            }
+            out
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_image_protocol() {
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            move |name: &str| {
+                vars.iter().find(|(k, _)| *k == name).map(|(_, v)| v.to_string())
+            }
+        };
+        let detect = |vars| ImageProtocol::detect(&env(vars));
+        assert_eq!(detect(&[("TERM_PROGRAM", "WezTerm")]), Some(ImageProtocol::Iterm2));
+        assert_eq!(detect(&[("TERM", "xterm-kitty")]), Some(ImageProtocol::Kitty));
+        assert_eq!(detect(&[("TERM", "xterm-kitty"), ("TMUX", "/tmp/tmux")]), None);
+        assert_eq!(detect(&[("TERM", "xterm-256color")]), None);
+
+        let png = [PNG_SIGNATURE, &[0u8; 4000]].concat();
+        let escape = String::from_utf8(image_escape(ImageProtocol::Kitty, &png)).unwrap();
+        // 4008 bytes make 5344 of base64: two chunks, the first saying more follow
+        assert_eq!(escape.matches("\x1b_G").count(), 2);
+        assert!(escape.starts_with("\x1b_Ga=T,f=100,i=4070,c=2,r=1,C=1,q=2,m=1;iVBORw0KGgo"));
+        assert!(escape.contains("\x1b\\\x1b_Gm=0;"));
+
+        let escape = String::from_utf8(image_escape(ImageProtocol::Iterm2, &png)).unwrap();
+        assert!(escape.starts_with("\x1b]1337;File=inline=1;size=4008;width=2;height=1;"));
+        assert!(escape.ends_with('\x07'));
+    }
+}
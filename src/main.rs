@@ -1,23 +1,122 @@
 mod app;
+mod archive;
+mod backend;
+mod bar;
+mod bench;
 mod cache;
+mod clipboard;
 mod config;
+mod dates;
+mod dns;
+mod doctor;
+mod export;
 mod feeds;
+mod http;
+mod logos;
+mod memory;
+mod overlay;
+mod platform;
+mod plugin;
+mod preview;
+mod print_config;
+mod replay;
+mod rotation;
+mod setup;
+mod source;
+mod stats;
+mod stories;
+mod systemd;
+mod theme;
 mod ticker;
 mod ui;
+mod urls;
 
 use anyhow::Result;
-use clap::Parser;
-use config::{CliArgs, Config};
-use feeds::{FeedStatus, create_http_client, parse_feeds_file, validate_feed};
+use clap::{CommandFactory, Parser};
+use config::{CliArgs, Command, Config, ExportFormat, OutputMode};
+use feeds::{FeedStatus, create_http_client, parse_feeds_files};
+use http::HttpClient;
+use source::HeadlineSource;
+use std::path::PathBuf;
+use std::sync::Arc;
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    let args = CliArgs::parse();
-    let config = Config::load(args)?;
+    let mut args = CliArgs::parse();
+    let command = args.command.take();
+
+    if let Some(Command::Completions { shell }) = command {
+        clap_complete::generate(shell, &mut CliArgs::command(), "chyron", &mut std::io::stdout());
+        return Ok(());
+    }
+
+    // Doctor loads the config itself, so it can report a broken one
+    if let Some(Command::Doctor { fetch }) = command {
+        return doctor::run(args, fetch).await;
+    }
+
+    if args.print_config {
+        return print_config::run(args);
+    }
+
+    let mut config = Config::load(args)?;
+    for warning in &config.warnings {
+        eprintln!("Warning: {}", warning);
+    }
+
+    if let Some(Command::Stats { json }) = command {
+        return print_stats(json);
+    }
+
+    if let Some(Command::History { limit, json }) = command {
+        return print_history(&config, limit, json);
+    }
+
+    if let Some(Command::Bench { headlines, length, wide, frames, width }) = command {
+        let opts = bench::BenchOptions { headlines, length, wide, frames, width };
+        return bench::run(&config, &opts);
+    }
+
+    // One client for everything: a recording holds every request, made once
+    let client = Arc::new(create_http_client(&config)?);
+
+    // Replay and follower modes don't touch feeds at all
+    if config.replay.is_some() || config.follow.is_some() {
+        let mut app = app::App::new(config, client).await?;
+        return app.run().await;
+    }
+
+    // Nothing set up yet: offer to do it interactively (declining leads to the help below)
+    if command.is_none() && !config.validate_only && setup::is_first_run(&config) {
+        setup::run(&mut config, &client).await?;
+    }
+
+    // With an aggregator backend the server does the polling: no feeds file needed
+    if config.backend.is_some() {
+        let sources = source::configured(&config, &[]);
+        if let Some(Command::Export { format, output }) = command {
+            return export_headlines(&config, &client, &sources, format, output).await;
+        }
+        if let Some(Command::Preview) = command {
+            let entries = sources.iter().map(|s| urls::FeedEntry::new(s.key())).collect::<Vec<_>>();
+            return preview::run(&config, &client, &entries, &sources).await;
+        }
+        if config.validate_only {
+            return validate_feeds(&config, &client, &sources).await;
+        }
+        if config.output != OutputMode::Tui {
+            return bar::run(config, &client, sources).await;
+        }
+        let mut app = app::App::new(config, client).await?;
+        return app.run().await;
+    }
 
     // Check if feeds file exists
-    if !config.feeds_path.exists() {
-        eprintln!("Error: Feeds file not found at {}", config.feeds_path.display());
+    let missing = config.feeds_paths.iter().find(|path| {
+        feeds::remote_list(path).is_none() && !path.exists()
+    });
+    if let Some(missing) = missing {
+        eprintln!("Error: Feeds file not found at {}", missing.display());
         eprintln!();
         eprintln!("Create a feeds file with one URL per line:");
         eprintln!("  mkdir -p ~/.config/chyron");
@@ -28,42 +127,136 @@ async fn main() -> Result<()> {
     }
 
     // Parse feed URLs
-    let feed_urls = parse_feeds_file(&config.feeds_path).await?;
+    let feed_entries = parse_feeds_files(&client, &config.feeds_paths).await?;
 
-    if feed_urls.is_empty() {
-        eprintln!("Error: No valid feed URLs found in {}", config.feeds_path.display());
+    if feed_entries.is_empty() {
+        eprintln!("Error: No valid feed URLs found in {}", config.feeds_label());
         eprintln!("Add feed URLs (one per line) to the file.");
         std::process::exit(1);
     }
 
-    println!("Found {} feed(s) in {}", feed_urls.len(), config.feeds_path.display());
+    let sources = source::configured(&config, &feed_entries);
+
+    if let Some(Command::Export { format, output }) = command {
+        return export_headlines(&config, &client, &sources, format, output).await;
+    }
+    if let Some(Command::Preview) = command {
+        return preview::run(&config, &client, &feed_entries, &sources).await;
+    }
+
+    // Status bars show every line, so skip the chatter below
+    if config.output != OutputMode::Tui && !config.validate_only {
+        return bar::run(config, &client, sources).await;
+    }
+
+    println!("Found {} feed(s) in {}", sources.len(), config.feeds_label());
 
     // Validate mode - check all feeds and exit
     if config.validate_only {
-        return validate_feeds(&feed_urls).await;
+        return validate_feeds(&config, &client, &sources).await;
     }
 
     // Run the main application
-    let mut app = app::App::new(config).await?;
+    let mut app = app::App::new(config, client).await?;
     app.run().await
 }
 
-async fn validate_feeds(urls: &[String]) -> Result<()> {
+async fn export_headlines(
+    config: &Config,
+    client: &HttpClient,
+    sources: &[Box<dyn HeadlineSource>],
+    format: Option<ExportFormat>,
+    output: Option<PathBuf>,
+) -> Result<()> {
+    let mut headlines = Vec::new();
+    for source in sources {
+        let mut fetched = source.fetch(client, config).await;
+        if let FeedStatus::Error(e) = fetched.status {
+            eprintln!("Error fetching {}: {}", source.key(), e);
+        }
+        headlines.append(&mut fetched.headlines);
+    }
+    feeds::filter_blocked(&mut headlines, &config.blocked_domains);
+    headlines.truncate(config.max_total);
+
+    // Order exactly as the ticker would
+    let mut ticker = ticker::Ticker::new(config);
+    ticker.set_headlines(headlines, config.sort);
+
+    let format = format.unwrap_or(config.export_format);
+    let content = export::render(ticker.headlines(), format, &config.dates)?;
+    match output {
+        Some(path) => {
+            std::fs::write(&path, content)?;
+            eprintln!("Exported {} headlines to {}", ticker.headline_count(), path.display());
+        }
+        None => print!("{}", content),
+    }
+
+    Ok(())
+}
+
+fn print_stats(json: bool) -> Result<()> {
+    let stats = stats::Stats::load()?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&stats)?);
+    } else {
+        for line in stats.summary() {
+            println!("{}", line);
+        }
+    }
+    Ok(())
+}
+
+fn print_history(config: &Config, limit: usize, json: bool) -> Result<()> {
+    for entry in archive::read_history(&archive::history_path(), limit)? {
+        if json {
+            println!("{}", serde_json::to_string(&entry)?);
+        } else {
+            println!(
+                "{}\t{}\t{}\t{}",
+                config.dates.format(entry.opened_at, "%Y-%m-%d %H:%M:%S"),
+                entry.source,
+                entry.title,
+                entry.url
+            );
+        }
+    }
+    Ok(())
+}
+
+async fn validate_feeds(
+    config: &Config,
+    client: &HttpClient,
+    sources: &[Box<dyn HeadlineSource>],
+) -> Result<()> {
     println!();
-    println!("Validating {} feed(s)...", urls.len());
+    println!("Validating {} feed(s)...", sources.len());
     println!();
 
-    let client = create_http_client()?;
     let mut success_count = 0;
+    let mut stale_count = 0;
     let mut error_count = 0;
 
-    for url in urls {
-        let result = validate_feed(&client, url).await;
+    for source in sources {
+        let url = source.key();
+        let status = source.validate(client, config).await;
 
-        match result.status {
-            FeedStatus::Ok { title, item_count } => {
-                println!("  ✓ {} ({} items)", title, item_count);
+        match status {
+            FeedStatus::Ok { title, item_count, expired, newest, user_agent } => {
+                let items = feeds::item_summary(item_count, expired, config.max_age);
+                match user_agent {
+                    Some(agent) => println!(
+                        "  ✓ {} ({}, 403 with default User-Agent, ok with \"{}\")",
+                        title, items, agent
+                    ),
+                    None => println!("  ✓ {} ({})", title, items),
+                }
                 println!("    {}", url);
+                if let Some(note) = feeds::stale_note(newest, config.stale_after) {
+                    println!("    ⚠ {}", note);
+                    stale_count += 1;
+                }
                 success_count += 1;
             }
             FeedStatus::Error(err) => {
@@ -75,7 +268,13 @@ async fn validate_feeds(urls: &[String]) -> Result<()> {
     }
 
     println!();
-    println!("Summary: {} ok, {} failed", success_count, error_count);
+    match stale_count {
+        0 => println!("Summary: {} ok, {} failed", success_count, error_count),
+        _ => println!(
+            "Summary: {} ok ({} stale), {} failed",
+            success_count, stale_count, error_count
+        ),
+    }
 
     if error_count > 0 {
         std::process::exit(1);
@@ -0,0 +1,84 @@
+use crate::feeds::Headline;
+use std::collections::HashSet;
+use std::mem::size_of;
+
+/// Rough footprint of retained headlines and caches, by kind, for the memory budget and
+/// the stats overlay
+#[derive(Debug, Default)]
+pub struct Usage {
+    /// Name, bytes, and entry count of each kind
+    parts: Vec<(&'static str, usize, usize)>,
+}
+
+impl Usage {
+    pub fn add(&mut self, name: &'static str, bytes: usize, count: usize) {
+        self.parts.push((name, bytes, count));
+    }
+
+    pub fn total(&self) -> usize {
+        self.parts.iter().map(|(_, bytes, _)| bytes).sum()
+    }
+
+    /// One line per kind, then the total against the budget (if any)
+    pub fn lines(&self, budget: Option<usize>) -> Vec<String> {
+        let mut lines: Vec<String> = self
+            .parts
+            .iter()
+            .map(|(name, bytes, count)| format!("{:>9}  {} ({})", format_bytes(*bytes), name, count))
+            .collect();
+        let total = format_bytes(self.total());
+        lines.push(match budget {
+            Some(budget) => format!("{:>9}  total, budget {}", total, format_bytes(budget)),
+            None => format!("{:>9}  total", total),
+        });
+        lines
+    }
+}
+
+/// Approximate size of a headline, its strings included
+pub fn headline_bytes(headline: &Headline) -> usize {
+    size_of::<Headline>()
+        + headline.title.capacity()
+        + headline.source.capacity()
+        + headline.url.as_ref().map_or(0, String::capacity)
+        + headline.source_url.as_ref().map_or(0, String::capacity)
+}
+
+pub fn headlines_bytes<'a>(headlines: impl IntoIterator<Item = &'a Headline>) -> usize {
+    headlines.into_iter().map(headline_bytes).sum()
+}
+
+/// Approximate size of a set of keys, table included
+pub fn keys_bytes(keys: &HashSet<String>) -> usize {
+    keys.capacity() * (size_of::<String>() + 1) + keys.iter().map(String::capacity).sum::<usize>()
+}
+
+/// Bytes in KB or MB
+pub fn format_bytes(bytes: usize) -> String {
+    if bytes >= 1024 * 1024 {
+        format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
+    } else {
+        format!("{} KB", bytes.div_ceil(1024))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_usage_lines() {
+        let mut usage = Usage::default();
+        usage.add("feed headlines", 3 * 1024 * 1024 / 2, 400);
+        usage.add("shown keys", 1000, 20);
+        assert_eq!(usage.total(), 3 * 1024 * 1024 / 2 + 1000);
+        assert_eq!(
+            usage.lines(Some(16 * 1024 * 1024)),
+            vec![
+                "   1.5 MB  feed headlines (400)",
+                "     1 KB  shown keys (20)",
+                "   1.5 MB  total, budget 16.0 MB",
+            ]
+        );
+    }
+}
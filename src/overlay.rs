@@ -0,0 +1,701 @@
+use crate::archive::OpenedRecord;
+use crate::config::ClickAction;
+use crate::dates::DateStyle;
+use crate::feeds::{self, FeedHealth, FeedStatus, Headline};
+use crate::ui::truncate;
+use crate::urls::{FeedEntry, UrlsFile};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Clear, Widget},
+};
+use std::collections::{BTreeSet, HashMap};
+use std::time::Duration;
+
+/// Popup drawn over the ticker that captures keyboard and mouse input
+pub enum Overlay {
+    Menu(ContextMenu),
+    Panel(Panel),
+    History(HistoryList),
+    Feeds(FeedList),
+    Manager(FeedManager),
+}
+
+/// Actions offered in the headline context menu, in display order
+const MENU_ITEMS: &[(ClickAction, &str)] = &[
+    (ClickAction::Open, "Open"),
+    (ClickAction::Copy, "Copy URL"),
+    (ClickAction::Bookmark, "Bookmark"),
+    (ClickAction::Mute, "Mute source"),
+    (ClickAction::Skip, "Skip"),
+    (ClickAction::Pin, "Pin / unpin"),
+    (ClickAction::Center, "Pause and center"),
+];
+
+/// Right-click menu of per-headline actions
+pub struct ContextMenu {
+    pub headline: Headline,
+    pub selected: usize,
+    /// Border title: the headline's source, or a generic one in safe mode
+    title: String,
+    /// Screen cell that was clicked
+    anchor_x: u16,
+    /// Row of the ticker line the menu belongs to
+    ticker_row: u16,
+}
+
+impl ContextMenu {
+    pub fn new(headline: Headline, anchor_x: u16, ticker_row: u16, safe_mode: bool) -> Self {
+        let title = if safe_mode {
+            "Headline".to_string()
+        } else {
+            headline.source.chars().take(30).collect()
+        };
+        Self {
+            headline,
+            selected: 0,
+            title,
+            anchor_x,
+            ticker_row,
+        }
+    }
+
+    pub fn select_next(&mut self) {
+        self.selected = (self.selected + 1) % MENU_ITEMS.len();
+    }
+
+    pub fn select_prev(&mut self) {
+        self.selected = (self.selected + MENU_ITEMS.len() - 1) % MENU_ITEMS.len();
+    }
+
+    pub fn selected_action(&self) -> ClickAction {
+        MENU_ITEMS[self.selected].0
+    }
+
+    /// Menu item under a screen position, if any
+    pub fn item_at(&self, screen: Rect, x: u16, y: u16) -> Option<usize> {
+        let area = self.area(screen);
+        let inner_top = area.y + 1;
+        if x <= area.x || x >= area.right().saturating_sub(1) || y < inner_top {
+            return None;
+        }
+        let idx = (y - inner_top) as usize;
+        (idx < MENU_ITEMS.len()).then_some(idx)
+    }
+
+    /// Where the menu is drawn: below the ticker row if it fits, otherwise above,
+    /// so it never covers the line it refers to
+    pub fn area(&self, screen: Rect) -> Rect {
+        let width = (MENU_ITEMS.iter().map(|(_, l)| l.len()).max().unwrap_or(0) as u16 + 4)
+            .max(self.title.chars().count() as u16 + 4)
+            .min(screen.width);
+        let height = (MENU_ITEMS.len() as u16 + 2).min(screen.height);
+
+        let x = self.anchor_x.min(screen.right().saturating_sub(width));
+        let below = self.ticker_row + 1;
+        let y = if below + height <= screen.bottom() {
+            below
+        } else {
+            self.ticker_row.saturating_sub(height)
+        };
+        Rect::new(x, y, width, height)
+    }
+}
+
+/// Widget drawing a context menu at its computed position
+pub struct ContextMenuWidget<'a> {
+    menu: &'a ContextMenu,
+}
+
+impl<'a> ContextMenuWidget<'a> {
+    pub fn new(menu: &'a ContextMenu) -> Self {
+        Self { menu }
+    }
+}
+
+impl Widget for ContextMenuWidget<'_> {
+    fn render(self, screen: Rect, buf: &mut Buffer) {
+        let area = self.menu.area(screen);
+        if area.width < 3 || area.height < 3 {
+            return;
+        }
+
+        Clear.render(area, buf);
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(self.menu.title.as_str())
+            .border_style(Style::default().fg(Color::DarkGray));
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        for (i, (_, label)) in MENU_ITEMS.iter().enumerate() {
+            if i as u16 >= inner.height {
+                break;
+            }
+            let style = if i == self.menu.selected {
+                Style::default().fg(Color::Black).bg(Color::Cyan)
+            } else {
+                Style::default()
+            };
+            let row = Rect::new(inner.x, inner.y + i as u16, inner.width, 1);
+            buf.set_style(row, style);
+            Line::styled(format!(" {}", label), style).render(row, buf);
+        }
+    }
+}
+
+/// Centered read-only text box (stats)
+pub struct Panel {
+    pub title: String,
+    pub lines: Vec<String>,
+}
+
+impl Panel {
+    pub fn new(title: impl Into<String>, lines: Vec<String>) -> Self {
+        Self {
+            title: title.into(),
+            lines,
+        }
+    }
+
+    /// Where the panel is drawn: centered, sized to its content
+    pub fn area(&self, screen: Rect) -> Rect {
+        let content_width = self
+            .lines
+            .iter()
+            .map(|l| l.chars().count())
+            .chain(std::iter::once(self.title.chars().count()))
+            .max()
+            .unwrap_or(0) as u16;
+        let width = (content_width + 4).min(screen.width);
+        let height = (self.lines.len() as u16 + 2).min(screen.height);
+        Rect::new(
+            screen.x + (screen.width - width) / 2,
+            screen.y + (screen.height - height) / 2,
+            width,
+            height,
+        )
+    }
+}
+
+/// Widget drawing a panel in the middle of the screen
+pub struct PanelWidget<'a> {
+    panel: &'a Panel,
+}
+
+impl<'a> PanelWidget<'a> {
+    pub fn new(panel: &'a Panel) -> Self {
+        Self { panel }
+    }
+}
+
+impl Widget for PanelWidget<'_> {
+    fn render(self, screen: Rect, buf: &mut Buffer) {
+        let area = self.panel.area(screen);
+        if area.width < 3 || area.height < 3 {
+            return;
+        }
+
+        Clear.render(area, buf);
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(self.panel.title.as_str())
+            .border_style(Style::default().fg(Color::DarkGray));
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        for (i, line) in self.panel.lines.iter().enumerate().take(inner.height as usize) {
+            let row = Rect::new(inner.x, inner.y + i as u16, inner.width, 1);
+            Line::raw(format!(" {}", line)).render(row, buf);
+        }
+    }
+}
+
+/// Recently opened headlines, selectable for re-opening
+pub struct HistoryList {
+    pub entries: Vec<OpenedRecord>,
+    pub selected: usize,
+}
+
+impl HistoryList {
+    pub fn new(entries: Vec<OpenedRecord>) -> Self {
+        Self {
+            entries,
+            selected: 0,
+        }
+    }
+
+    pub fn select_next(&mut self) {
+        if !self.entries.is_empty() {
+            self.selected = (self.selected + 1) % self.entries.len();
+        }
+    }
+
+    pub fn select_prev(&mut self) {
+        if !self.entries.is_empty() {
+            self.selected = (self.selected + self.entries.len() - 1) % self.entries.len();
+        }
+    }
+
+    pub fn selected_entry(&self) -> Option<&OpenedRecord> {
+        self.entries.get(self.selected)
+    }
+
+    /// Entry under a screen position, if any
+    pub fn item_at(&self, screen: Rect, x: u16, y: u16) -> Option<usize> {
+        list_item_at(list_area(screen, self.entries.len()), self.selected, self.entries.len(), x, y)
+    }
+
+    fn label(entry: &OpenedRecord, dates: &DateStyle) -> String {
+        format!(
+            " {}  [{}] {}",
+            dates.format(entry.opened_at, "%m-%d %H:%M"),
+            entry.source,
+            entry.title
+        )
+    }
+}
+
+/// Widget drawing the history list in the middle of the screen
+pub struct HistoryWidget<'a> {
+    list: &'a HistoryList,
+    dates: &'a DateStyle,
+}
+
+impl<'a> HistoryWidget<'a> {
+    pub fn new(list: &'a HistoryList, dates: &'a DateStyle) -> Self {
+        Self { list, dates }
+    }
+}
+
+impl Widget for HistoryWidget<'_> {
+    fn render(self, screen: Rect, buf: &mut Buffer) {
+        let rows = self
+            .list
+            .entries
+            .iter()
+            .map(|entry| (HistoryList::label(entry, self.dates), Style::default()))
+            .collect();
+        render_list(
+            screen,
+            buf,
+            "Recently opened (Enter to re-open)",
+            rows,
+            self.list.selected,
+            " Nothing opened yet",
+        );
+    }
+}
+
+/// Configured feeds with the outcome of their latest fetch
+pub struct FeedList {
+    pub urls: Vec<String>,
+    pub selected: usize,
+}
+
+impl FeedList {
+    pub fn new(urls: Vec<String>) -> Self {
+        Self { urls, selected: 0 }
+    }
+
+    pub fn select_next(&mut self) {
+        if !self.urls.is_empty() {
+            self.selected = (self.selected + 1) % self.urls.len();
+        }
+    }
+
+    pub fn select_prev(&mut self) {
+        if !self.urls.is_empty() {
+            self.selected = (self.selected + self.urls.len() - 1) % self.urls.len();
+        }
+    }
+
+    pub fn selected_url(&self) -> Option<&str> {
+        self.urls.get(self.selected).map(String::as_str)
+    }
+
+    /// Feed under a screen position, if any
+    pub fn item_at(&self, screen: Rect, x: u16, y: u16) -> Option<usize> {
+        list_item_at(list_area(screen, self.urls.len()), self.selected, self.urls.len(), x, y)
+    }
+
+    fn label(
+        url: &str,
+        health: Option<&FeedHealth>,
+        disabled: bool,
+        dates: &DateStyle,
+        stale_after: Duration,
+    ) -> (String, Style) {
+        if disabled {
+            return (format!(" ○  {}  (off)", url), Style::default().fg(Color::DarkGray));
+        }
+        match health.map(|h| (&h.status, h.last_success)) {
+            None => (format!(" ·  {}", url), Style::default().fg(Color::DarkGray)),
+            Some((FeedStatus::Ok { title, item_count, expired, newest, .. }, _)) => {
+                let mut items = format!("{} items", item_count);
+                if *expired > 0 {
+                    items.push_str(&format!(", {} too old", expired));
+                }
+                let warnings = health.map_or(&[][..], |h| h.warnings.as_slice());
+                let stale = feeds::stale_note(*newest, stale_after);
+                if warnings.is_empty() && stale.is_none() {
+                    return (format!(" ✓  {} ({})  {}", title, items, url), Style::default());
+                }
+                let warnings: Vec<String> =
+                    stale.into_iter().chain(warnings.iter().map(ToString::to_string)).collect();
+                let text = format!(" ⚠  {} ({})  {}  {}", title, items, url, warnings.join("; "));
+                (text, Style::default().fg(Color::Yellow))
+            }
+            Some((FeedStatus::Error(e), last_success)) => {
+                let last_ok = match last_success {
+                    Some(at) => {
+                        format!(" (last ok {})", dates.format(at, "%m-%d %H:%M"))
+                    }
+                    None => String::new(),
+                };
+                (format!(" ✗  {}  {}{}", url, e, last_ok), Style::default().fg(Color::Red))
+            }
+        }
+    }
+}
+
+/// Widget drawing the feed list in the middle of the screen
+pub struct FeedListWidget<'a> {
+    list: &'a FeedList,
+    health: &'a HashMap<String, FeedHealth>,
+    disabled: &'a BTreeSet<String>,
+    dates: &'a DateStyle,
+    stale_after: Duration,
+}
+
+impl<'a> FeedListWidget<'a> {
+    pub fn new(
+        list: &'a FeedList,
+        health: &'a HashMap<String, FeedHealth>,
+        disabled: &'a BTreeSet<String>,
+        dates: &'a DateStyle,
+        stale_after: Duration,
+    ) -> Self {
+        Self {
+            list,
+            health,
+            disabled,
+            dates,
+            stale_after,
+        }
+    }
+}
+
+impl Widget for FeedListWidget<'_> {
+    fn render(self, screen: Rect, buf: &mut Buffer) {
+        let rows = self
+            .list
+            .urls
+            .iter()
+            .map(|url| {
+                let disabled = self.disabled.contains(url);
+                FeedList::label(url, self.health.get(url), disabled, self.dates, self.stale_after)
+            })
+            .collect();
+        render_list(
+            screen,
+            buf,
+            "Feeds (r: refresh, space: on/off)",
+            rows,
+            self.list.selected,
+            " No feeds configured",
+        );
+    }
+}
+
+/// What the feed manager's input line is asking for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PromptKind {
+    Add,
+    Rename,
+    Tags,
+    Remove,
+}
+
+/// Text being typed into the feed manager's input line
+pub struct Prompt {
+    pub kind: PromptKind,
+    pub input: String,
+}
+
+/// Full-screen editor for the urls file
+pub struct FeedManager {
+    pub file: UrlsFile,
+    pub selected: usize,
+    pub prompt: Option<Prompt>,
+}
+
+impl FeedManager {
+    pub fn new(file: UrlsFile) -> Self {
+        Self {
+            file,
+            selected: 0,
+            prompt: None,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.file.entries().len()
+    }
+
+    pub fn select_next(&mut self) {
+        let len = self.len();
+        if len > 0 {
+            self.selected = (self.selected + 1) % len;
+        }
+    }
+
+    pub fn select_prev(&mut self) {
+        let len = self.len();
+        if len > 0 {
+            self.selected = (self.selected + len - 1) % len;
+        }
+    }
+
+    /// Keep the selection on a feed after the list shrank
+    pub fn clamp_selection(&mut self) {
+        self.selected = self.selected.min(self.len().saturating_sub(1));
+    }
+
+    pub fn selected_entry(&self) -> Option<FeedEntry> {
+        self.file.entries().into_iter().nth(self.selected)
+    }
+
+    /// Start prompting, prefilled with the selected feed's current value where there is one
+    pub fn prompt(&mut self, kind: PromptKind) {
+        let entry = self.selected_entry();
+        let input = match (kind, entry) {
+            (PromptKind::Rename, Some(entry)) => entry.name.unwrap_or_default(),
+            (PromptKind::Tags, Some(entry)) => entry.tags.join(", "),
+            (PromptKind::Add, _) => String::new(),
+            (_, None) => return,
+            (PromptKind::Remove, Some(_)) => String::new(),
+        };
+        self.prompt = Some(Prompt { kind, input });
+    }
+
+    /// Feed under a screen row, if any
+    pub fn item_at(&self, screen: Rect, y: u16) -> Option<usize> {
+        let top = screen.y + MANAGER_HEADER_ROWS;
+        if y < top || y >= screen.bottom().saturating_sub(MANAGER_FOOTER_ROWS) {
+            return None;
+        }
+        let idx = self.first_visible(screen) + (y - top) as usize;
+        (idx < self.len()).then_some(idx)
+    }
+
+    /// First feed shown, scrolled so the selection stays visible
+    fn first_visible(&self, screen: Rect) -> usize {
+        let rows = screen
+            .height
+            .saturating_sub(MANAGER_HEADER_ROWS + MANAGER_FOOTER_ROWS)
+            .max(1) as usize;
+        self.selected.saturating_sub(rows - 1)
+    }
+}
+
+/// Title and column header rows above the feed manager's list
+const MANAGER_HEADER_ROWS: u16 = 2;
+
+/// Detail and input/help rows below the feed manager's list
+const MANAGER_FOOTER_ROWS: u16 = 2;
+
+/// Widget drawing the feed manager over the whole screen
+pub struct FeedManagerWidget<'a> {
+    manager: &'a FeedManager,
+    health: &'a HashMap<String, FeedHealth>,
+    disabled: &'a BTreeSet<String>,
+}
+
+impl<'a> FeedManagerWidget<'a> {
+    pub fn new(
+        manager: &'a FeedManager,
+        health: &'a HashMap<String, FeedHealth>,
+        disabled: &'a BTreeSet<String>,
+    ) -> Self {
+        Self {
+            manager,
+            health,
+            disabled,
+        }
+    }
+
+    fn row(&self, entry: &FeedEntry) -> (String, Style) {
+        let health = self.health.get(&entry.url);
+        let (marker, title, items, style) = match health.map(|h| &h.status) {
+            _ if self.disabled.contains(&entry.url) => {
+                ("○", None, String::new(), Style::default().fg(Color::DarkGray))
+            }
+            Some(FeedStatus::Ok { title, item_count, .. }) => {
+                ("✓", Some(title.as_str()), item_count.to_string(), Style::default())
+            }
+            Some(FeedStatus::Error(_)) => {
+                ("✗", None, String::new(), Style::default().fg(Color::Red))
+            }
+            None => ("·", None, String::new(), Style::default().fg(Color::DarkGray)),
+        };
+        let name = entry.name.as_deref().or(title).unwrap_or("");
+        let text = format!(
+            " {} {} {:>5}  {} {}",
+            marker,
+            cell(name, 30),
+            items,
+            cell(&entry.tags.join(","), 16),
+            entry.url
+        );
+        (text, style)
+    }
+}
+
+impl Widget for FeedManagerWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.width < 3 || area.height < MANAGER_HEADER_ROWS + MANAGER_FOOTER_ROWS + 1 {
+            return;
+        }
+        Clear.render(area, buf);
+        let line = |buf: &mut Buffer, y: u16, text: String, style: Style| {
+            let rect = Rect::new(area.x, y, area.width, 1);
+            buf.set_style(rect, style);
+            Line::styled(text, style).render(rect, buf);
+        };
+
+        let title = format!(" Feeds: {}", self.manager.file.path().display());
+        line(buf, area.y, title, Style::default().fg(Color::Black).bg(Color::Cyan));
+        let header = format!("   {} {:>5}  {} URL", cell("Name", 30), "Items", cell("Tags", 16));
+        line(buf, area.y + 1, header, Style::default().fg(Color::DarkGray));
+
+        let entries = self.manager.file.entries();
+        let list_rows = area.height - MANAGER_HEADER_ROWS - MANAGER_FOOTER_ROWS;
+        let first = self.manager.first_visible(area);
+        for (row, (i, entry)) in entries
+            .iter()
+            .enumerate()
+            .skip(first)
+            .take(list_rows as usize)
+            .enumerate()
+        {
+            let (text, style) = self.row(entry);
+            let style = if i == self.manager.selected {
+                Style::default().fg(Color::Black).bg(Color::Cyan)
+            } else {
+                style
+            };
+            line(buf, area.y + MANAGER_HEADER_ROWS + row as u16, text, style);
+        }
+        if entries.is_empty() {
+            let text = " No feeds yet: press a to add one".to_string();
+            line(buf, area.y + MANAGER_HEADER_ROWS, text, Style::default());
+        }
+
+        // The selected feed's error, if its last fetch failed
+        let detail = self.manager.selected_entry().and_then(|entry| {
+            match self.health.get(&entry.url).map(|h| &h.status) {
+                Some(FeedStatus::Error(e)) => Some(format!(" {}", e)),
+                _ => None,
+            }
+        });
+        let bottom = area.bottom();
+        line(buf, bottom - 2, detail.unwrap_or_default(), Style::default().fg(Color::Red));
+
+        let footer = match &self.manager.prompt {
+            Some(prompt) => {
+                let label = match prompt.kind {
+                    PromptKind::Add => "Add feed (page or feed URL)",
+                    PromptKind::Rename => "Display name (empty for the feed's title)",
+                    PromptKind::Tags => "Tags (comma separated)",
+                    PromptKind::Remove => "Remove this feed? [y/N]",
+                };
+                format!(" {}: {}█", label, prompt.input)
+            }
+            None => " a add  d remove  n rename  t tags  J/K move  r refresh  space on/off  Esc close"
+                .to_string(),
+        };
+        line(buf, bottom - 1, footer, Style::default().add_modifier(Modifier::BOLD));
+    }
+}
+
+/// Text cut or padded to exactly width columns
+fn cell(text: &str, width: usize) -> String {
+    format!("{:<width$}", truncate(text, width), width = width)
+}
+
+/// Centered box for a scrolling list of rows, as wide as the screen allows
+fn list_area(screen: Rect, rows: usize) -> Rect {
+    let width = screen.width.min(100);
+    let height = (rows.max(1) as u16 + 2).min(screen.height);
+    Rect::new(
+        screen.x + (screen.width - width) / 2,
+        screen.y + (screen.height - height) / 2,
+        width,
+        height,
+    )
+}
+
+/// First row shown, scrolled so the selection stays visible
+fn first_visible(selected: usize, area: Rect) -> usize {
+    let rows = area.height.saturating_sub(2).max(1) as usize;
+    selected.saturating_sub(rows - 1)
+}
+
+/// List row under a screen position, if any
+fn list_item_at(area: Rect, selected: usize, len: usize, x: u16, y: u16) -> Option<usize> {
+    let inner_top = area.y + 1;
+    if x <= area.x || x >= area.right().saturating_sub(1) || y < inner_top {
+        return None;
+    }
+    let idx = first_visible(selected, area) + (y - inner_top) as usize;
+    (idx < len && y < area.bottom().saturating_sub(1)).then_some(idx)
+}
+
+/// Draw a bordered, scrolling list with the selected row highlighted
+fn render_list(
+    screen: Rect,
+    buf: &mut Buffer,
+    title: &str,
+    rows: Vec<(String, Style)>,
+    selected: usize,
+    empty: &str,
+) {
+    let area = list_area(screen, rows.len());
+    if area.width < 3 || area.height < 3 {
+        return;
+    }
+
+    Clear.render(area, buf);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(title)
+        .border_style(Style::default().fg(Color::DarkGray));
+    let inner = block.inner(area);
+    block.render(area, buf);
+
+    if rows.is_empty() {
+        Line::raw(empty).render(inner, buf);
+        return;
+    }
+
+    let first = first_visible(selected, area);
+    for (row, (i, (label, style))) in rows
+        .into_iter()
+        .enumerate()
+        .skip(first)
+        .take(inner.height as usize)
+        .enumerate()
+    {
+        let style = if i == selected {
+            Style::default().fg(Color::Black).bg(Color::Cyan)
+        } else {
+            style
+        };
+        let rect = Rect::new(inner.x, inner.y + row as u16, inner.width, 1);
+        buf.set_style(rect, style);
+        Line::styled(label, style).render(rect, buf);
+    }
+}
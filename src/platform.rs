@@ -0,0 +1,78 @@
+use std::process::Command;
+
+/// The command that opens a URL in the default browser
+pub fn open_command(url: &str) -> Command {
+    #[cfg(target_os = "macos")]
+    {
+        let mut command = Command::new("open");
+        command.arg(url);
+        command
+    }
+    #[cfg(windows)]
+    {
+        // Arguments are quoted for programs that parse them like C runtimes do, which
+        // cmd.exe doesn't, so the line is built by hand. The empty title keeps `start`
+        // from taking the URL as one
+        use std::os::windows::process::CommandExt;
+        let mut command = Command::new("cmd");
+        command.raw_arg(format!("/C start \"\" {}", cmd_escape(url)));
+        command
+    }
+    #[cfg(not(any(target_os = "macos", windows)))]
+    {
+        let mut command = Command::new("xdg-open");
+        command.arg(url);
+        command
+    }
+}
+
+/// Escape cmd.exe's special characters with carets, so a URL reaches `start` whole (`&`
+/// would end the command there, `%` expand variables). Spaces are percent-encoded
+#[cfg(any(windows, test))]
+fn cmd_escape(url: &str) -> String {
+    let mut escaped = String::with_capacity(url.len());
+    for c in url.chars() {
+        match c {
+            ' ' => escaped.push_str("^%20"),
+            '^' | '&' | '|' | '<' | '>' | '(' | ')' | '"' | '%' | '!' => {
+                escaped.push('^');
+                escaped.push(c);
+            }
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Whether this is the classic Windows console (conhost) rather than Windows Terminal or
+/// another emulator: it prints OSC 8 hyperlinks as garbage instead of rendering them
+pub fn is_conhost(env: &dyn Fn(&str) -> Option<String>) -> bool {
+    env("OS").as_deref() == Some("Windows_NT")
+        && env("WT_SESSION").is_none()
+        && env("TERM_PROGRAM").is_none()
+        && env("TERM").is_none()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cmd_escape() {
+        assert_eq!(
+            cmd_escape("https://a.example/?q=rates&page=2"),
+            "https://a.example/?q=rates^&page=2"
+        );
+        assert_eq!(cmd_escape("https://a.example/%PATH%/x y"), "https://a.example/^%PATH^%/x^%20y");
+
+        let windows = |name: &str| (name == "OS").then(|| "Windows_NT".to_string());
+        assert!(is_conhost(&windows));
+        let terminal = |name: &str| match name {
+            "OS" => Some("Windows_NT".to_string()),
+            "WT_SESSION" => Some("0f3c".to_string()),
+            _ => None,
+        };
+        assert!(!is_conhost(&terminal));
+        assert!(!is_conhost(&|_: &str| None));
+    }
+}
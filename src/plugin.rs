@@ -0,0 +1,206 @@
+use crate::config::{expand_home, Config};
+use crate::dates;
+use crate::feeds::{FeedStatus, Headline};
+use crate::http::HttpClient;
+use crate::source::{Fetched, HeadlineSource, SourceFuture};
+use crate::urls::FeedEntry;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::process::Command;
+
+/// What a plugin prints on stdout: `{"title": …, "items": […]}`, or just the items
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Output {
+    Feed {
+        title: Option<String>,
+        items: Vec<Item>,
+    },
+    Items(Vec<Item>),
+}
+
+#[derive(Debug, Deserialize)]
+struct Item {
+    title: String,
+    url: Option<String>,
+    /// Source name for this item, instead of the plugin's title
+    source: Option<String>,
+    /// RFC 3339, e.g. "2024-03-09T20:30:00Z"
+    published: Option<DateTime<Utc>>,
+}
+
+/// A `plugin:` line from the feeds file: a command that prints headlines as JSON
+#[derive(Debug, Clone)]
+pub struct PluginSource(pub FeedEntry);
+
+impl HeadlineSource for PluginSource {
+    fn key(&self) -> &str {
+        &self.0.url
+    }
+
+    fn fetch<'a>(
+        &'a self,
+        _client: &'a HttpClient,
+        config: &'a Config,
+    ) -> SourceFuture<'a, Fetched> {
+        Box::pin(async move {
+            let (headlines, status) = match self.run(config).await {
+                Ok((title, headlines)) => {
+                    let status = FeedStatus::Ok {
+                        title,
+                        item_count: headlines.len(),
+                        expired: 0,
+                        newest: headlines.iter().filter_map(|h| h.published).max(),
+                        user_agent: None,
+                    };
+                    (headlines, status)
+                }
+                Err(e) => (Vec::new(), FeedStatus::Error(format!("{:#}", e))),
+            };
+            Fetched {
+                headlines,
+                status,
+                ids: HashMap::new(),
+                outdated: Vec::new(),
+                warnings: Vec::new(),
+            }
+        })
+    }
+
+    fn validate<'a>(
+        &'a self,
+        client: &'a HttpClient,
+        config: &'a Config,
+    ) -> SourceFuture<'a, FeedStatus> {
+        Box::pin(async move { self.fetch(client, config).await.status })
+    }
+}
+
+impl PluginSource {
+    /// Run the command and read its headlines, returning them with the plugin's title
+    async fn run(&self, config: &Config) -> Result<(String, Vec<Headline>)> {
+        let command = self.0.plugin_command().unwrap_or_default();
+        let stdout = run_command(command, config.plugin_timeout).await?;
+        let fallback = command.split_whitespace().next().unwrap_or_default();
+        let (title, mut headlines) =
+            parse_output(&stdout, fallback, config.max_per_feed, config.max_age)?;
+        if let Some(name) = &self.0.name {
+            for headline in &mut headlines {
+                headline.source = name.clone();
+            }
+        }
+        Ok((title, headlines))
+    }
+}
+
+/// Run a command line (split on whitespace, no shell, leading `~/` expanded) and return
+/// its stdout, killing it if it runs past `timeout`
+async fn run_command(command: &str, timeout: Duration) -> Result<Vec<u8>> {
+    let mut words = command.split_whitespace();
+    let program = words.next().context("plugin command is empty")?;
+    let program = expand_home(program)?;
+
+    let child = Command::new(&program)
+        .args(words)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .with_context(|| format!("Couldn't run {}", program.display()))?;
+    let output = tokio::time::timeout(timeout, child.wait_with_output())
+        .await
+        .map_err(|_| anyhow::anyhow!("timed out after {}s", timeout.as_secs()))?
+        .with_context(|| format!("Couldn't read output of {}", program.display()))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let reason = stderr.lines().map(str::trim).find(|l| !l.is_empty());
+        anyhow::bail!(
+            "{} exited with {}: {}",
+            program.display(),
+            output.status,
+            reason.unwrap_or("no error output")
+        );
+    }
+    Ok(output.stdout)
+}
+
+/// Headlines from a plugin's JSON output and its title (`fallback` if it gives none),
+/// dropping untitled and too-old items like a feed fetch does
+fn parse_output(
+    json: &[u8],
+    fallback: &str,
+    max_items: usize,
+    max_age: Duration,
+) -> Result<(String, Vec<Headline>)> {
+    let output: Output =
+        serde_json::from_slice(json).context("Plugin output isn't the expected JSON")?;
+    let (title, items) = match output {
+        Output::Feed { title, items } => (title, items),
+        Output::Items(items) => (None, items),
+    };
+    let title = title.unwrap_or_else(|| fallback.to_string());
+
+    let cutoff = dates::age_cutoff(max_age);
+    let headlines = items
+        .into_iter()
+        .filter(|item| !item.title.trim().is_empty())
+        .filter(|item| item.published.is_none_or(|d| d >= cutoff))
+        .take(max_items)
+        .map(|item| Headline {
+            title: item.title,
+            url: item.url,
+            source: item.source.unwrap_or_else(|| title.clone()),
+            published: item.published,
+            source_url: None,
+        })
+        .collect();
+    Ok((title, headlines))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_output() {
+        let day = Duration::from_secs(86400);
+        let now = Utc::now().to_rfc3339();
+        let json = format!(
+            r#"{{"title": "Builds", "items": [
+                {{"title": "main is green", "url": "https://ci.example/1", "published": "{}"}},
+                {{"title": "Ancient", "published": "2001-01-01T00:00:00Z"}},
+                {{"title": " "}},
+                {{"title": "Deploy done", "source": "Deploys"}}
+            ]}}"#,
+            now
+        );
+        let (title, headlines) = parse_output(json.as_bytes(), "ci", 10, day).unwrap();
+        assert_eq!(title, "Builds");
+        let titles: Vec<_> = headlines.iter().map(|h| h.title.as_str()).collect();
+        assert_eq!(titles, ["main is green", "Deploy done"]);
+        assert_eq!(headlines[0].source, "Builds");
+        assert_eq!(headlines[1].source, "Deploys");
+
+        let (title, headlines) = parse_output(br#"[{"title": "One"}]"#, "ci", 10, day).unwrap();
+        assert_eq!((title.as_str(), headlines.len()), ("ci", 1));
+
+        assert!(parse_output(b"not json", "ci", 10, day).is_err());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_run_command() {
+        let out = run_command("echo []", Duration::from_secs(5)).await.unwrap();
+        assert_eq!(out, b"[]\n");
+        let err = run_command("sleep 5", Duration::from_millis(50)).await.unwrap_err();
+        assert!(err.to_string().contains("timed out"));
+        let err = run_command("false", Duration::from_secs(5)).await.unwrap_err();
+        assert!(err.to_string().contains("exited with"));
+    }
+}
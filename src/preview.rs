@@ -0,0 +1,182 @@
+use crate::cache::{DisabledFeeds, SeenTitles};
+use crate::config::Config;
+use crate::feeds::{FeedStatus, Headline};
+use crate::http::HttpClient;
+use crate::rotation::{self, Excluded};
+use crate::source::HeadlineSource;
+use crate::ticker::{age_label, headline_key, Carried, Ticker};
+use crate::urls::FeedEntry;
+use anyhow::Result;
+use std::collections::{BTreeSet, HashMap};
+
+/// Fetch every source and print the rotation the ticker would build from it, in order,
+/// followed by the headlines left out and why
+pub async fn run(
+    config: &Config,
+    client: &HttpClient,
+    entries: &[FeedEntry],
+    sources: &[Box<dyn HeadlineSource>],
+) -> Result<()> {
+    let disabled = if config.remember_disabled_feeds {
+        DisabledFeeds::load()
+    } else {
+        DisabledFeeds::default()
+    };
+    let mut feed_headlines = HashMap::new();
+    let mut outdated = Vec::new();
+    for source in sources {
+        let fetched = source.fetch(client, config).await;
+        if let FeedStatus::Error(e) = &fetched.status {
+            eprintln!("Error fetching {}: {}", source.key(), e);
+        }
+        feed_headlines.insert(source.key().to_string(), fetched.headlines);
+        outdated.push((source.key().to_string(), fetched.outdated));
+    }
+
+    // The seen titles are only read: previewing mustn't hide headlines from the ticker
+    let seen = SeenTitles::load(config.duplicate_window);
+    let fetched = (feed_headlines, outdated);
+    let carried = Carried::load(config);
+    let (ticker, excluded) = build(config, entries, fetched, &disabled.urls, seen, carried);
+    print!("{}", report(config, &ticker, &excluded));
+    Ok(())
+}
+
+/// Latest headlines by feed, and each feed's newest ones older than max_age
+type Fetches = (HashMap<String, Vec<Headline>>, Vec<(String, Vec<Headline>)>);
+
+/// Build the rotation from fetched headlines the way the app does, noting each headline
+/// left out along the way
+fn build(
+    config: &Config,
+    entries: &[FeedEntry],
+    (mut feed_headlines, outdated): Fetches,
+    disabled: &BTreeSet<String>,
+    mut seen: SeenTitles,
+    carried: Carried,
+) -> (Ticker, Vec<Excluded>) {
+    let mut excluded = Vec::new();
+    if rotation::clock_fallback(&mut feed_headlines, &outdated, disabled).is_empty() {
+        for headline in outdated.into_iter().flat_map(|(_, headlines)| headlines) {
+            let reason = "older than max_age".to_string();
+            excluded.push(Excluded { headline, reason });
+        }
+    }
+    let assembled = rotation::assemble(config, entries, &feed_headlines, disabled, &mut seen);
+    excluded.extend(assembled.excluded);
+
+    let mut ticker = Ticker::with_carried(config, carried);
+    ticker.set_source_tags(assembled.source_tags);
+    ticker.set_headlines(assembled.headlines.clone(), config.sort);
+    for headline in assembled.headlines {
+        if let Some(reason) = ticker.left_out(&headline) {
+            let reason = reason.to_string();
+            excluded.push(Excluded { headline, reason });
+        }
+    }
+    (ticker, excluded)
+}
+
+/// The rotation, numbered, with each story's other headlines beneath its slot, then the
+/// headlines left out
+fn report(config: &Config, ticker: &Ticker, excluded: &[Excluded]) -> String {
+    let age = |h: &Headline| match h.published {
+        Some(published) => age_label(published, &config.dates),
+        None => "-".to_string(),
+    };
+    let headlines = ticker.headlines();
+    let width = headlines
+        .iter()
+        .chain(excluded.iter().map(|e| &e.headline))
+        .map(|h| h.source.chars().count())
+        .max()
+        .unwrap_or(0);
+    let mut out = format!("Rotation ({} headlines)\n", headlines.len());
+    for (i, headline) in headlines.iter().enumerate() {
+        let (source, title) = (&headline.source, &headline.title);
+        out += &format!("{:>4}. {:>4} {:width$}  {}\n", i + 1, age(headline), source, title);
+        let key = headline_key(headline);
+        if let Some(story) = ticker.stories().iter().find(|s| s.slot_key() == key) {
+            let shown = headline_key(story.current());
+            for member in story.members.iter().filter(|m| headline_key(m) != shown) {
+                let (source, title) = (&member.source, &member.title);
+                out += &format!("      {:>4} {:width$}  + {}\n", age(member), source, title);
+            }
+        }
+    }
+    if !excluded.is_empty() {
+        out += &format!("\nLeft out ({})\n", excluded.len());
+        for Excluded { headline, reason } in excluded {
+            let (source, title) = (&headline.source, &headline.title);
+            let line = format!("{:>4} {:width$}  {} ({})", age(headline), source, title, reason);
+            out += &format!("      {}\n", line);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::test_config;
+    use crate::feeds::headline;
+    use chrono::Utc;
+
+    #[test]
+    fn test_build() {
+        let mut config = test_config();
+        config.blocked_domains = vec!["ads.example".to_string()];
+        config.max_total = 2;
+        let fresh = |title: &str, url: &str| Headline {
+            published: Some(Utc::now()),
+            ..headline(title, "Wire", Some(url))
+        };
+        let entries = [FeedEntry::new("wire"), FeedEntry::new("off")];
+        let feed_headlines = HashMap::from([
+            (
+                "wire".to_string(),
+                vec![
+                    fresh("Rates held", "https://wire.example/1"),
+                    fresh("Sponsored", "https://ads.example/2"),
+                    fresh("Storm nears", "https://wire.example/3"),
+                    fresh("League final", "https://wire.example/4"),
+                ],
+            ),
+            ("off".to_string(), vec![fresh("Off feed", "https://off.example/5")]),
+        ]);
+        let disabled = BTreeSet::from(["off".to_string()]);
+        let preview = |feed_headlines| {
+            let stale = fresh("Stale", "https://wire.example/0");
+            let outdated = vec![("wire".to_string(), vec![stale])];
+            let fetched = (feed_headlines, outdated);
+            let seen = SeenTitles::default();
+            build(&config, &entries, fetched, &disabled, seen, Carried::default())
+        };
+        let (ticker, excluded) = preview(feed_headlines);
+        assert_eq!(ticker.headline_count(), 2);
+        let reasons: Vec<(&str, &str)> = excluded
+            .iter()
+            .map(|e| (e.headline.title.as_str(), e.reason.as_str()))
+            .collect();
+        assert_eq!(
+            reasons,
+            [
+                ("Stale", "older than max_age"),
+                ("Sponsored", "blocked domain"),
+                ("Off feed", "feed switched off"),
+                ("League final", "over max_total of 2"),
+            ]
+        );
+
+        let out = report(&config, &ticker, &excluded);
+        assert!(out.starts_with("Rotation (2 headlines)\n"));
+        assert!(out.contains("\nLeft out (4)\n"));
+        assert!(out.contains("Wire  Sponsored (blocked domain)\n"));
+
+        // With nothing recent anywhere, the outdated headlines are shown as the app does
+        let feed_headlines = HashMap::from([("wire".to_string(), Vec::new())]);
+        let (ticker, excluded) = preview(feed_headlines);
+        assert_eq!(ticker.headlines()[0].title, "Stale");
+        assert!(excluded.is_empty());
+    }
+}
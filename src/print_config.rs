@@ -0,0 +1,111 @@
+use crate::config::{CliArgs, Config, FileConfig, Layers, ENV_PREFIX};
+use anyhow::{Context, Result};
+use clap::parser::ValueSource;
+use clap::{ArgMatches, CommandFactory};
+use std::collections::HashSet;
+
+/// Command-line flags that set a config key under another name
+const FLAG_KEYS: &[(&str, &str)] = &[
+    ("hide_source", "show_source"),
+    ("no_status_bar", "status_bar"),
+    ("lower_third", "layout"),
+];
+
+/// Print the effective config as TOML, each setting marked with where it came from:
+/// the command line, a CHYRON_* variable, the config file, or the default
+pub fn run(args: CliArgs) -> Result<()> {
+    let cli = cli_keys(&CliArgs::command().get_matches());
+    let config = Config::load(args)?;
+    let path = config.config_path.clone().context("No config file path")?;
+    let layers = Layers::read(&path)?;
+    for warning in &layers.warnings {
+        eprintln!("Warning: {}", warning);
+    }
+    let effective = config.effective(layers.merged()?);
+    print!("{}", render(&effective, &layers, &cli)?);
+    Ok(())
+}
+
+/// Config keys given on the command line
+fn cli_keys(matches: &ArgMatches) -> HashSet<String> {
+    matches
+        .ids()
+        .map(|id| id.as_str())
+        .filter(|id| matches.value_source(id) == Some(ValueSource::CommandLine))
+        .map(|id| {
+            let key = FLAG_KEYS.iter().find(|(flag, _)| *flag == id).map_or(id, |(_, key)| key);
+            key.to_string()
+        })
+        .collect()
+}
+
+fn render(effective: &FileConfig, layers: &Layers, cli: &HashSet<String>) -> Result<String> {
+    let origin = |key: &str| {
+        if cli.contains(key) {
+            "command line".to_string()
+        } else if layers.env.contains_key(key) {
+            format!("{}{}", ENV_PREFIX, key.to_uppercase())
+        } else if let Some(file) = layers.origins.get(key) {
+            file.display().to_string()
+        } else {
+            "default".to_string()
+        }
+    };
+
+    let toml::Value::Table(table) = toml::Value::try_from(effective)? else {
+        anyhow::bail!("config didn't serialize to a table");
+    };
+    // Plain keys first: anything after a [table] header would belong to it
+    let mut keys = String::new();
+    let mut tables = String::new();
+    for (key, value) in table {
+        let is_table = match &value {
+            toml::Value::Table(_) => true,
+            toml::Value::Array(items) => {
+                !items.is_empty() && items.iter().all(toml::Value::is_table)
+            }
+            _ => false,
+        };
+        let origin = origin(&key);
+        let text = toml::to_string(&toml::Table::from_iter([(key, value)]))?;
+        if is_table {
+            tables.push_str(&format!("\n# {}\n{}", origin, text));
+        } else {
+            keys.push_str(&format!("{}  # {}\n", text.trim_end(), origin));
+        }
+    }
+    Ok(keys + &tables)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::test_config;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_render() {
+        let layers = Layers {
+            file: toml::from_str("speed = 4\nsort = \"random\"\n[theme]\ndelimiter = \"dim\"")
+                .unwrap(),
+            env: toml::from_str("sort = \"by_source\"").unwrap(),
+            origins: ["speed", "sort", "theme"]
+                .into_iter()
+                .map(|key| (key.to_string(), PathBuf::from("config.toml")))
+                .collect(),
+            ..Default::default()
+        };
+        let mut config = test_config();
+        config.speed = 20;
+        let effective = config.effective(layers.merged().unwrap());
+        let cli = HashSet::from(["speed".to_string()]);
+        let text = render(&effective, &layers, &cli).unwrap();
+
+        assert!(text.contains("speed = 20  # command line\n"));
+        assert!(text.contains("sort = \"by_date\"  # CHYRON_SORT\n"));
+        assert!(text.contains("max_total = 100  # default\n"));
+        assert!(text.ends_with("\n# config.toml\n[theme]\ndelimiter = \"dim\"\n"));
+        // The whole output reads back as a config file
+        assert!(toml::from_str::<FileConfig>(&text).is_ok());
+    }
+}
@@ -0,0 +1,101 @@
+use crate::archive::{self, ArchiveRecord};
+use crate::feeds::Headline;
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use std::path::Path;
+use std::time::Instant;
+
+/// Feeds archived headlines into the ticker in the order they were first seen
+pub struct Replay {
+    /// Records sorted by first_seen
+    records: Vec<ArchiveRecord>,
+    /// Index of the next record to release
+    next: usize,
+    /// Wall-clock time the replay started
+    started: Instant,
+    /// first_seen of the earliest record, mapped to `started`
+    origin: DateTime<Utc>,
+    /// How much faster than real time the archive is replayed
+    speed_factor: f64,
+    /// Headlines released so far
+    released: Vec<Headline>,
+}
+
+impl Replay {
+    pub fn open(path: &Path, speed_factor: f64) -> Result<Self> {
+        let mut records = archive::read_records(path)?;
+        if records.is_empty() {
+            anyhow::bail!("Archive contains no headlines: {}", path.display());
+        }
+        records.sort_by_key(|r| r.first_seen);
+        let origin = records[0].first_seen;
+
+        Ok(Self {
+            records,
+            next: 0,
+            started: Instant::now(),
+            origin,
+            speed_factor: speed_factor.max(f64::MIN_POSITIVE),
+            released: Vec::new(),
+        })
+    }
+
+    /// Release every record whose (scaled) time has come
+    /// Returns true if any headlines were released
+    pub fn advance(&mut self) -> bool {
+        let elapsed = self.started.elapsed().as_secs_f64() * self.speed_factor;
+        let elapsed = chrono::Duration::milliseconds((elapsed * 1000.0) as i64);
+        let virtual_now = self.origin + elapsed;
+
+        let before = self.next;
+        while self.next < self.records.len() && self.records[self.next].first_seen <= virtual_now {
+            self.released.push(self.records[self.next].clone().into_headline());
+            self.next += 1;
+        }
+        self.next > before
+    }
+
+    /// The most recently released headlines, newest last, at most `limit` of them
+    pub fn headlines(&self, limit: usize) -> Vec<Headline> {
+        let skip = self.released.len().saturating_sub(limit);
+        self.released[skip..].to_vec()
+    }
+
+    pub fn finished(&self) -> bool {
+        self.next >= self.records.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    fn record(title: &str, first_seen: DateTime<Utc>) -> String {
+        serde_json::to_string(&ArchiveRecord {
+            title: title.to_string(),
+            url: None,
+            source: "Test".to_string(),
+            published: None,
+            first_seen,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_releases_in_chronological_order() {
+        let now = Utc::now();
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "{}", record("Later", now)).unwrap();
+        writeln!(file, "{}", record("Earlier", now - chrono::Duration::hours(1))).unwrap();
+
+        let mut replay = Replay::open(file.path(), 1.0).unwrap();
+        assert!(replay.advance());
+        let released = replay.headlines(10);
+        assert_eq!(released.len(), 1);
+        assert_eq!(released[0].title, "Earlier");
+        assert!(!replay.finished());
+        assert!(!replay.advance());
+    }
+}
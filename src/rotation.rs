@@ -0,0 +1,135 @@
+use crate::cache::SeenTitles;
+use crate::config::Config;
+use crate::dates;
+use crate::feeds::{self, Headline};
+use crate::ticker::headline_key;
+use crate::urls::FeedEntry;
+use chrono::Utc;
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+/// A headline left out of the rotation, and why
+pub struct Excluded {
+    pub headline: Headline,
+    pub reason: String,
+}
+
+/// Headlines for the rotation, gathered from every feed's latest fetch, before the ticker
+/// orders them
+#[derive(Default)]
+pub struct Assembled {
+    /// In feed order, with cleaned titles
+    pub headlines: Vec<Headline>,
+    /// Tags of the feeds each source came from
+    pub source_tags: HashMap<String, Vec<String>>,
+    /// Keys of the headlines from feeds tagged breaking_tag
+    pub priority: HashSet<String>,
+    pub excluded: Vec<Excluded>,
+    /// Whether titles were added to the seen titles, so they're worth saving
+    pub seen_changed: bool,
+}
+
+impl Assembled {
+    fn exclude(&mut self, headline: Headline, reason: &str) {
+        self.excluded.push(Excluded {
+            headline,
+            reason: reason.to_string(),
+        });
+    }
+}
+
+/// Gather the rotation from each feed's latest headlines. Feeds switched off or outside
+/// their scheduled hours sit out, titles are cleaned, and headlines from blocked domains,
+/// already seen under another URL, or past max_total are dropped
+pub fn assemble(
+    config: &Config,
+    feeds: &[FeedEntry],
+    feed_headlines: &HashMap<String, Vec<Headline>>,
+    disabled: &BTreeSet<String>,
+    seen: &mut SeenTitles,
+) -> Assembled {
+    let mut assembled = Assembled::default();
+    let now = config.dates.wall_clock(Utc::now());
+    let fetched = feeds.iter().filter_map(|feed| Some((feed, feed_headlines.get(&feed.url)?)));
+    for (feed, headlines) in fetched {
+        if disabled.contains(&feed.url) {
+            for headline in headlines {
+                assembled.exclude(headline.clone(), "feed switched off");
+            }
+            continue;
+        }
+        let breaking = config.breaking_tag.as_ref().is_some_and(|tag| {
+            feed.tags.iter().any(|t| t.eq_ignore_ascii_case(tag))
+        });
+        for headline in headlines {
+            // Feeds outside their scheduled hours sit out
+            if !dates::scheduled(&config.schedules, &headline.source, &feed.tags, now) {
+                assembled.exclude(headline.clone(), "outside its feed's schedule");
+                continue;
+            }
+            if breaking {
+                assembled.priority.insert(headline_key(headline));
+            }
+            let tags = assembled.source_tags.entry(headline.source.clone()).or_default();
+            for tag in &feed.tags {
+                if !tags.contains(tag) {
+                    tags.push(tag.clone());
+                }
+            }
+            let blocked = headline
+                .url
+                .as_deref()
+                .is_some_and(|url| feeds::is_blocked(url, &config.blocked_domains));
+            if blocked {
+                assembled.exclude(headline.clone(), "blocked domain");
+                continue;
+            }
+            let mut headline = headline.clone();
+            feeds::clean_title(&mut headline, &config.title_rules, &feed.tags);
+            assembled.headlines.push(headline);
+        }
+    }
+
+    // Drop stories already seen under another URL
+    let window = config.duplicate_window;
+    if !window.is_zero() {
+        let mut kept = assembled.headlines.clone();
+        assembled.seen_changed = seen.suppress(&mut kept, window);
+        let kept_keys: HashSet<String> = kept.iter().map(headline_key).collect();
+        let gathered = std::mem::replace(&mut assembled.headlines, kept);
+        for headline in gathered.into_iter().filter(|h| !kept_keys.contains(&headline_key(h))) {
+            assembled.exclude(headline, "same story as a title seen under another URL");
+        }
+    }
+
+    if assembled.headlines.len() > config.max_total {
+        let reason = format!("over max_total of {}", config.max_total);
+        for headline in assembled.headlines.split_off(config.max_total) {
+            assembled.exclude(headline, &reason);
+        }
+    }
+    assembled
+}
+
+/// Every feed too old to show more likely means the clock is wrong (a kiosk without a
+/// real-time clock) than that the news stopped: put each feed's newest outdated headlines
+/// in place of its recent ones. Returns the headlines put in, none if any feed switched on
+/// has recent ones
+pub fn clock_fallback(
+    feed_headlines: &mut HashMap<String, Vec<Headline>>,
+    outdated: &[(String, Vec<Headline>)],
+    disabled: &BTreeSet<String>,
+) -> Vec<Headline> {
+    let nothing_recent = feed_headlines
+        .iter()
+        .filter(|(url, _)| !disabled.contains(*url))
+        .all(|(_, headlines)| headlines.is_empty());
+    if !nothing_recent {
+        return Vec::new();
+    }
+    let mut restored = Vec::new();
+    for (key, headlines) in outdated.iter().filter(|(_, h)| !h.is_empty()) {
+        restored.extend(headlines.iter().cloned());
+        feed_headlines.insert(key.clone(), headlines.clone());
+    }
+    restored
+}
@@ -0,0 +1,173 @@
+use crate::config::{example_config, Config, SortMode};
+use crate::feeds::{self, FeedStatus};
+use crate::http::HttpClient;
+use crate::urls::FeedEntry;
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use std::fs;
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::path::Path;
+
+/// Whether to offer the setup wizard: nothing configured yet, and someone to answer
+pub fn is_first_run(config: &Config) -> bool {
+    let has_config = config.config_path.as_ref().is_some_and(|p| p.exists());
+    let has_feeds = config.feeds_paths.iter().any(|path| path.exists());
+    !has_feeds && !has_config && io::stdin().is_terminal()
+}
+
+/// Interactive first-run setup: asks for feeds (checked live), speed, and sort, then
+/// writes the feeds file and config. Leaves everything alone if the user declines.
+pub async fn run(config: &mut Config, client: &HttpClient) -> Result<()> {
+    let (Some(config_path), Some(feeds_path)) = (config.config_path.clone(), config.feeds_file())
+    else {
+        return Ok(());
+    };
+    println!("Welcome to chyron! No feeds or config were found.");
+    if !confirm(&format!(
+        "Set up {} and {} now? [Y/n] ",
+        feeds_path.display(),
+        config_path.display()
+    ))? {
+        return Ok(());
+    }
+
+    println!();
+    println!("Paste feed URLs (or pages that link to a feed), one per line. Blank to finish.");
+    let mut entries: Vec<FeedEntry> = Vec::new();
+    loop {
+        let input = ask("Feed URL: ")?;
+        if input.is_empty() {
+            if entries.is_empty() {
+                println!("  Add at least one feed to get started.");
+                continue;
+            }
+            break;
+        }
+        if let Some(url) = check_feed(client, config, &input).await? {
+            if entries.iter().any(|e| e.url == url) {
+                println!("  Already added.");
+            } else {
+                entries.push(FeedEntry::new(url));
+            }
+        }
+    }
+
+    println!();
+    let speed = loop {
+        let input = ask("Scroll speed in characters per second [8]: ")?;
+        if input.is_empty() {
+            break 8;
+        }
+        match input.parse::<u32>() {
+            Ok(speed) if speed > 0 => break speed,
+            _ => println!("  Enter a whole number, like 8."),
+        }
+    };
+    let sort = loop {
+        let input = ask("Sort: by_date, by_date_asc, by_source, or random [by_date]: ")?;
+        if input.is_empty() {
+            break SortMode::ByDate;
+        }
+        match SortMode::from_str(&input.replace('_', "-"), true) {
+            Ok(sort) => break sort,
+            Err(_) => println!("  Not a sort mode: {}", input),
+        }
+    };
+
+    write_file(
+        &feeds_path,
+        &entries.iter().map(|e| e.to_line() + "\n").collect::<String>(),
+    )?;
+    write_file(&config_path, &with_choices(example_config(), speed, sort)?)?;
+    config.reload()?;
+
+    println!();
+    println!("Wrote {} and {}.", feeds_path.display(), config_path.display());
+    println!("Press F in the ticker to manage feeds, or E to edit the config.");
+    Ok(())
+}
+
+/// Fetch a feed (or find the one a page links to) and report on it.
+/// Returns the URL to subscribe to, or None if the user skipped it.
+async fn check_feed(client: &HttpClient, config: &Config, input: &str) -> Result<Option<String>> {
+    let url = match feeds::discover_feed(client, input).await {
+        Ok(url) => url,
+        Err(e) => {
+            println!("  ✗ {:#}", e);
+            return Ok(None);
+        }
+    };
+    let (_, status) = feeds::fetch_one(client, &url, config.max_per_feed, config.max_age).await;
+    match status {
+        FeedStatus::Ok { title, item_count, expired, .. } => {
+            let items = feeds::item_summary(item_count, expired, config.max_age);
+            println!("  ✓ {} ({}) {}", title, items, url);
+            Ok(Some(url))
+        }
+        FeedStatus::Error(e) => {
+            println!("  ✗ {}", e);
+            Ok(confirm("  Add it anyway? [y/N] ")?.then_some(url))
+        }
+    }
+}
+
+/// The example config with the chosen speed and sort filled in
+fn with_choices(template: &str, speed: u32, sort: SortMode) -> Result<String> {
+    let sort = serde_json::to_string(&sort)?;
+    Ok(template
+        .lines()
+        .map(|line| {
+            if line.starts_with("speed = ") {
+                format!("speed = {}", speed)
+            } else if line.starts_with("sort = ") {
+                format!("sort = {}", sort)
+            } else {
+                line.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+        + "\n")
+}
+
+fn write_file(path: &Path, content: &str) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    fs::write(path, content).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Print a prompt and read one trimmed line (an error at end of input)
+fn ask(prompt: &str) -> Result<String> {
+    print!("{}", prompt);
+    io::stdout().flush()?;
+    let mut line = String::new();
+    if io::stdin().lock().read_line(&mut line)? == 0 {
+        anyhow::bail!("Setup cancelled");
+    }
+    Ok(line.trim().to_string())
+}
+
+/// Yes/no question; the default is whichever letter is capitalized in the prompt
+fn confirm(prompt: &str) -> Result<bool> {
+    let default_yes = prompt.contains("[Y/n]");
+    let answer = ask(prompt)?.to_lowercase();
+    Ok(match answer.as_str() {
+        "" => default_yes,
+        _ => answer.starts_with('y'),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_with_choices() {
+        let config = with_choices(example_config(), 12, SortMode::BySource).unwrap();
+        assert!(config.lines().any(|l| l == "speed = 12"));
+        assert!(config.lines().any(|l| l == "sort = \"by_source\""));
+        assert!(toml::from_str::<crate::config::FileConfig>(&config).is_ok());
+    }
+}
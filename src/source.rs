@@ -0,0 +1,185 @@
+use crate::backend::Backend;
+use crate::config::Config;
+use crate::feeds::{self, FeedStatus, FeedWarning, Headline};
+use crate::http::HttpClient;
+use crate::plugin::PluginSource;
+use crate::ticker::headline_key;
+use crate::urls::FeedEntry;
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+
+/// Future returned by source methods, boxed so sources can be mixed behind `dyn`
+pub type SourceFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// What one fetch from a source produced
+#[derive(Debug)]
+pub struct Fetched {
+    /// Headlines (none on failure)
+    pub headlines: Vec<Headline>,
+    /// Outcome, folded into the source's health
+    pub status: FeedStatus,
+    /// Upstream ids by headline key, for sources that track read state
+    pub ids: HashMap<String, String>,
+    /// Newest headlines older than max_age, when none were recent enough
+    pub outdated: Vec<Headline>,
+    /// Non-fatal problems with the source, folded into its health
+    pub warnings: Vec<FeedWarning>,
+}
+
+/// Somewhere headlines come from: a feed, an aggregator server, ...
+///
+/// Each source's health and latest headlines are kept under its `key`.
+pub trait HeadlineSource: Send + Sync {
+    /// Stable identifier: the feed URL, or the server URL for a backend
+    fn key(&self) -> &str;
+
+    /// Fetch current headlines within the configured limits
+    fn fetch<'a>(
+        &'a self,
+        client: &'a HttpClient,
+        config: &'a Config,
+    ) -> SourceFuture<'a, Fetched>;
+
+    /// Check the source is reachable and readable, for `--validate`
+    fn validate<'a>(
+        &'a self,
+        client: &'a HttpClient,
+        config: &'a Config,
+    ) -> SourceFuture<'a, FeedStatus>;
+}
+
+/// An RSS/Atom feed from the feeds file
+#[derive(Debug, Clone)]
+pub struct FeedSource(pub FeedEntry);
+
+impl HeadlineSource for FeedSource {
+    fn key(&self) -> &str {
+        &self.0.url
+    }
+
+    fn fetch<'a>(
+        &'a self,
+        client: &'a HttpClient,
+        config: &'a Config,
+    ) -> SourceFuture<'a, Fetched> {
+        Box::pin(async move {
+            let fetched =
+                feeds::fetch_feed(client, &self.0.url, config.max_per_feed, config.max_age).await;
+            let (mut headlines, status, mut outdated, warnings) = match fetched {
+                Ok(fetched) => {
+                    let status = fetched.status();
+                    (fetched.headlines, status, fetched.outdated, fetched.warnings)
+                }
+                Err(e) => {
+                    let status = FeedStatus::Error(format!("{:#}", e));
+                    (Vec::new(), status, Vec::new(), Vec::new())
+                }
+            };
+            if let Some(name) = &self.0.name {
+                for headline in headlines.iter_mut().chain(&mut outdated) {
+                    headline.source = name.clone();
+                }
+            }
+            Fetched {
+                headlines,
+                status,
+                ids: HashMap::new(),
+                outdated,
+                warnings,
+            }
+        })
+    }
+
+    fn validate<'a>(
+        &'a self,
+        client: &'a HttpClient,
+        config: &'a Config,
+    ) -> SourceFuture<'a, FeedStatus> {
+        Box::pin(async move {
+            feeds::validate_feed(client, &self.0.url, config.max_age).await.status
+        })
+    }
+}
+
+impl HeadlineSource for Backend {
+    fn key(&self) -> &str {
+        self.url()
+    }
+
+    fn fetch<'a>(
+        &'a self,
+        client: &'a HttpClient,
+        config: &'a Config,
+    ) -> SourceFuture<'a, Fetched> {
+        Box::pin(async move {
+            let (entries, status) =
+                self.fetch_entries(client, config.max_total, config.max_age).await;
+            let ids = entries
+                .iter()
+                .map(|e| (headline_key(&e.headline), e.id.clone()))
+                .collect();
+            Fetched {
+                headlines: entries.into_iter().map(|e| e.headline).collect(),
+                status,
+                ids,
+                outdated: Vec::new(),
+                warnings: Vec::new(),
+            }
+        })
+    }
+
+    fn validate<'a>(
+        &'a self,
+        client: &'a HttpClient,
+        config: &'a Config,
+    ) -> SourceFuture<'a, FeedStatus> {
+        Box::pin(async move { self.fetch(client, config).await.status })
+    }
+}
+
+/// The configured sources: the backend server alone, or else each feed (or plugin) in
+/// `feeds`
+pub fn configured(config: &Config, feeds: &[FeedEntry]) -> Vec<Box<dyn HeadlineSource>> {
+    match &config.backend {
+        Some(backend) => vec![Box::new(backend.clone())],
+        None => feeds
+            .iter()
+            .map(|entry| -> Box<dyn HeadlineSource> {
+                match entry.plugin_command() {
+                    Some(_) => Box::new(PluginSource(entry.clone())),
+                    None => Box::new(FeedSource(entry.clone())),
+                }
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{test_config, BackendKind, ServerConfig};
+
+    #[test]
+    fn test_configured() {
+        let mut config = test_config();
+        let feeds = vec![
+            FeedEntry::new("https://a.example/rss"),
+            FeedEntry::new("https://b.example/atom"),
+            FeedEntry::new("plugin:my-source"),
+        ];
+        let sources = configured(&config, &feeds);
+        let keys: Vec<&str> = sources.iter().map(|s| s.key()).collect();
+        assert_eq!(keys, ["https://a.example/rss", "https://b.example/atom", "plugin:my-source"]);
+
+        let server = ServerConfig {
+            url: Some("https://rss.example/".to_string()),
+            token: Some("secret".to_string()),
+            ..Default::default()
+        };
+        config.backend = Backend::from_config(BackendKind::Miniflux, &server).unwrap();
+        let sources = configured(&config, &feeds);
+        assert_eq!(sources.len(), 1);
+        assert_eq!(sources[0].key(), "https://rss.example");
+    }
+}
@@ -0,0 +1,180 @@
+use crate::config::get_data_dir;
+use crate::feeds::Headline;
+use crate::ticker::headline_key;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::PathBuf;
+
+/// Headline counters, overall or for one source
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct Counts {
+    /// Distinct headlines received from feeds
+    pub fetched: u64,
+    /// Headlines that scrolled all the way off the left edge
+    pub shown: u64,
+    /// Headlines opened from the ticker
+    pub clicked: u64,
+}
+
+impl Counts {
+    fn add(&mut self, other: &Counts) {
+        self.fetched += other.fetched;
+        self.shown += other.shown;
+        self.clicked += other.clicked;
+    }
+}
+
+/// Headline statistics for a session, or accumulated over all sessions
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Stats {
+    /// When counting started
+    pub since: Option<DateTime<Utc>>,
+    pub total: Counts,
+    pub sources: BTreeMap<String, Counts>,
+    /// Headlines already counted as fetched this session
+    #[serde(skip)]
+    seen: HashSet<String>,
+}
+
+impl Stats {
+    /// Empty stats for a session starting now
+    pub fn new() -> Self {
+        Self {
+            since: Some(Utc::now()),
+            ..Default::default()
+        }
+    }
+
+    /// Load lifetime stats, or empty stats if none have been saved yet
+    pub fn load() -> Result<Self> {
+        let path = Self::path();
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", path.display()))
+    }
+
+    /// Save as lifetime stats
+    pub fn save(&self) -> Result<()> {
+        let path = Self::path();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    fn path() -> PathBuf {
+        get_data_dir().join("stats.json")
+    }
+
+    /// Count headlines from a fetch that haven't been counted this session
+    pub fn record_fetched(&mut self, headlines: &[Headline]) {
+        for headline in headlines {
+            if self.seen.insert(headline_key(headline)) {
+                self.total.fetched += 1;
+                self.source(&headline.source).fetched += 1;
+            }
+        }
+    }
+
+    /// Forget which headlines were counted as fetched, except those in `keys` (ones still
+    /// in a feed, which would otherwise be counted again)
+    pub fn forget_fetched_except(&mut self, keys: &HashSet<String>) {
+        self.seen.retain(|key| keys.contains(key));
+    }
+
+    /// Keys of the headlines counted as fetched this session
+    pub fn fetched_keys(&self) -> &HashSet<String> {
+        &self.seen
+    }
+
+    pub fn record_shown(&mut self, source: &str) {
+        self.total.shown += 1;
+        self.source(source).shown += 1;
+    }
+
+    pub fn record_clicked(&mut self, source: &str) {
+        self.total.clicked += 1;
+        self.source(source).clicked += 1;
+    }
+
+    fn source(&mut self, source: &str) -> &mut Counts {
+        self.sources.entry(source.to_string()).or_default()
+    }
+
+    /// Add another set of stats into this one, keeping the earliest start
+    pub fn merge(&mut self, other: &Stats) {
+        self.since = match (self.since, other.since) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        self.total.add(&other.total);
+        for (source, counts) in &other.sources {
+            self.source(source).add(counts);
+        }
+    }
+
+    /// Human-readable summary, sources ordered by clicks then times shown
+    pub fn summary(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        if let Some(since) = self.since {
+            let since = since.with_timezone(&chrono::Local);
+            lines.push(format!("Since {}", since.format("%Y-%m-%d %H:%M")));
+        }
+        lines.push(format!(
+            "{} fetched, {} shown, {} clicked",
+            self.total.fetched, self.total.shown, self.total.clicked
+        ));
+
+        let mut sources: Vec<_> = self.sources.iter().collect();
+        sources.sort_by(|a, b| {
+            b.1.clicked
+                .cmp(&a.1.clicked)
+                .then(b.1.shown.cmp(&a.1.shown))
+                .then(a.0.cmp(b.0))
+        });
+        if !sources.is_empty() {
+            lines.push(String::new());
+            lines.push(format!("{:>7} {:>7} {:>7}  Source", "clicked", "shown", "fetched"));
+        }
+        for (source, counts) in sources {
+            lines.push(format!(
+                "{:>7} {:>7} {:>7}  {}",
+                counts.clicked, counts.shown, counts.fetched, source
+            ));
+        }
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::feeds::headline;
+
+    #[test]
+    fn test_fetched_counted_once_and_merged() {
+        let mut session = Stats::new();
+        let headlines = vec![headline("One", "A", None), headline("Two", "B", None)];
+        session.record_fetched(&headlines);
+        session.record_fetched(&headlines);
+        session.record_shown("A");
+        session.record_clicked("A");
+        assert_eq!(session.total.fetched, 2);
+
+        let mut lifetime = Stats::default();
+        lifetime.record_clicked("B");
+        lifetime.merge(&session);
+        assert_eq!(lifetime.total.clicked, 2);
+        assert_eq!(lifetime.sources["A"].shown, 1);
+        assert_eq!(lifetime.sources["B"].fetched, 1);
+        assert_eq!(lifetime.since, session.since);
+    }
+}
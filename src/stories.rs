@@ -0,0 +1,146 @@
+use crate::feeds::Headline;
+use std::collections::{BTreeSet, HashSet};
+
+/// Words too common in headlines to tie two of them to the same story
+const COMMON_WORDS: &[&str] = &[
+    "about", "after", "against", "amid", "and", "are", "but", "can", "could", "for", "from",
+    "has", "have", "how", "its", "new", "not", "now", "off", "out", "over", "says", "than",
+    "that", "the", "this", "what", "when", "who", "why", "will", "with", "you", "your",
+];
+
+/// Significant words two titles must share to be about the same story
+const SHARED_WORDS: usize = 2;
+
+/// Share of the shorter title's significant words the shared ones must make up
+const SHARED_SHARE: f64 = 0.4;
+
+/// Headlines about one developing story from more than one source, shown as a single slot
+/// in the rotation that turns to the next of them on each pass
+#[derive(Debug, Clone)]
+pub struct Story {
+    /// In rotation order, the first leading the story
+    pub members: Vec<Headline>,
+    /// Number of sources reporting it
+    pub sources: usize,
+    /// Member the slot shows now
+    turn: usize,
+}
+
+impl Story {
+    fn new(members: Vec<Headline>) -> Self {
+        let sources = members.iter().map(|h| h.source.as_str()).collect::<HashSet<_>>().len();
+        Self {
+            members,
+            sources,
+            turn: 0,
+        }
+    }
+
+    /// The member the slot shows now
+    pub fn current(&self) -> &Headline {
+        &self.members[self.turn]
+    }
+
+    /// The slot as it appears in the rotation: the current member, retitled for the story
+    pub fn slot(&self) -> Headline {
+        let mut slot = self.current().clone();
+        slot.title = format!("Story: {} ({} sources)", slot.title, self.sources);
+        slot
+    }
+
+    /// Key the slot goes by in the rotation: the current member's URL, or for one without a
+    /// URL the slot's retitled title
+    pub fn slot_key(&self) -> String {
+        match &self.current().url {
+            Some(url) => url.clone(),
+            None => self.slot().title,
+        }
+    }
+
+    /// Move on to the next member
+    pub fn turn(&mut self) {
+        self.turn = (self.turn + 1) % self.members.len();
+    }
+
+    /// Carry on from the member `previous` (the same story before a rebuild) was showing
+    pub fn resume(&mut self, previous: &Story) {
+        let url = &previous.current().url;
+        let title = &previous.current().title;
+        if let Some(turn) = self.members.iter().position(|h| &h.url == url && &h.title == title) {
+            self.turn = turn;
+        }
+    }
+}
+
+/// Lowercased words of a title of three letters or more, leaving out common ones
+fn significant_words(title: &str) -> BTreeSet<String> {
+    title
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| word.chars().count() >= 3)
+        .map(str::to_lowercase)
+        .filter(|word| !COMMON_WORDS.contains(&word.as_str()))
+        .collect()
+}
+
+fn same_story(a: &BTreeSet<String>, b: &BTreeSet<String>) -> bool {
+    let shared = a.intersection(b).count();
+    shared >= SHARED_WORDS && shared as f64 >= a.len().min(b.len()) as f64 * SHARED_SHARE
+}
+
+/// Group headlines about the same story: each joins the first group whose leading headline
+/// shares enough significant words with it. Returns the groups with more than one source,
+/// in rotation order
+pub fn group(headlines: &[Headline]) -> Vec<Story> {
+    let words: Vec<BTreeSet<String>> =
+        headlines.iter().map(|h| significant_words(&h.title)).collect();
+    let mut groups: Vec<Vec<usize>> = Vec::new();
+    for (i, title) in words.iter().enumerate() {
+        match groups.iter_mut().find(|group| same_story(&words[group[0]], title)) {
+            Some(group) => group.push(i),
+            None => groups.push(vec![i]),
+        }
+    }
+    groups
+        .into_iter()
+        .map(|group| Story::new(group.into_iter().map(|i| headlines[i].clone()).collect()))
+        .filter(|story| story.sources > 1)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::feeds::headline;
+
+    #[test]
+    fn test_group() {
+        let linked = |title: &str, source: &str| {
+            let url = format!("https://{}.example/{}", source, title.len());
+            headline(title, source, Some(&url))
+        };
+        let headlines = [
+            linked("Earthquake strikes Turkey, hundreds dead", "wire"),
+            linked("Markets rally as inflation cools", "money"),
+            linked("Turkey earthquake: death toll rises", "daily"),
+            linked("Rescuers search rubble after Turkey earthquake", "world"),
+            linked("Inflation cools again in May", "money"),
+            linked("The new season starts", "sport"),
+        ];
+        let mut stories = group(&headlines);
+        // The inflation headlines share a source, so they stay apart
+        assert_eq!(stories.len(), 1);
+        let story = &mut stories[0];
+        assert_eq!((story.members.len(), story.sources), (3, 3));
+        let title = "Story: Earthquake strikes Turkey, hundreds dead (3 sources)";
+        assert_eq!(story.slot().title, title);
+
+        story.turn();
+        assert_eq!(story.current().source, "daily");
+        let mut rebuilt = group(&headlines).remove(0);
+        rebuilt.resume(story);
+        assert_eq!(rebuilt.current().source, "daily");
+        rebuilt.turn();
+        rebuilt.turn();
+        assert_eq!(rebuilt.current().source, "wire");
+    }
+}
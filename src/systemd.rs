@@ -0,0 +1,117 @@
+#[cfg(unix)]
+use std::os::unix::net::{SocketAddr, UnixDatagram};
+use std::time::{Duration, Instant};
+
+/// Readiness, status, and watchdog messages for systemd (`Type=notify`, `WatchdogSec=`),
+/// sent to $NOTIFY_SOCKET. Does nothing when not started by systemd
+pub struct Notifier {
+    #[cfg(unix)]
+    socket: Option<(UnixDatagram, SocketAddr)>,
+    /// How often the watchdog must hear from us, if it's on
+    watchdog: Option<Duration>,
+    last_ping: Instant,
+}
+
+impl Notifier {
+    pub fn from_env() -> Self {
+        let watchdog = std::env::var("WATCHDOG_USEC")
+            .ok()
+            .and_then(|usec| usec.parse().ok())
+            .filter(|_| watchdog_is_ours(std::env::var("WATCHDOG_PID").ok().as_deref()))
+            .map(Duration::from_micros);
+        Self {
+            #[cfg(unix)]
+            socket: std::env::var("NOTIFY_SOCKET").ok().and_then(|path| connect(&path)),
+            watchdog,
+            last_ping: Instant::now(),
+        }
+    }
+
+    /// Startup is done: the ticker is on screen
+    pub fn ready(&mut self) {
+        self.send("READY=1");
+    }
+
+    /// One-line state for `systemctl status`
+    pub fn status(&mut self, status: &str) {
+        self.send(&format!("STATUS={}", status.replace('\n', " ")));
+    }
+
+    pub fn stopping(&mut self) {
+        self.send("STOPPING=1");
+    }
+
+    /// Tell the watchdog we're alive, at twice the rate it requires; call from the main loop
+    pub fn keep_alive(&mut self) {
+        if let Some(interval) = self.watchdog {
+            if self.last_ping.elapsed() >= interval / 2 {
+                self.send("WATCHDOG=1");
+                self.last_ping = Instant::now();
+            }
+        }
+    }
+
+    #[cfg(unix)]
+    fn send(&mut self, message: &str) {
+        if let Some((socket, addr)) = &self.socket {
+            let _ = socket.send_to_addr(message.as_bytes(), addr); // Nothing to do if it fails
+        }
+    }
+
+    #[cfg(not(unix))]
+    fn send(&mut self, _message: &str) {}
+}
+
+/// Whether WATCHDOG_PID (if set) names this process rather than a parent's
+fn watchdog_is_ours(pid: Option<&str>) -> bool {
+    pid.is_none_or(|pid| pid.parse() == Ok(std::process::id()))
+}
+
+/// A socket for sending to $NOTIFY_SOCKET, a path or (starting with '@') an abstract name
+#[cfg(unix)]
+fn connect(path: &str) -> Option<(UnixDatagram, SocketAddr)> {
+    let addr = match path.strip_prefix('@') {
+        #[cfg(target_os = "linux")]
+        Some(name) => {
+            use std::os::linux::net::SocketAddrExt;
+            SocketAddr::from_abstract_name(name).ok()?
+        }
+        #[cfg(not(target_os = "linux"))]
+        Some(_) => return None,
+        None => SocketAddr::from_pathname(path).ok()?,
+    };
+    Some((UnixDatagram::unbound().ok()?, addr))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn test_notify_socket_messages() {
+        let dir = std::env::temp_dir().join(format!("chyron-notify-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("notify.sock");
+        let _ = std::fs::remove_file(&path);
+        let listener = UnixDatagram::bind(&path).unwrap();
+
+        let mut notifier = Notifier {
+            socket: connect(path.to_str().unwrap()),
+            watchdog: Some(Duration::ZERO),
+            last_ping: Instant::now(),
+        };
+        notifier.ready();
+        notifier.keep_alive();
+        let mut buf = [0u8; 64];
+        let n = listener.recv(&mut buf).unwrap();
+        assert_eq!(&buf[..n], b"READY=1");
+        let n = listener.recv(&mut buf).unwrap();
+        assert_eq!(&buf[..n], b"WATCHDOG=1");
+
+        assert!(watchdog_is_ours(None));
+        assert!(watchdog_is_ours(Some(&std::process::id().to_string())));
+        assert!(!watchdog_is_ours(Some("0")));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,511 @@
+use crate::config::expand_home;
+use crate::feeds::Headline;
+use anyhow::{bail, Context, Result};
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::str::FromStr;
+
+/// Contrast (WCAG ratio, 1 to 21) text needs against its background unless min_contrast
+/// says otherwise: the AA level for body text
+pub const DEFAULT_MIN_CONTRAST: f64 = 4.5;
+
+/// `theme` in the config file: a `[theme]` table, or just a color scheme to import
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ThemeSetting {
+    Import(String),
+    Table(ThemeConfig),
+}
+
+impl From<ThemeSetting> for ThemeConfig {
+    fn from(setting: ThemeSetting) -> Self {
+        match setting {
+            ThemeSetting::Import(import) => ThemeConfig {
+                import: Some(import),
+                ..Default::default()
+            },
+            ThemeSetting::Table(config) => config,
+        }
+    }
+}
+
+/// `[theme]` table from the config file: style strings per crawl element, and the ticker
+/// row's padding and edge fade
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ThemeConfig {
+    /// Color scheme to start from, e.g. "base16:~/.config/base16/ocean.yaml"
+    pub import: Option<String>,
+    pub delimiter: Option<String>,
+    pub headline: Option<String>,
+    pub hover: Option<String>,
+    pub badge: Option<String>,
+    pub status: Option<String>,
+    pub band: Option<String>,
+    pub padding: Option<u16>,
+    pub fade: Option<String>,
+    /// Least contrast between text and background before a warning (0 turns it off)
+    pub min_contrast: Option<f64>,
+    /// Brighten or darken text that's short of min_contrast instead of warning
+    pub adjust_contrast: Option<bool>,
+}
+
+/// Resolved styles for the crawl
+#[derive(Debug, Clone)]
+pub struct Theme {
+    /// Style of the delimiter between headlines
+    pub delimiter: Style,
+    /// Headline text (linked headlines are also underlined)
+    pub headline: Style,
+    /// Linked headline under the mouse
+    pub hover: Style,
+    /// NEW badge on fresh headlines
+    pub badge: Style,
+    /// Status bar text
+    pub status: Style,
+    /// The whole ticker row, under the crawl (single and broadcast layouts)
+    pub band: Style,
+    /// Columns kept clear at each end of the ticker row
+    pub padding: u16,
+    /// Characters covering each end of the crawl, outermost first (mirrored on the right)
+    pub fade: Vec<char>,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            delimiter: Style::default(),
+            headline: Style::default(),
+            hover: Style::default().fg(Color::Cyan).underlined(),
+            badge: Style::default().fg(Color::Black).bg(Color::Yellow).bold(),
+            status: Style::default().fg(Color::DarkGray),
+            band: Style::default(),
+            padding: 0,
+            fade: Vec::new(),
+        }
+    }
+}
+
+impl Theme {
+    pub fn from_config(config: &ThemeConfig) -> Result<Self> {
+        let mut theme = match &config.import {
+            Some(import) => {
+                import_scheme(import).with_context(|| format!("Invalid theme import {:?}", import))?
+            }
+            None => Theme::default(),
+        };
+        let fields = [
+            ("delimiter", &config.delimiter, &mut theme.delimiter),
+            ("headline", &config.headline, &mut theme.headline),
+            ("hover", &config.hover, &mut theme.hover),
+            ("badge", &config.badge, &mut theme.badge),
+            ("status", &config.status, &mut theme.status),
+            ("band", &config.band, &mut theme.band),
+        ];
+        for (name, spec, style) in fields {
+            if let Some(spec) = spec {
+                *style = parse_style(spec)
+                    .with_context(|| format!("Invalid theme.{}: {:?}", name, spec))?;
+            }
+        }
+        if let Some(padding) = config.padding {
+            theme.padding = padding;
+        }
+        if let Some(fade) = &config.fade {
+            theme.fade = fade.chars().collect();
+        }
+        Ok(theme)
+    }
+
+    /// Check each element's text against its background (its own, or the band's under the
+    /// crawl) where both colors are known, returning a warning for each short of
+    /// min_contrast; with adjust_contrast the text is instead moved toward black or white
+    /// until it's readable
+    pub fn guard_contrast(&mut self, config: &ThemeConfig) -> Vec<String> {
+        let min = config.min_contrast.unwrap_or(DEFAULT_MIN_CONTRAST);
+        let adjust = config.adjust_contrast.unwrap_or(false);
+        let band = self.band.bg;
+        let elements = [
+            ("delimiter", &mut self.delimiter, band),
+            ("headline", &mut self.headline, band),
+            ("hover", &mut self.hover, band),
+            ("badge", &mut self.badge, None),
+            ("status", &mut self.status, None),
+        ];
+        let mut warnings = Vec::new();
+        for (name, style, under) in elements {
+            let (Some(fg), Some(bg)) = (style.fg, style.bg.or(under)) else {
+                continue;
+            };
+            let Some(ratio) = contrast_ratio(fg, bg) else {
+                continue;
+            };
+            if ratio >= min {
+                continue;
+            }
+            match adjust.then(|| readable_on(fg, bg, min)).flatten() {
+                Some(fg) => style.fg = Some(fg),
+                None => warnings.push(format!(
+                    "theme.{} is hard to read: contrast {:.1}:1 against its background, under \
+                     min_contrast {}:1",
+                    name, ratio, min
+                )),
+            }
+        }
+        warnings
+    }
+
+    /// Styles from a base16 scheme's palette, following base16's styling guidelines
+    fn from_base16(palette: &[Color; 16]) -> Self {
+        Self {
+            delimiter: Style::default().fg(palette[0x3]),
+            headline: Style::default().fg(palette[0x5]),
+            hover: Style::default().fg(palette[0xC]).underlined(),
+            badge: Style::default().fg(palette[0x0]).bg(palette[0xA]).bold(),
+            status: Style::default().fg(palette[0x4]),
+            ..Default::default()
+        }
+    }
+}
+
+/// `[[style]]` entry from the config file: a style for headlines matching every condition
+/// given
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StyleRuleConfig {
+    pub source: Option<String>,
+    pub tag: Option<String>,
+    /// Regular expression searched for in the title
+    pub title: Option<String>,
+    pub style: String,
+}
+
+/// A resolved `[[style]]` rule
+#[derive(Debug, Clone)]
+pub struct StyleRule {
+    source: Option<String>,
+    tag: Option<String>,
+    title: Option<Regex>,
+    pub style: Style,
+}
+
+impl StyleRule {
+    /// Whether the rule applies to a headline from a feed with these tags
+    pub fn matches(&self, headline: &Headline, tags: &[String]) -> bool {
+        self.source.as_ref().is_none_or(|s| s.eq_ignore_ascii_case(&headline.source))
+            && self.tag.as_ref().is_none_or(|t| tags.iter().any(|tag| t.eq_ignore_ascii_case(tag)))
+            && self.title.as_ref().is_none_or(|re| re.is_match(&headline.title))
+    }
+}
+
+/// Resolve `[[style]]` entries, keeping their order
+pub fn style_rules(configs: &[StyleRuleConfig]) -> Result<Vec<StyleRule>> {
+    configs
+        .iter()
+        .enumerate()
+        .map(|(i, config)| {
+            let rule = || -> Result<StyleRule> {
+                if config.source.is_none() && config.tag.is_none() && config.title.is_none() {
+                    bail!("needs source, tag, or title to match on");
+                }
+                let title = config.title.as_deref().map(Regex::new).transpose()?;
+                Ok(StyleRule {
+                    source: config.source.clone(),
+                    tag: config.tag.clone(),
+                    title,
+                    style: parse_style(&config.style)?,
+                })
+            };
+            rule().with_context(|| format!("Invalid [[style]] rule {}", i + 1))
+        })
+        .collect()
+}
+
+/// Combined style of every rule matching a headline, later rules overriding earlier ones
+pub fn rule_style(rules: &[StyleRule], headline: &Headline, tags: &[String]) -> Style {
+    rules
+        .iter()
+        .filter(|rule| rule.matches(headline, tags))
+        .fold(Style::default(), |style, rule| style.patch(rule.style))
+}
+
+/// Load a scheme named like "base16:PATH"
+fn import_scheme(import: &str) -> Result<Theme> {
+    let Some(path) = import.strip_prefix("base16:") else {
+        bail!("expected base16:PATH");
+    };
+    let path = expand_home(path)?;
+    let content = fs::read_to_string(&path)
+        .with_context(|| format!("Couldn't read {}", path.display()))?;
+    Ok(Theme::from_base16(&parse_base16(&content)?))
+}
+
+/// The sixteen colors of a base16 scheme file, in either the classic layout
+/// (`base00: "2b303b"` at the top level) or the newer one (under `palette:`, with `#`)
+fn parse_base16(yaml: &str) -> Result<[Color; 16]> {
+    let mut palette = [None; 16];
+    for line in yaml.lines() {
+        let Some((key, value)) = line.trim().split_once(':') else {
+            continue;
+        };
+        let Some(index) = key.trim().strip_prefix("base") else {
+            continue;
+        };
+        let Ok(index) = usize::from_str_radix(index, 16) else {
+            continue;
+        };
+        if index >= 16 || key.trim().len() != 6 {
+            continue;
+        }
+        // Drop a trailing comment, quotes, and any leading #
+        let value = value.split(" #").next().unwrap_or_default();
+        let hex = value.trim().trim_matches(['"', '\'']).trim_start_matches('#');
+        palette[index] = Some(
+            parse_color(&format!("#{}", hex))
+                .with_context(|| format!("{} isn't a hex color", key.trim()))?,
+        );
+    }
+    let mut colors = [Color::Reset; 16];
+    for (i, color) in palette.into_iter().enumerate() {
+        colors[i] = color.with_context(|| format!("base{:02X} is missing", i))?;
+    }
+    Ok(colors)
+}
+
+/// Parse a style like "dim", "bold yellow", or "#ffaa00 on dark_gray"
+/// Words are modifiers or colors; the first color is the foreground, one after "on" the background
+pub fn parse_style(spec: &str) -> Result<Style> {
+    let mut style = Style::default();
+    let mut words = spec.split_whitespace();
+    while let Some(word) = words.next() {
+        let modifier = match word.to_lowercase().as_str() {
+            "bold" => Some(Modifier::BOLD),
+            "dim" => Some(Modifier::DIM),
+            "italic" => Some(Modifier::ITALIC),
+            "underlined" | "underline" => Some(Modifier::UNDERLINED),
+            "reversed" | "reverse" => Some(Modifier::REVERSED),
+            _ => None,
+        };
+        if let Some(modifier) = modifier {
+            style = style.add_modifier(modifier);
+        } else if word.eq_ignore_ascii_case("on") {
+            let Some(color) = words.next() else {
+                bail!("expected a color after \"on\"");
+            };
+            style = style.bg(parse_color(color)?);
+        } else {
+            style = style.fg(parse_color(word)?);
+        }
+    }
+    Ok(style)
+}
+
+fn parse_color(word: &str) -> Result<Color> {
+    Color::from_str(word).map_err(|_| anyhow::anyhow!("unknown color or modifier {:?}", word))
+}
+
+/// A color's RGB value: named and indexed colors as xterm draws them by default; None for
+/// the terminal's own default
+fn rgb(color: Color) -> Option<[u8; 3]> {
+    const ANSI: [[u8; 3]; 16] = [
+        [0, 0, 0],
+        [205, 0, 0],
+        [0, 205, 0],
+        [205, 205, 0],
+        [0, 0, 238],
+        [205, 0, 205],
+        [0, 205, 205],
+        [229, 229, 229],
+        [127, 127, 127],
+        [255, 0, 0],
+        [0, 255, 0],
+        [255, 255, 0],
+        [92, 92, 255],
+        [255, 0, 255],
+        [0, 255, 255],
+        [255, 255, 255],
+    ];
+    let index = match color {
+        Color::Reset => return None,
+        Color::Rgb(r, g, b) => return Some([r, g, b]),
+        Color::Indexed(i) => i,
+        Color::Black => 0,
+        Color::Red => 1,
+        Color::Green => 2,
+        Color::Yellow => 3,
+        Color::Blue => 4,
+        Color::Magenta => 5,
+        Color::Cyan => 6,
+        Color::Gray => 7,
+        Color::DarkGray => 8,
+        Color::LightRed => 9,
+        Color::LightGreen => 10,
+        Color::LightYellow => 11,
+        Color::LightBlue => 12,
+        Color::LightMagenta => 13,
+        Color::LightCyan => 14,
+        Color::White => 15,
+    };
+    Some(match index {
+        0..=15 => ANSI[index as usize],
+        // 6×6×6 color cube, then a ramp of grays
+        16..=231 => {
+            let level = |n: u8| if n == 0 { 0 } else { 55 + n * 40 };
+            let n = index - 16;
+            [level(n / 36), level(n / 6 % 6), level(n % 6)]
+        }
+        _ => [8 + (index - 232) * 10; 3],
+    })
+}
+
+/// Relative luminance as WCAG defines it
+fn luminance([r, g, b]: [u8; 3]) -> f64 {
+    let linear = |c: u8| {
+        let c = c as f64 / 255.0;
+        if c <= 0.03928 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    0.2126 * linear(r) + 0.7152 * linear(g) + 0.0722 * linear(b)
+}
+
+fn rgb_contrast(a: [u8; 3], b: [u8; 3]) -> f64 {
+    let (a, b) = (luminance(a), luminance(b));
+    (a.max(b) + 0.05) / (a.min(b) + 0.05)
+}
+
+/// WCAG contrast ratio between two colors, from 1 (none) to 21 (black on white); None if
+/// either is the terminal's default
+pub fn contrast_ratio(fg: Color, bg: Color) -> Option<f64> {
+    Some(rgb_contrast(rgb(fg)?, rgb(bg)?))
+}
+
+/// `fg` blended toward black or white (whichever stands out more on `bg`) just far enough
+/// to reach `min` contrast, if it can
+fn readable_on(fg: Color, bg: Color, min: f64) -> Option<Color> {
+    let (fg, bg) = (rgb(fg)?, rgb(bg)?);
+    let target = if rgb_contrast([0; 3], bg) > rgb_contrast([255; 3], bg) { 0.0 } else { 255.0 };
+    (1..=20)
+        .map(|step| {
+            let amount = step as f64 / 20.0;
+            fg.map(|c| (c as f64 + (target - c as f64) * amount).round() as u8)
+        })
+        .find(|&color| rgb_contrast(color, bg) >= min)
+        .map(|[r, g, b]| Color::Rgb(r, g, b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::feeds::headline;
+
+    #[test]
+    fn test_parse_style() {
+        assert_eq!(parse_style("dim").unwrap(), Style::default().add_modifier(Modifier::DIM));
+        assert_eq!(
+            parse_style("bold yellow on #102030").unwrap(),
+            Style::default()
+                .fg(Color::Yellow)
+                .bg(Color::Rgb(0x10, 0x20, 0x30))
+                .add_modifier(Modifier::BOLD)
+        );
+        assert!(parse_style("sparkly").is_err());
+        assert!(parse_style("red on").is_err());
+    }
+
+    #[test]
+    fn test_style_rules() {
+        let configs: Vec<StyleRuleConfig> = toml::from_str::<toml::Table>(
+            r#"
+            [[style]]
+            source = "SecurityFeed"
+            style = "bold red"
+            [[style]]
+            title = "\\bAI\\b"
+            style = "italic"
+            [[style]]
+            tag = "work"
+            style = "blue"
+            "#,
+        )
+        .unwrap()["style"]
+            .clone()
+            .try_into()
+            .unwrap();
+        let rules = style_rules(&configs).unwrap();
+        let style = rule_style(&rules, &headline("New AI exploit", "securityfeed", None), &[]);
+        assert_eq!(
+            style,
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD | Modifier::ITALIC)
+        );
+        let work = ["work".to_string()];
+        let style = rule_style(&rules, &headline("Said it again", "SecurityFeed", None), &work);
+        assert_eq!(style.fg, Some(Color::Blue));
+        assert_eq!(rule_style(&rules, &headline("Plain", "Other", None), &[]), Style::default());
+
+        let bad = StyleRuleConfig {
+            title: Some("(".to_string()),
+            style: "bold".to_string(),
+            ..Default::default()
+        };
+        assert!(style_rules(&[bad]).is_err());
+        assert!(style_rules(&[StyleRuleConfig::default()]).is_err());
+    }
+
+    #[test]
+    fn test_guard_contrast() {
+        assert_eq!(contrast_ratio(Color::Black, Color::White).map(|r| r.round()), Some(21.0));
+        assert_eq!(contrast_ratio(Color::Indexed(16), Color::Indexed(231)), Some(21.0));
+        assert_eq!(contrast_ratio(Color::Reset, Color::White), None);
+
+        let config = ThemeConfig {
+            headline: Some("#303030".to_string()),
+            band: Some("on #1c1c1c".to_string()),
+            status: Some("dark_gray".to_string()),
+            ..Default::default()
+        };
+        let mut theme = Theme::from_config(&config).unwrap();
+        let warnings = theme.guard_contrast(&config);
+        // Status has no background of its own to compare with, and the badge is readable
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].starts_with("theme.headline is hard to read: contrast 1.3:1"));
+
+        let config = ThemeConfig {
+            adjust_contrast: Some(true),
+            ..config
+        };
+        let mut theme = Theme::from_config(&config).unwrap();
+        assert!(theme.guard_contrast(&config).is_empty());
+        let ratio = contrast_ratio(theme.headline.fg.unwrap(), Color::Rgb(0x1c, 0x1c, 0x1c));
+        assert!(ratio.unwrap() >= DEFAULT_MIN_CONTRAST);
+        assert!(theme.headline.fg.unwrap() != Color::White);
+
+        let off = ThemeConfig {
+            min_contrast: Some(0.0),
+            ..Default::default()
+        };
+        let mut theme = Theme::from_config(&config).unwrap();
+        assert!(theme.guard_contrast(&off).is_empty());
+    }
+
+    #[test]
+    fn test_parse_base16() {
+        let classic: String = (0..16)
+            .map(|i| format!("base{:02X}: \"{:02x}0000\"\n", i, i * 16))
+            .collect();
+        let yaml = format!("scheme: \"Test\"\nauthor: \"Someone\"\n{}", classic);
+        let palette = parse_base16(&yaml).unwrap();
+        assert_eq!(palette[0x0], Color::Rgb(0x00, 0, 0));
+        assert_eq!(palette[0xA], Color::Rgb(0xa0, 0, 0));
+
+        let tinted: String = (0..16)
+            .map(|i| format!("  base{:02X}: \"#00{:02x}00\" # shade\n", i, i))
+            .collect();
+        let palette = parse_base16(&format!("system: \"base16\"\npalette:\n{}", tinted)).unwrap();
+        assert_eq!(palette[0xF], Color::Rgb(0, 0x0f, 0));
+
+        assert!(parse_base16("base00: \"2b303b\"").is_err());
+    }
+}
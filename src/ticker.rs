@@ -1,13 +1,108 @@
-use crate::cache::ShownCache;
-use crate::config::{Config, RotationMode, SortMode};
+use crate::cache::{CrawlPosition, FirstSeen, ShownCache};
+use crate::config::{Config, DisplayMode, RotationMode, SortMode, UndatedMode};
+use crate::dates::{self, DateStyle};
 use crate::feeds::Headline;
+use crate::memory::{self, Usage};
+use crate::stories::{self, Story};
+use crate::theme::{rule_style, StyleRule, Theme};
 use chrono::Utc;
+use rand::rngs::StdRng;
 use rand::seq::SliceRandom;
-use std::collections::HashSet;
+use rand::{Rng, SeedableRng};
+use ratatui::style::Style;
+use std::cmp::{Ordering, Reverse};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::mem::size_of;
 use std::time::Duration;
 
+/// Marker shown before headlines that arrived in the latest refresh
+const NEW_BADGE: &str = " NEW ";
+
+/// Clock time shown when relative dates are flipped to absolute ones
+const ABSOLUTE_TIME_FORMAT: &str = "%H:%M";
+
+/// How far ahead of the clock a publication date may be before it's treated as bogus
+const FUTURE_DATE_SLACK: chrono::Duration = chrono::Duration::hours(1);
+
+/// Seconds the BREAKING slate takes to wipe across the row
+const TAKEOVER_WIPE: f64 = 0.6;
+
+/// Seconds a breaking headline holds the row once the slate is up
+const TAKEOVER_HOLD: f64 = 6.0;
+
+/// Breaking slates queued at most: a burst from a priority feed beyond this just joins the
+/// rotation rather than holding the crawl still for minutes
+const MAX_BREAKING: usize = 3;
+
+/// Seconds the crawl takes to glide a headline to the center
+const GLIDE_DURATION: f64 = 0.5;
+
+/// Time constant of speed changes, pauses, and resumes: the crawl gets 95% of the way to
+/// a new speed in three of these (300ms)
+const SPEED_EASING: f64 = 0.1;
+
+/// A breaking headline taking over the ticker row
+pub struct Takeover<'a> {
+    pub headline: &'a Headline,
+    /// How far the slate has wiped across the row, 0 to 1
+    pub wipe: f64,
+    /// Seconds since the takeover began
+    pub elapsed: f64,
+    /// What the slate says: the headline's source and title (just the title in safe mode)
+    pub caption: String,
+}
+
+/// The crawl easing from one offset to another, while paused
+struct Glide {
+    from: f64,
+    /// Where it ends up: may lie outside the text, taking the shorter way round the loop
+    to: f64,
+    elapsed: f64,
+}
+
+/// Position within the crawl, expressed relative to a headline so it survives rebuilds
+struct CrawlAnchor {
+    key: String,
+    index: usize,
+    into: usize,
+}
+
+/// How many skip/mute actions can be undone
+const UNDO_LIMIT: usize = 20;
+
+/// A skip or mute that can be undone
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Exclusion {
+    Skipped { key: String, title: String },
+    Muted(String),
+}
+
+/// What a ticker carries over from earlier sessions
+#[derive(Default)]
+pub struct Carried {
+    /// Keys of headlines already shown
+    pub shown: HashSet<String>,
+    /// Where the last session's crawl was
+    pub position: Option<CrawlPosition>,
+    pub first_seen: FirstSeen,
+}
+
+impl Carried {
+    /// Load what the last session saved to the cache dir
+    pub fn load(config: &Config) -> Self {
+        let cache = ShownCache::load(config.max_age, config.max_shown_entries);
+        Self {
+            shown: cache.shown_keys(),
+            position: CrawlPosition::load(),
+            first_seen: FirstSeen::load(),
+        }
+    }
+}
+
 /// Manages the scrolling ticker state and headline rotation
 pub struct Ticker {
+    /// All headlines from the latest fetch, before sorting and filtering
+    pool: Vec<Headline>,
     /// All headlines currently in rotation
     headlines: Vec<Headline>,
     /// The rendered ticker text (headlines joined with delimiter)
@@ -20,10 +115,28 @@ pub struct Ticker {
     offset: f64,
     /// Characters per second
     speed: u32,
-    /// Delimiter between headlines
-    delimiter: String,
+    /// Speed the crawl is actually moving at, easing toward `speed` (or 0 while paused)
+    current_speed: f64,
+    /// Delimiters cycled between headlines (never empty)
+    delimiters: Vec<String>,
+    /// Crawl styles
+    theme: Theme,
+    /// `[[style]]` rules for individual headlines
+    style_rules: Vec<StyleRule>,
+    /// Tags of the feed each source name comes from, for tag rules
+    source_tags: HashMap<String, Vec<String>>,
+    /// Rule style of each headline in rotation
+    styles: Vec<Style>,
+    /// Draw sub-cell motion cues at the crawl edges
+    smooth: bool,
+    /// Seconds each headline is held in flip display (None: crawl)
+    flip_interval: Option<f64>,
+    /// Seconds the current headline has been held in flip display
+    flip_elapsed: f64,
     /// Whether to show source prefix
     show_source: bool,
+    /// Screen-share safe mode: titles only, without sources or links
+    safe_mode: bool,
     /// Whether ticker is paused (manual toggle via spacebar)
     manual_paused: bool,
     /// Whether ticker is auto-paused (by hover/focus mode)
@@ -38,8 +151,60 @@ pub struct Ticker {
     current_headline_end: usize,
     /// Max age for pruning cache
     max_age: Duration,
+    /// Max entries kept in the persisted shown cache
+    max_shown_entries: usize,
     /// Date format string (strftime or "relative")
     date_format: Option<String>,
+    /// Zone and locale dates are shown in
+    dates: DateStyle,
+    /// Show absolute times where relative ones are configured, and vice versa
+    times_flipped: bool,
+    /// Headline template, replacing the default "[source] date - title"
+    template: Option<String>,
+    /// Icons substituted for {icon}, keyed by source name
+    icons: HashMap<String, String>,
+    /// Keys of headlines added by the latest refresh that haven't completed a pass yet
+    new_keys: HashSet<String>,
+    /// Sort mode applied when building the rotation
+    sort: SortMode,
+    /// Where headlines without a (believable) publication date go
+    undated: UndatedMode,
+    /// When each headline was first fetched, across sessions
+    first_seen: FirstSeen,
+    /// Keys of pool headlines without a (believable) publication date
+    undated_keys: HashSet<String>,
+    /// Shown instead of the empty-rotation message, e.g. while every feed is failing
+    banner: Option<String>,
+    /// The rotation is the clock fallback's outdated headlines, so nothing expires
+    clock_suspect: bool,
+    /// Breaking headlines interrupting the crawl, the first one on screen
+    breaking: VecDeque<Headline>,
+    /// Seconds the current takeover has been on screen
+    takeover_elapsed: f64,
+    /// Glide bringing a headline to the center, if one is under way
+    glide: Option<Glide>,
+    /// Only show headlines that have never been shown or clicked
+    unread_only: bool,
+    /// Fold headlines about the same story from several sources into one slot
+    group_stories: bool,
+    /// Stories folded into the rotation, each taking one slot
+    stories: Vec<Story>,
+    /// Headlines from other sources kept between two from the same one, where possible
+    min_source_gap: usize,
+    /// Sources hidden for this session
+    muted_sources: HashSet<String>,
+    /// Headlines removed from rotation for this session
+    skipped: HashSet<String>,
+    /// Recent skips and mutes, newest last
+    undo: Vec<Exclusion>,
+    /// Seed for the random sort order, persisted so restarts keep the same order
+    seed: u64,
+    /// Saved position to resume at once the first headlines arrive
+    resume: Option<CrawlPosition>,
+    /// Headlines that finished crossing the screen, for stats and read sync
+    shown: Vec<Headline>,
+    /// No headlines have been set yet
+    loading: bool,
 }
 
 /// A segment of the ticker text that maps to a URL
@@ -48,72 +213,232 @@ pub struct TickerSegment {
     pub start: usize,
     pub end: usize,
     pub url: Option<String>,
+    /// Length of the NEW badge at the start of the segment (0 if none)
+    pub badge_len: usize,
+    /// Index of the headline in the rotation
+    pub index: usize,
 }
 
 impl Ticker {
+    /// A ticker carrying on from the last session's saved state
     pub fn new(config: &Config) -> Self {
-        // Load persisted shown cache
-        let mut cache = ShownCache::load();
-        cache.prune(config.max_age);
-        let shown_urls = cache.shown_keys();
+        Self::with_carried(config, Carried::load(config))
+    }
 
-        Self {
+    /// A ticker carrying on from `carried` rather than what's saved
+    pub fn with_carried(config: &Config, carried: Carried) -> Self {
+        // Resume where the last session left off
+        let resume = carried.position;
+        let seed = resume
+            .as_ref()
+            .map(|p| p.seed)
+            .unwrap_or_else(|| rand::rng().random());
+
+        let mut ticker = Self {
+            pool: Vec::new(),
             headlines: Vec::new(),
             ticker_text: String::new(),
             ticker_chars: Vec::new(),
             segments: Vec::new(),
             offset: 0.0,
             speed: config.speed,
-            delimiter: config.delimiter.clone(),
+            current_speed: config.speed as f64,
+            delimiters: if config.delimiters.is_empty() {
+                vec![config.delimiter.clone()]
+            } else {
+                config.delimiters.clone()
+            },
+            theme: config.theme.clone(),
+            style_rules: config.style_rules.clone(),
+            source_tags: HashMap::new(),
+            styles: Vec::new(),
+            smooth: config.smooth,
+            flip_interval: (config.display == DisplayMode::Flip)
+                .then_some(config.flip_interval.as_secs_f64()),
+            flip_elapsed: 0.0,
             show_source: config.show_source,
+            safe_mode: config.safe_mode,
             manual_paused: false,
             auto_paused: false,
             rotation_mode: config.rotation,
-            shown_urls,
+            shown_urls: carried.shown,
             current_headline_idx: 0,
             current_headline_end: 0,
             max_age: config.max_age,
+            max_shown_entries: config.max_shown_entries,
             date_format: config.date_format.clone(),
+            dates: config.dates,
+            times_flipped: false,
+            template: config.template.clone(),
+            icons: config.icons.clone(),
+            new_keys: HashSet::new(),
+            sort: config.sort,
+            undated: config.undated,
+            first_seen: carried.first_seen,
+            undated_keys: HashSet::new(),
+            banner: None,
+            clock_suspect: false,
+            breaking: VecDeque::new(),
+            takeover_elapsed: 0.0,
+            glide: None,
+            unread_only: config.unread_only,
+            group_stories: config.group_stories,
+            stories: Vec::new(),
+            min_source_gap: config.min_source_gap,
+            muted_sources: HashSet::new(),
+            skipped: HashSet::new(),
+            undo: Vec::new(),
+            seed,
+            resume,
+            shown: Vec::new(),
+            loading: true,
+        };
+        ticker.rebuild_ticker_text();
+        ticker
+    }
+
+    /// Save the crawl position and ordering seed for the next session
+    pub fn save_position(&self) {
+        let _ = self.crawl_position().save(); // Ignore errors, position is non-critical
+    }
+
+    /// Where the crawl is, relative to the headline at the left edge
+    pub fn crawl_position(&self) -> CrawlPosition {
+        let anchor = self.anchor();
+        CrawlPosition {
+            seed: self.seed,
+            headline_key: anchor.as_ref().map(|a| a.key.clone()),
+            headline_index: anchor.as_ref().map(|a| a.index).unwrap_or(0),
+            into: anchor.map(|a| a.into as f64).unwrap_or(0.0) + self.offset.fract(),
         }
     }
 
-    /// Save shown headlines to persistent cache
+    /// Take another instance's rotation as is (no sorting or filtering), keeping the
+    /// crawl position
+    pub fn mirror(&mut self, headlines: Vec<Headline>) {
+        let anchor = self.anchor();
+        self.pool = headlines.clone();
+        self.headlines = headlines;
+        self.loading = false;
+        self.rebuild_ticker_text();
+        self.restore_anchor(anchor);
+    }
+
+    /// Jump to another instance's crawl position, moved `shift` headlines along and
+    /// `ahead` characters further, unless already within a character of it
+    pub fn follow(&mut self, position: &CrawlPosition, shift: isize, ahead: f64) {
+        if self.segments.is_empty() {
+            return;
+        }
+        let count = self.segments.len();
+        let index = position
+            .headline_key
+            .as_ref()
+            .and_then(|key| self.headlines.iter().position(|h| headline_key(h) == *key))
+            .unwrap_or(position.headline_index.min(count - 1));
+        let target = (index as isize + shift).rem_euclid(count as isize) as usize;
+        let len = self.ticker_chars.len() as f64;
+        let offset = (self.segments[target].start as f64 + position.into + ahead).rem_euclid(len);
+        let drift = (offset - self.offset).rem_euclid(len);
+        if drift.min(len - drift) > 1.0 {
+            self.offset = offset;
+            self.sync_headline_tracking();
+        }
+    }
+
+    /// Save shown headlines and first-seen times to persistent cache
     pub fn save_shown_cache(&self) {
-        let mut cache = ShownCache::load();
-        cache.prune(self.max_age);
+        let mut cache = ShownCache::load(self.max_age, self.max_shown_entries);
         cache.merge_shown(&self.shown_urls);
+        cache.evict_to(self.max_shown_entries);
         let _ = cache.save(); // Ignore errors, cache is non-critical
+        let _ = self.first_seen.save();
     }
 
     /// Update headlines and rebuild the ticker text
-    pub fn set_headlines(&mut self, mut headlines: Vec<Headline>, sort: SortMode) {
+    /// Returns the number of headlines that weren't in the previous rotation
+    pub fn set_headlines(&mut self, mut headlines: Vec<Headline>, sort: SortMode) -> usize {
+        // Dates far in the future are as good as none
+        let now = Utc::now();
+        for headline in &mut headlines {
+            if headline.published.is_some_and(|date| date > now + FUTURE_DATE_SLACK) {
+                headline.published = None;
+            }
+        }
+        self.undated_keys = headlines
+            .iter()
+            .filter(|h| h.published.is_none())
+            .map(headline_key)
+            .collect();
+
+        // Badge additions, but not the initial load (everything would be "new")
+        let previous: HashSet<String> = self.pool.iter().map(headline_key).collect();
+        let current: HashSet<String> = headlines.iter().map(headline_key).collect();
+        self.first_seen.record(&current, self.max_age);
+        self.new_keys.retain(|k| current.contains(k));
+        let mut added = 0;
+        if !previous.is_empty() {
+            for key in current.difference(&previous) {
+                self.new_keys.insert(key.clone());
+                added += 1;
+            }
+        }
+
+        self.pool = headlines;
+        self.sort = sort;
+        self.loading = false;
+        self.apply_rotation();
+
+        // First headlines of the session: jump to where the last one stopped
+        if !self.headlines.is_empty() {
+            if let Some(position) = self.resume.take() {
+                let anchor = position.headline_key.map(|key| CrawlAnchor {
+                    key,
+                    index: position.headline_index,
+                    into: position.into as usize,
+                });
+                self.offset = position.into.fract();
+                self.restore_anchor(anchor);
+            }
+        }
+
+        added
+    }
+
+    /// Rebuild the rotation from the fetched pool using the current sort and filters
+    fn apply_rotation(&mut self) {
+        let mut headlines = self.pool.clone();
+        headlines.retain(|h| !self.is_excluded(h));
+        self.place_undated(&mut headlines);
+
         // Sort headlines according to mode
-        match sort {
+        match self.sort {
             SortMode::Random => {
-                let mut rng = rand::rng();
+                let mut rng = StdRng::seed_from_u64(self.seed);
                 headlines.shuffle(&mut rng);
             }
             SortMode::BySource => {
                 headlines.sort_by(|a, b| a.source.cmp(&b.source));
             }
             SortMode::ByDate => {
-                headlines.sort_by(|a, b| {
-                    let a_date = a.published.unwrap_or(Utc::now());
-                    let b_date = b.published.unwrap_or(Utc::now());
-                    b_date.cmp(&a_date) // newest first
-                });
+                headlines.sort_by(|a, b| compare_dates(a, b, true, self.undated));
             }
             SortMode::ByDateAsc => {
-                headlines.sort_by(|a, b| {
-                    let a_date = a.published.unwrap_or(Utc::now());
-                    let b_date = b.published.unwrap_or(Utc::now());
-                    a_date.cmp(&b_date) // oldest first
+                headlines.sort_by(|a, b| compare_dates(a, b, false, self.undated));
+            }
+            SortMode::ByFirstSeen => {
+                // Latest arrivals first, by publication date within a fetch
+                headlines.sort_by_cached_key(|h| {
+                    Reverse((self.first_seen.get(&headline_key(h)), h.published))
                 });
             }
         }
 
-        // For fair rotation, prioritize unshown headlines
-        if self.rotation_mode == RotationMode::Fair {
+        if self.unread_only {
+            // Only headlines that have never completed a pass or been clicked
+            headlines.retain(|h| !self.is_headline_shown(h));
+        } else if self.rotation_mode == RotationMode::Fair {
+            // For fair rotation, prioritize unshown headlines
             // Partition into unshown and shown
             let (unshown, shown): (Vec<_>, Vec<_>) = headlines
                 .into_iter()
@@ -134,6 +459,10 @@ impl Ticker {
                 headlines.extend(shown);
             }
         }
+        let mut headlines = self.fold_stories(headlines);
+        if self.sort != SortMode::BySource {
+            headlines = space_sources(headlines, self.min_source_gap);
+        }
 
         self.headlines = headlines;
         self.rebuild_ticker_text();
@@ -153,14 +482,370 @@ impl Ticker {
         };
     }
 
-    /// Check if a headline has been shown (by URL or title if no URL)
-    fn is_headline_shown(&self, headline: &Headline) -> bool {
-        if let Some(url) = &headline.url {
-            self.shown_urls.contains(url)
+    /// Put each story (headlines about the same thing from several sources) in one slot
+    /// where its first headline was, carrying on each one's turn from the last rebuild
+    fn fold_stories(&mut self, headlines: Vec<Headline>) -> Vec<Headline> {
+        let previous = std::mem::take(&mut self.stories);
+        if !self.group_stories {
+            return headlines;
+        }
+        self.stories = stories::group(&headlines);
+        for story in &mut self.stories {
+            let lead = headline_key(&story.members[0]);
+            if let Some(before) = previous.iter().find(|s| headline_key(&s.members[0]) == lead) {
+                story.resume(before);
+            }
+        }
+        let slots: HashMap<String, Headline> = self
+            .stories
+            .iter()
+            .map(|story| (headline_key(&story.members[0]), story.slot()))
+            .collect();
+        let folded: HashSet<String> = self
+            .stories
+            .iter()
+            .flat_map(|story| story.members[1..].iter().map(headline_key))
+            .collect();
+        headlines
+            .into_iter()
+            .filter(|h| !folded.contains(&headline_key(h)))
+            .map(|h| slots.get(&headline_key(&h)).cloned().unwrap_or(h))
+            .collect()
+    }
+
+    /// Stories folded into the rotation
+    pub fn stories(&self) -> &[Story] {
+        &self.stories
+    }
+
+    /// Re-sort the current rotation without refetching
+    pub fn set_sort(&mut self, sort: SortMode) {
+        self.sort = sort;
+        self.apply_rotation();
+    }
+
+    pub fn sort(&self) -> SortMode {
+        self.sort
+    }
+
+    /// Feed tags by source name, for `[[style]]` rules matching on tag; applies from the
+    /// next rebuild
+    pub fn set_source_tags(&mut self, source_tags: HashMap<String, Vec<String>>) {
+        self.source_tags = source_tags;
+    }
+
+    /// Style from the `[[style]]` rules for the headline at `index` in rotation
+    pub fn headline_style(&self, index: usize) -> Style {
+        self.styles.get(index).copied().unwrap_or_default()
+    }
+
+    /// Rule style of the leading headline
+    pub fn leading_style(&self) -> Style {
+        self.anchor().map(|a| self.headline_style(a.index)).unwrap_or_default()
+    }
+
+    /// Shuffle the random order again with a fresh seed, which the next session keeps
+    pub fn reshuffle(&mut self) {
+        self.seed = rand::rng().random();
+        self.apply_rotation();
+    }
+
+    /// Take up reloaded display settings (everything but speed and safe mode, which keep
+    /// what the keys set), rebuilding the rotation
+    /// and keeping the crawl on the same headline
+    pub fn reconfigure(&mut self, config: &Config) {
+        self.delimiters = if config.delimiters.is_empty() {
+            vec![config.delimiter.clone()]
         } else {
-            // For headlines without URLs, use title as key
-            self.shown_urls.contains(&headline.title)
+            config.delimiters.clone()
+        };
+        self.theme = config.theme.clone();
+        self.style_rules = config.style_rules.clone();
+        self.smooth = config.smooth;
+        let flip_interval =
+            (config.display == DisplayMode::Flip).then_some(config.flip_interval.as_secs_f64());
+        if flip_interval.is_some() != self.flip_interval.is_some() {
+            self.flip_elapsed = 0.0;
         }
+        self.flip_interval = flip_interval;
+        self.show_source = config.show_source;
+        self.rotation_mode = config.rotation;
+        self.max_age = config.max_age;
+        self.date_format = config.date_format.clone();
+        self.dates = config.dates;
+        self.template = config.template.clone();
+        self.icons = config.icons.clone();
+        self.unread_only = config.unread_only;
+        self.group_stories = config.group_stories;
+        self.min_source_gap = config.min_source_gap;
+        self.sort = config.sort;
+        self.undated = config.undated;
+
+        let anchor = self.anchor();
+        self.apply_rotation();
+        self.restore_anchor(anchor);
+    }
+
+    /// Toggle unread-only mode, rebuilding the rotation from the fetched pool
+    /// Returns the new state
+    pub fn toggle_unread_only(&mut self) -> bool {
+        self.unread_only = !self.unread_only;
+        self.apply_rotation();
+        self.unread_only
+    }
+
+    /// Flip timestamps between relative ("2h ago") and absolute ("14:32"), keeping
+    /// the crawl position. Returns whether they're now relative, or None if no
+    /// timestamps are shown (date_format is none and the template has no {age})
+    pub fn toggle_relative_times(&mut self) -> Option<bool> {
+        let shows_age = self.template.as_ref().is_some_and(|t| t.contains("{age}"));
+        if self.date_format.is_none() && !shows_age {
+            return None;
+        }
+        self.times_flipped = !self.times_flipped;
+        let anchor = self.anchor();
+        self.rebuild_ticker_text();
+        self.restore_anchor(anchor);
+        Some(self.relative_times())
+    }
+
+    /// Switch screen-share safe mode, keeping the crawl position. Returns the new state
+    pub fn toggle_safe_mode(&mut self) -> bool {
+        self.safe_mode = !self.safe_mode;
+        let anchor = self.anchor();
+        self.rebuild_ticker_text();
+        self.restore_anchor(anchor);
+        self.safe_mode
+    }
+
+    pub fn safe_mode(&self) -> bool {
+        self.safe_mode
+    }
+
+    pub fn unread_only(&self) -> bool {
+        self.unread_only
+    }
+
+    /// "Source: title" for a headline, or just the title in safe mode
+    pub fn caption(&self, headline: &Headline) -> String {
+        if self.safe_mode {
+            headline.title.clone()
+        } else {
+            format!("{}: {}", headline.source, headline.title)
+        }
+    }
+
+    /// Whether timestamps currently read as ages
+    fn relative_times(&self) -> bool {
+        let configured = self.date_format.as_deref().is_none_or(|f| f == "relative");
+        configured != self.times_flipped
+    }
+
+    /// Hide all headlines from a source for the rest of the session
+    pub fn mute_source(&mut self, source: &str) {
+        if self.muted_sources.insert(source.to_string()) {
+            self.push_undo(Exclusion::Muted(source.to_string()));
+        }
+        self.drop_excluded();
+    }
+
+    /// Remove a single headline from the rotation for the rest of the session
+    pub fn skip_headline(&mut self, headline: &Headline) {
+        let key = headline_key(headline);
+        if self.skipped.insert(key.clone()) {
+            self.push_undo(Exclusion::Skipped {
+                key,
+                title: headline.title.clone(),
+            });
+        }
+        self.drop_excluded();
+    }
+
+    fn push_undo(&mut self, exclusion: Exclusion) {
+        if self.undo.len() == UNDO_LIMIT {
+            self.undo.remove(0);
+        }
+        self.undo.push(exclusion);
+    }
+
+    /// Reverse the most recent skip or mute, returning it
+    pub fn undo_exclusion(&mut self) -> Option<Exclusion> {
+        let exclusion = self.undo.pop()?;
+        match &exclusion {
+            Exclusion::Skipped { key, .. } => self.skipped.remove(key),
+            Exclusion::Muted(source) => self.muted_sources.remove(source),
+        };
+        let anchor = self.anchor();
+        self.apply_rotation();
+        self.restore_anchor(anchor);
+        Some(exclusion)
+    }
+
+    /// Why a headline handed to set_headlines isn't in the rotation; None if it is, on its
+    /// own or as a member of a story
+    pub fn left_out(&self, headline: &Headline) -> Option<&'static str> {
+        let key = headline_key(headline);
+        let in_story = |s: &Story| s.members.iter().any(|m| headline_key(m) == key);
+        let in_rotation = self.headlines.iter().any(|h| headline_key(h) == key);
+        if in_rotation || self.stories.iter().any(in_story) {
+            return None;
+        }
+        Some(if self.is_excluded(headline) {
+            "source muted or headline skipped"
+        } else if headline.published.is_none() && self.undated == UndatedMode::Exclude {
+            "undated"
+        } else if headline.published.is_none() && self.undated == UndatedMode::FirstSeen {
+            "undated, first seen longer than max_age ago"
+        } else if self.unread_only && self.is_headline_shown(headline) {
+            "already shown (unread_only)"
+        } else {
+            "not in rotation"
+        })
+    }
+
+    /// Whether a headline is muted or skipped
+    fn is_excluded(&self, headline: &Headline) -> bool {
+        self.muted_sources.contains(&headline.source)
+            || self.skipped.contains(&headline_key(headline))
+    }
+
+    /// Remove muted/skipped headlines from the live rotation without reordering it
+    fn drop_excluded(&mut self) {
+        let anchor = self.anchor();
+        let mut headlines = std::mem::take(&mut self.headlines);
+        headlines.retain(|h| !self.is_excluded(h));
+        self.headlines = headlines;
+        self.rebuild_ticker_text();
+        self.restore_anchor(anchor);
+    }
+
+    /// Date or drop the undated headlines of a rotation according to the undated mode
+    fn place_undated(&self, headlines: &mut Vec<Headline>) {
+        let cutoff = dates::age_cutoff(self.max_age);
+        headlines.retain_mut(|h| {
+            if h.published.is_some() {
+                return true;
+            }
+            match self.undated {
+                UndatedMode::Exclude => false,
+                UndatedMode::FirstSeen => {
+                    h.published = self.first_seen.get(&headline_key(h));
+                    h.published.is_none_or(|seen| seen >= cutoff)
+                }
+                UndatedMode::Top | UndatedMode::Bottom => true,
+            }
+        });
+    }
+
+    /// Set or clear the message shown when the rotation is empty
+    pub fn set_banner(&mut self, banner: Option<String>) {
+        if banner != self.banner {
+            self.banner = banner;
+            self.rebuild_ticker_text();
+        }
+    }
+
+    /// Note whether the rotation came from the clock fallback, whose headlines are all older
+    /// than max_age and mustn't be expired
+    pub fn set_clock_suspect(&mut self, clock_suspect: bool) {
+        self.clock_suspect = clock_suspect;
+    }
+
+    /// Mark a headline as read by its key (URL, or title if no URL)
+    pub fn mark_read(&mut self, key: &str) {
+        self.shown_urls.insert(key.to_string());
+    }
+
+    /// Remove headlines published before the max_age cutoff from the live rotation
+    /// Returns the number of headlines removed
+    pub fn expire_headlines(&mut self, max_age: Duration) -> usize {
+        if self.clock_suspect {
+            return 0;
+        }
+        let cutoff = dates::age_cutoff(max_age);
+
+        let is_fresh = |h: &Headline| h.published.map(|d| d >= cutoff).unwrap_or(true);
+        self.pool.retain(is_fresh);
+
+        let before = self.headlines.len();
+        let anchor = self.anchor();
+        self.headlines.retain(is_fresh);
+        let removed = before - self.headlines.len();
+
+        if removed > 0 {
+            self.rebuild_ticker_text();
+            self.restore_anchor(anchor);
+        }
+        removed
+    }
+
+    /// Capture the headline currently at the left edge and how far into it we've scrolled
+    fn anchor(&self) -> Option<CrawlAnchor> {
+        let offset = self.offset as usize;
+        self.segments
+            .iter()
+            .position(|s| offset < s.end)
+            .map(|index| CrawlAnchor {
+                key: headline_key(&self.headlines[index]),
+                index,
+                into: offset.saturating_sub(self.segments[index].start),
+            })
+    }
+
+    /// Move the offset back to an anchored headline after the text was rebuilt
+    /// Falls back to the headline now at the same index if the anchored one is gone
+    fn restore_anchor(&mut self, anchor: Option<CrawlAnchor>) {
+        // A glide's offsets are into the old text
+        self.glide = None;
+        let len = self.ticker_chars.len();
+        let Some(anchor) = anchor else {
+            if self.offset as usize >= len {
+                self.offset = 0.0;
+            }
+            self.sync_headline_tracking();
+            return;
+        };
+
+        let found = self
+            .headlines
+            .iter()
+            .position(|h| headline_key(h) == anchor.key);
+        self.offset = match found {
+            Some(idx) => {
+                let seg = &self.segments[idx];
+                let into = anchor.into.min(seg.end - seg.start);
+                (seg.start + into) as f64 + self.offset.fract()
+            }
+            None => self
+                .segments
+                .get(anchor.index.min(self.segments.len().saturating_sub(1)))
+                .map(|s| s.start as f64)
+                .unwrap_or(0.0),
+        };
+        if self.offset >= len as f64 {
+            self.offset = 0.0;
+        }
+        self.sync_headline_tracking();
+    }
+
+    /// Point fair-rotation tracking at the headline under the current offset
+    fn sync_headline_tracking(&mut self) {
+        let offset = self.offset as usize;
+        self.current_headline_idx = self
+            .segments
+            .iter()
+            .position(|s| offset < s.end)
+            .unwrap_or(self.segments.len());
+        self.current_headline_end = self
+            .segments
+            .get(self.current_headline_idx)
+            .map(|s| s.end)
+            .unwrap_or(0);
+    }
+
+    /// Check if a headline has been shown (by URL or title if no URL)
+    fn is_headline_shown(&self, headline: &Headline) -> bool {
+        self.shown_urls.contains(&headline_key(headline))
     }
 
     /// Format a date according to the configured format
@@ -175,33 +860,68 @@ impl Ticker {
             None => return String::new(),
         };
 
-        if format == "relative" {
-            let now = Utc::now();
-            let duration = now.signed_duration_since(date);
-
-            let relative = if duration.num_days() > 0 {
-                format!("{}d ago", duration.num_days())
-            } else if duration.num_hours() > 0 {
-                format!("{}h ago", duration.num_hours())
-            } else if duration.num_minutes() > 0 {
-                format!("{}m ago", duration.num_minutes())
+        if self.relative_times() {
+            let age = age_label(date, &self.dates);
+            if age == "now" {
+                "now ".to_string()
             } else {
-                "now".to_string()
-            };
-            format!("{} ", relative)
+                format!("{} ago ", age)
+            }
         } else {
-            // Use strftime format, convert to local time
-            let local = date.with_timezone(&chrono::Local);
-            format!("{} ", local.format(format))
+            // strftime format, in the configured zone and locale
+            let format = if format == "relative" { ABSOLUTE_TIME_FORMAT } else { format };
+            format!("{} ", self.dates.format(date, format))
         }
     }
 
+    /// Delimiter following the headline at idx
+    fn delimiter_after(&self, idx: usize) -> &str {
+        &self.delimiters[idx % self.delimiters.len()]
+    }
+
+    /// Zone and locale for rendered timestamps
+    pub fn dates(&self) -> &DateStyle {
+        &self.dates
+    }
+
+    /// Crawl styles
+    pub fn theme(&self) -> &Theme {
+        &self.theme
+    }
+
+    pub fn smooth(&self) -> bool {
+        self.smooth
+    }
+
+    /// Whether headlines are shown one at a time instead of crawling
+    pub fn is_flip(&self) -> bool {
+        self.flip_interval.is_some()
+    }
+
     /// Rebuild the ticker text from current headlines
     fn rebuild_ticker_text(&mut self) {
         self.segments.clear();
+        self.styles = self
+            .headlines
+            .iter()
+            .map(|h| {
+                let tags = self.source_tags.get(&h.source).map_or(&[][..], Vec::as_slice);
+                rule_style(&self.style_rules, h, tags)
+            })
+            .collect();
 
         if self.headlines.is_empty() {
-            self.ticker_text = "No headlines available. Check your feed configuration.".to_string();
+            let message = if let Some(banner) = &self.banner {
+                banner.as_str()
+            } else if self.loading {
+                "Loading headlines..."
+            } else if self.unread_only && !self.pool.is_empty() {
+                "All caught up. No unread headlines."
+            } else {
+                "No headlines available. Check your feed configuration."
+            };
+            self.ticker_text = format!("{}{}", message, self.delimiters[0]);
+            self.ticker_chars = self.ticker_text.chars().collect();
             return;
         }
 
@@ -210,8 +930,9 @@ impl Ticker {
 
         for (idx, headline) in self.headlines.iter().enumerate() {
             if idx > 0 {
-                text.push_str(&self.delimiter);
-                pos += self.delimiter.chars().count();
+                let delimiter = self.delimiter_after(idx - 1);
+                text.push_str(delimiter);
+                pos += delimiter.chars().count();
             }
 
             let segment_start = pos;
@@ -225,7 +946,35 @@ impl Ticker {
             let date_part = self.format_date(headline.published);
             let separator = if !date_part.is_empty() { "- " } else { "" };
 
-            let display_text = format!("{}{}{}{}", source_prefix, date_part, separator, headline.title);
+            let (badge, badge_len) = if self.new_keys.contains(&headline_key(headline)) {
+                (format!("{} ", NEW_BADGE), NEW_BADGE.chars().count())
+            } else {
+                (String::new(), 0)
+            };
+
+            let display_text = match &self.template {
+                _ if self.safe_mode => format!("{}{}", badge, headline.title),
+                Some(template) => {
+                    let icon = self.icons.get(&headline.source).map(String::as_str);
+                    format!(
+                        "{}{}",
+                        badge,
+                        render_template(
+                            template,
+                            headline,
+                            idx + 1,
+                            icon.unwrap_or(""),
+                            date_part.trim(),
+                            &self.dates,
+                            !self.relative_times(),
+                        )
+                    )
+                }
+                None => format!(
+                    "{}{}{}{}{}",
+                    badge, source_prefix, date_part, separator, headline.title
+                ),
+            };
 
             text.push_str(&display_text);
             pos += display_text.chars().count();
@@ -233,12 +982,14 @@ impl Ticker {
             self.segments.push(TickerSegment {
                 start: segment_start,
                 end: pos,
-                url: headline.url.clone(),
+                url: headline.url.clone().filter(|_| !self.safe_mode),
+                badge_len,
+                index: idx,
             });
         }
 
         // Add trailing delimiter for seamless looping
-        text.push_str(&self.delimiter);
+        text.push_str(self.delimiter_after(self.headlines.len() - 1));
 
         self.ticker_chars = text.chars().collect();
         self.ticker_text = text;
@@ -246,54 +997,195 @@ impl Ticker {
 
     /// Advance the ticker by the given time delta
     pub fn tick(&mut self, delta_secs: f64) {
-        if self.manual_paused || self.auto_paused || self.ticker_chars.is_empty() {
+        if let Some(glide) = &mut self.glide {
+            glide.elapsed += delta_secs;
+            let t = (glide.elapsed / GLIDE_DURATION).min(1.0);
+            let len = self.ticker_chars.len().max(1) as f64;
+            self.offset = (glide.from + (glide.to - glide.from) * ease_in_out(t)).rem_euclid(len);
+            if t >= 1.0 {
+                self.glide = None;
+                self.sync_headline_tracking();
+            }
+            self.current_speed = 0.0;
+            return;
+        }
+        let paused = self.manual_paused || self.auto_paused;
+        // A takeover holds the crawl still until it's over
+        if !self.breaking.is_empty() {
+            if !paused {
+                self.takeover_elapsed += delta_secs;
+                if self.takeover_elapsed >= TAKEOVER_WIPE + TAKEOVER_HOLD {
+                    self.end_takeover();
+                }
+            }
+            self.current_speed = 0.0;
             return;
         }
 
+        // Ease toward the wanted speed rather than jumping to it
+        let target = if paused { 0.0 } else { self.speed as f64 };
+        let eased = target + (self.current_speed - target) * (-delta_secs / SPEED_EASING).exp();
+        self.current_speed = if (eased - target).abs() < 0.05 { target } else { eased };
+        if self.ticker_chars.is_empty() {
+            return;
+        }
+
+        let chars = match self.flip_interval {
+            // Flip display jumps between headlines, so it stops and starts at once
+            Some(_) if paused => return,
+            Some(interval) => {
+                self.flip_elapsed += delta_secs;
+                if self.flip_elapsed < interval {
+                    return;
+                }
+                self.flip_elapsed = 0.0;
+                self.distance_to_next_headline()
+            }
+            None => delta_secs * self.current_speed,
+        };
+        if chars > 0.0 {
+            self.advance(chars);
+        }
+    }
+
+    /// Characters from the offset to the start of the headline after the leading one
+    fn distance_to_next_headline(&self) -> f64 {
+        let len = self.ticker_chars.len() as f64;
+        let Some(anchor) = self.anchor() else {
+            return len;
+        };
+        let next = self.segments[(anchor.index + 1) % self.segments.len()].start as f64;
+        let distance = (next - self.offset).rem_euclid(len);
+        if distance == 0.0 { len } else { distance }
+    }
+
+    /// Move the crawl forward by a number of characters
+    fn advance(&mut self, chars: f64) {
         let old_offset = self.offset as usize;
         let len = self.ticker_chars.len() as f64;
-        self.offset += delta_secs * self.speed as f64;
+        self.offset += chars;
 
         // Wrap around
         if self.offset >= len {
             self.offset -= len;
         }
 
-        // Track shown headlines for fair rotation
-        if self.rotation_mode == RotationMode::Fair && !self.headlines.is_empty() {
+        // Track headlines passing the left edge (fair rotation, NEW badges)
+        if !self.headlines.is_empty() {
             let new_offset = self.offset as usize;
+            let badges_before = self.new_keys.len();
 
             // Check if we've scrolled past the end of the current headline
             // A headline is "shown" once its end position has scrolled off the left edge
+            let mut turned = false;
             if new_offset > old_offset {
                 // Normal forward scrolling
                 if old_offset < self.current_headline_end && new_offset >= self.current_headline_end {
-                    self.mark_current_headline_shown();
+                    turned = self.mark_current_headline_shown();
                     self.advance_to_next_headline();
                 }
             } else if new_offset < old_offset {
                 // Wrapped around - mark current and reset
-                self.mark_current_headline_shown();
+                turned = self.mark_current_headline_shown();
                 self.current_headline_idx = 0;
                 self.current_headline_end = if !self.segments.is_empty() {
                     self.segments[0].end
                 } else {
                     0
                 };
+
+                // In unread-only mode, everything has now been seen once
+                if self.unread_only {
+                    self.apply_rotation();
+                    return;
+                }
+            }
+
+            // Drop badges from headlines that completed their first pass, and show stories
+            // that did with their next headline
+            if self.new_keys.len() != badges_before || turned {
+                let anchor = self.anchor();
+                self.rebuild_ticker_text();
+                self.restore_anchor(anchor);
             }
         }
     }
 
-    /// Mark the current headline as shown
-    fn mark_current_headline_shown(&mut self) {
-        if self.current_headline_idx < self.headlines.len() {
-            let key = if let Some(url) = &self.headlines[self.current_headline_idx].url {
-                url.clone()
-            } else {
-                self.headlines[self.current_headline_idx].title.clone()
-            };
+    /// Mark the current headline as shown, clearing its NEW badge. Returns whether it was
+    /// a story's slot, which now holds the story's next headline (the text needs rebuilding)
+    fn mark_current_headline_shown(&mut self) -> bool {
+        let Some(headline) = self.headlines.get(self.current_headline_idx) else {
+            return false;
+        };
+        self.shown.push(headline.clone());
+        let key = headline_key(headline);
+        // A story's slot is marked by the key of the member it shows
+        let story = self.stories.iter_mut().find(|s| s.slot_key() == key);
+        let key = story.as_ref().map_or(key, |story| headline_key(story.current()));
+        self.new_keys.remove(&key);
+        let turned = story.map(|story| {
+            story.turn();
+            story.slot()
+        });
+        if self.rotation_mode == RotationMode::Fair || self.unread_only {
             self.shown_urls.insert(key);
         }
+        match turned {
+            Some(slot) => {
+                self.headlines[self.current_headline_idx] = slot;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Headline currently at the left edge of the crawl
+    pub fn leading_headline(&self) -> Option<&Headline> {
+        self.anchor().and_then(|a| self.headlines.get(a.index))
+    }
+
+    /// Display text of the leading headline, or the placeholder message if there are none
+    pub fn leading_text(&self) -> String {
+        match self.anchor() {
+            Some(anchor) => {
+                let segment = &self.segments[anchor.index];
+                self.ticker_chars[segment.start..segment.end].iter().collect()
+            }
+            None => self.ticker_text.trim_end_matches(self.delimiters[0].as_str()).to_string(),
+        }
+    }
+
+    /// Most recently published headline in the rotation (the first one if none are dated)
+    /// First-seen times don't count as publication dates here
+    pub fn latest_headline(&self) -> Option<&Headline> {
+        self.headlines
+            .iter()
+            .filter(|h| h.published.is_some() && !self.undated_keys.contains(&headline_key(h)))
+            .max_by_key(|h| h.published)
+            .or(self.headlines.first())
+    }
+
+    /// Headlines fully shown since the last call
+    pub fn take_shown(&mut self) -> Vec<Headline> {
+        std::mem::take(&mut self.shown)
+    }
+
+    /// Add what the rotation (headlines and rendered text) and shown keys take up
+    pub fn memory(&self, usage: &mut Usage) {
+        let rotation = memory::headlines_bytes(self.pool.iter().chain(&self.headlines))
+            + self.ticker_text.capacity()
+            + self.ticker_chars.capacity() * size_of::<char>()
+            + self.segments.capacity() * size_of::<TickerSegment>();
+        usage.add("rotation", rotation, self.headlines.len());
+        usage.add("shown keys", memory::keys_bytes(&self.shown_urls), self.shown_urls.len());
+        let (bytes, count) = self.first_seen.memory();
+        usage.add("first-seen times", bytes, count);
+    }
+
+    /// Save the shown keys, then keep only the max_shown_entries most recently shown
+    pub fn compact_shown(&mut self) {
+        self.save_shown_cache();
+        self.shown_urls = ShownCache::load(self.max_age, self.max_shown_entries).shown_keys();
     }
 
     /// Advance tracking to the next headline
@@ -354,10 +1246,15 @@ impl Ticker {
                     let end_in_view = (seg_end - vis_start).min(width);
 
                     if start_in_view < width && end_in_view > start_in_view {
+                        let badge_end = (seg_start + segment.badge_len)
+                            .saturating_sub(vis_start)
+                            .min(width);
                         visible.push(VisibleSegment {
                             start: start_in_view,
                             end: end_in_view,
                             url: segment.url.clone(),
+                            badge_end,
+                            index: segment.index,
                         });
                     }
                 }
@@ -367,15 +1264,61 @@ impl Ticker {
         visible
     }
 
-    /// Find URL at a given screen position (x coordinate)
-    pub fn get_url_at_position(&self, x: usize, width: usize) -> Option<String> {
-        let segments = self.get_visible_segments(width);
-        for segment in segments {
-            if x >= segment.start && x < segment.end {
-                return segment.url;
-            }
+    /// Find the headline at a given screen position (x coordinate)
+    pub fn headline_at_position(&self, x: usize, width: usize) -> Option<&Headline> {
+        self.get_visible_segments(width)
+            .into_iter()
+            .find(|s| x >= s.start && x < s.end)
+            .and_then(|s| self.headlines.get(s.index))
+    }
+
+    /// Pause, and glide the crawl until `headline` sits in the middle of a crawl `width`
+    /// wide (starting at the left edge if it's wider). False if it isn't in rotation
+    pub fn center(&mut self, headline: &Headline, width: usize) -> bool {
+        let key = headline_key(headline);
+        let index = self.headlines.iter().position(|h| headline_key(h) == key);
+        let Some(segment) = index.and_then(|index| self.segments.get(index)) else {
+            return false;
+        };
+        let margin = width.saturating_sub(segment.end - segment.start) / 2;
+        let target = segment.start as f64 - margin as f64;
+        let len = self.ticker_chars.len() as f64;
+        let mut distance = (target - self.offset).rem_euclid(len);
+        if distance > len / 2.0 {
+            distance -= len;
         }
-        None
+        self.manual_paused = true;
+        self.glide = Some(Glide {
+            from: self.offset,
+            to: self.offset + distance,
+            elapsed: 0.0,
+        });
+        true
+    }
+
+    /// Interrupt the crawl with a BREAKING slate for this headline, after any already queued,
+    /// unless MAX_BREAKING are
+    pub fn break_in(&mut self, headline: Headline) {
+        if self.breaking.len() < MAX_BREAKING {
+            self.breaking.push_back(headline);
+        }
+    }
+
+    /// The takeover on screen, if any
+    pub fn takeover(&self) -> Option<Takeover<'_>> {
+        let headline = self.breaking.front()?;
+        Some(Takeover {
+            headline,
+            wipe: (self.takeover_elapsed / TAKEOVER_WIPE).min(1.0),
+            elapsed: self.takeover_elapsed,
+            caption: self.caption(headline),
+        })
+    }
+
+    /// Take the current slate down, moving on to the next queued one or back to the crawl
+    pub fn end_takeover(&mut self) {
+        self.breaking.pop_front();
+        self.takeover_elapsed = 0.0;
     }
 
     /// Auto-pause (called by hover/focus mode)
@@ -398,6 +1341,16 @@ impl Ticker {
         self.manual_paused = !self.manual_paused;
     }
 
+    /// Headlines in current rotation order
+    pub fn headlines(&self) -> &[Headline] {
+        &self.headlines
+    }
+
+    /// Length of the rendered ticker text in chars
+    pub fn text_len(&self) -> usize {
+        self.ticker_chars.len()
+    }
+
     pub fn headline_count(&self) -> usize {
         self.headlines.len()
     }
@@ -411,57 +1364,126 @@ impl Ticker {
     }
 }
 
+/// Cubic ease-in-out over t from 0 to 1: slow start, quick middle, gentle landing
+fn ease_in_out(t: f64) -> f64 {
+    if t < 0.5 {
+        4.0 * t * t * t
+    } else {
+        1.0 - (2.0 - 2.0 * t).powi(3) / 2.0
+    }
+}
+
+/// Compact age of a headline: "5m", "3h", "2d", or "now". Days are counted as
+/// calendar days in the configured zone, so last night's story reads "1d" this morning
+pub(crate) fn age_label(published: chrono::DateTime<Utc>, dates: &DateStyle) -> String {
+    let now = Utc::now();
+    let duration = now.signed_duration_since(published);
+    if duration.num_days() > 0 {
+        format!("{}d", dates.days_between(published, now).max(1))
+    } else if duration.num_hours() > 0 {
+        format!("{}h", duration.num_hours())
+    } else if duration.num_minutes() > 0 {
+        format!("{}m", duration.num_minutes())
+    } else {
+        "now".to_string()
+    }
+}
+
+/// Fill a headline template; placeholders with nothing to show collapse away
+fn render_template(
+    template: &str,
+    headline: &Headline,
+    index: usize,
+    icon: &str,
+    date: &str,
+    dates: &DateStyle,
+    absolute: bool,
+) -> String {
+    let age = match headline.published {
+        Some(d) if absolute => dates.format(d, ABSOLUTE_TIME_FORMAT),
+        Some(d) => age_label(d, dates),
+        None => String::new(),
+    };
+    let text = template
+        .replace("{source}", &headline.source)
+        .replace("{icon}", icon)
+        .replace("{age}", &age)
+        .replace("{date}", date)
+        .replace("{index}", &index.to_string())
+        .replace("{title}", &headline.title);
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Reorder a rotation so each source's headlines are at least `gap` others apart: each
+/// place goes to the first remaining headline whose source hasn't been in the last `gap`,
+/// or the first remaining one if every source left has
+fn space_sources(mut headlines: Vec<Headline>, gap: usize) -> Vec<Headline> {
+    if gap == 0 {
+        return headlines;
+    }
+    let mut spaced: Vec<Headline> = Vec::with_capacity(headlines.len());
+    while !headlines.is_empty() {
+        let recent = &spaced[spaced.len().saturating_sub(gap)..];
+        let next = headlines
+            .iter()
+            .position(|h| recent.iter().all(|r| r.source != h.source))
+            .unwrap_or(0);
+        spaced.push(headlines.remove(next));
+    }
+    spaced
+}
+
+/// Key used to identify a headline across refreshes (URL, or title if no URL)
+pub(crate) fn headline_key(headline: &Headline) -> String {
+    headline
+        .url
+        .clone()
+        .unwrap_or_else(|| headline.title.clone())
+}
+
+/// Order two headlines by date; undated ones go first unless `undated` is Bottom, whichever
+/// way the dates run
+fn compare_dates(a: &Headline, b: &Headline, newest_first: bool, undated: UndatedMode) -> Ordering {
+    let undated_last = undated == UndatedMode::Bottom;
+    match (a.published, b.published) {
+        (Some(a), Some(b)) if newest_first => b.cmp(&a),
+        (Some(a), Some(b)) => a.cmp(&b),
+        (None, None) => Ordering::Equal,
+        (None, Some(_)) if undated_last => Ordering::Greater,
+        (None, Some(_)) => Ordering::Less,
+        (Some(_), None) if undated_last => Ordering::Less,
+        (Some(_), None) => Ordering::Greater,
+    }
+}
+
 /// A segment visible on screen with its position
 #[derive(Debug, Clone)]
 pub struct VisibleSegment {
     pub start: usize,
     pub end: usize,
     pub url: Option<String>,
+    /// End of the NEW badge in screen columns (equal to start if none visible)
+    pub badge_end: usize,
+    /// Index of the headline in the rotation
+    pub index: usize,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-
-    fn test_config() -> Config {
-        Config {
-            feeds_path: std::path::PathBuf::new(),
-            delimiter: " | ".to_string(),
-            speed: 10,
-            sort: SortMode::ByDate,
-            pause_mode: crate::config::PauseMode::Hover,
-            refresh_interval: std::time::Duration::from_secs(300),
-            max_age: std::time::Duration::from_secs(86400),
-            max_per_feed: 10,
-            max_total: 100,
-            show_source: false,
-            validate_only: false,
-            show_status_bar: false,
-            click_modifier: crate::config::ClickModifier::None,
-            rotation: RotationMode::Continuous,
-            date_format: None,
-            config_path: None,
-        }
-    }
+    use crate::config::test_config;
+    use crate::feeds::headline;
+    use crate::rotation;
+    use std::collections::BTreeSet;
 
     #[test]
     fn test_ticker_basic() {
         let config = test_config();
-        let mut ticker = Ticker::new(&config);
+        let mut ticker = Ticker::with_carried(&config, Carried::default());
 
         let headlines = vec![
-            Headline {
-                title: "Hello".to_string(),
-                url: Some("https://example.com".to_string()),
-                source: "Test".to_string(),
-                published: None,
-            },
-            Headline {
-                title: "World".to_string(),
-                url: None,
-                source: "Test".to_string(),
-                published: None,
-            },
+            headline("Hello", "Test", Some("https://example.com")),
+            headline("World", "Test", None),
         ];
 
         ticker.set_headlines(headlines, SortMode::ByDate);
@@ -472,10 +1494,31 @@ mod tests {
         assert_eq!(visible.chars().count(), 6);
     }
 
+    #[test]
+    fn test_follow() {
+        let config = test_config();
+        let headlines: Vec<Headline> = ["One", "Two", "Three"]
+            .iter()
+            .map(|title| headline(title, "Test", Some(&format!("https://example.com/{}", title))))
+            .collect();
+        let mut leader = Ticker::with_carried(&config, Carried::default());
+        leader.set_headlines(headlines, SortMode::BySource);
+        leader.advance(leader.segments[1].start as f64 + 2.0);
+
+        let mut follower = Ticker::with_carried(&config, Carried::default());
+        follower.mirror(leader.headlines().to_vec());
+        follower.follow(&leader.crawl_position(), 0, 0.0);
+        assert_eq!(follower.leading_headline().unwrap().title, "Two");
+        assert_eq!(follower.offset, leader.offset);
+
+        follower.follow(&leader.crawl_position(), -2, 0.0);
+        assert_eq!(follower.leading_headline().unwrap().title, "Three");
+    }
+
     #[test]
     fn test_ticker_pause() {
         let config = test_config();
-        let mut ticker = Ticker::new(&config);
+        let mut ticker = Ticker::with_carried(&config, Carried::default());
         assert!(!ticker.is_paused());
 
         ticker.auto_pause();
@@ -497,4 +1540,513 @@ mod tests {
         ticker.toggle_pause();
         assert!(!ticker.is_paused());
     }
+
+    #[test]
+    fn test_speed_easing() {
+        let mut ticker = Ticker::with_carried(&test_config(), Carried::default());
+        let title = "A headline long enough to scroll for a good while without wrapping".repeat(9);
+        let headline = headline(&title, "Test", None);
+        ticker.set_headlines(vec![headline], SortMode::BySource);
+        ticker.set_speed(10);
+        ticker.current_speed = 10.0;
+
+        // Pausing coasts to a stop rather than freezing
+        ticker.toggle_pause();
+        let before = ticker.offset;
+        ticker.tick(0.05);
+        let coasted = ticker.offset - before;
+        assert!(coasted > 0.0 && coasted < 0.5);
+        ticker.tick(1.0);
+        let stopped = ticker.offset;
+        ticker.tick(1.0);
+        assert_eq!(ticker.offset, stopped);
+
+        // Resuming and speeding up ramp in
+        ticker.toggle_pause();
+        ticker.set_speed(40);
+        ticker.tick(0.1);
+        assert!(ticker.current_speed > 20.0 && ticker.current_speed < 30.0);
+        ticker.tick(1.0);
+        assert_eq!(ticker.current_speed, 40.0);
+    }
+
+    #[test]
+    fn test_expire_headlines() {
+        let config = test_config();
+        let mut ticker = Ticker::with_carried(&config, Carried::default());
+
+        let headlines = vec![
+            Headline {
+                published: Some(Utc::now()),
+                ..headline("Fresh", "Test", Some("https://example.com/fresh"))
+            },
+            Headline {
+                published: Some(Utc::now() - chrono::Duration::hours(48)),
+                ..headline("Stale", "Test", Some("https://example.com/stale"))
+            },
+            headline("Undated", "Test", None),
+        ];
+
+        ticker.set_headlines(headlines, SortMode::ByDate);
+        assert_eq!(ticker.expire_headlines(Duration::from_secs(86400)), 1);
+        assert_eq!(ticker.headline_count(), 2);
+        assert_eq!(ticker.expire_headlines(Duration::from_secs(86400)), 0);
+
+        // Headlines the clock fallback put in stay, though all are older than max_age
+        let stale = Headline {
+            published: Some(Utc::now() - chrono::Duration::hours(48)),
+            ..headline("Stale", "Test", None)
+        };
+        let mut feed_headlines = HashMap::from([("test".to_string(), Vec::new())]);
+        let outdated = [("test".to_string(), vec![stale])];
+        let restored = rotation::clock_fallback(&mut feed_headlines, &outdated, &BTreeSet::new());
+        ticker.set_clock_suspect(!restored.is_empty());
+        ticker.set_headlines(restored, SortMode::ByDate);
+        assert_eq!(ticker.expire_headlines(Duration::from_secs(86400)), 0);
+        assert_eq!(ticker.headline_count(), 1);
+    }
+
+    #[test]
+    fn test_new_headlines_badged() {
+        let config = test_config();
+        let mut ticker = Ticker::with_carried(&config, Carried::default());
+
+        let first = headline("First", "Test", Some("https://example.com/1"));
+        let second = headline("Second", "Test", Some("https://example.com/2"));
+
+        // Initial load is never badged
+        assert_eq!(ticker.set_headlines(vec![first.clone()], SortMode::ByDate), 0);
+        assert!(!ticker.ticker_text.contains(NEW_BADGE));
+
+        assert_eq!(ticker.set_headlines(vec![first, second], SortMode::ByDate), 1);
+        assert!(ticker.ticker_text.contains(NEW_BADGE));
+        assert_eq!(ticker.segments.iter().filter(|s| s.badge_len > 0).count(), 1);
+    }
+
+    #[test]
+    fn test_toggle_relative_times() {
+        let mut config = test_config();
+        config.dates = DateStyle::parse(Some("UTC"), Some("C")).unwrap();
+        let mut ticker = Ticker::with_carried(&config, Carried::default());
+        let published = Utc::now() - chrono::Duration::hours(2);
+        let headlines = vec![Headline {
+            published: Some(published),
+            ..headline("Launch delayed", "Space", None)
+        }];
+        ticker.set_headlines(headlines, SortMode::ByDate);
+        assert_eq!(ticker.toggle_relative_times(), None);
+
+        ticker.date_format = Some("relative".to_string());
+        ticker.rebuild_ticker_text();
+        assert!(ticker.ticker_text.starts_with("2h ago - Launch delayed"));
+        assert_eq!(ticker.toggle_relative_times(), Some(false));
+        let clock = published.format("%H:%M").to_string();
+        assert!(ticker.ticker_text.starts_with(&format!("{} - Launch delayed", clock)));
+        assert_eq!(ticker.toggle_relative_times(), Some(true));
+        assert!(ticker.ticker_text.starts_with("2h ago"));
+    }
+
+    #[test]
+    fn test_unread_only() {
+        let mut config = test_config();
+        config.unread_only = true;
+        let mut ticker = Ticker::with_carried(&config, Carried::default());
+        ticker.shown_urls.clear();
+
+        let headlines = vec![
+            headline("Seen", "Test", Some("https://example.com/seen")),
+            headline("Unseen", "Test", Some("https://example.com/unseen")),
+        ];
+
+        ticker.mark_read("https://example.com/seen");
+        ticker.set_headlines(headlines, SortMode::ByDate);
+        assert_eq!(ticker.headline_count(), 1);
+
+        ticker.mark_read("https://example.com/unseen");
+        assert!(!ticker.toggle_unread_only());
+        assert_eq!(ticker.headline_count(), 2);
+        assert!(ticker.toggle_unread_only());
+        assert_eq!(ticker.headline_count(), 0);
+        assert!(ticker.ticker_text.starts_with("All caught up"));
+    }
+
+    #[test]
+    fn test_reconfigure() {
+        let mut config = test_config();
+        let mut ticker = Ticker::with_carried(&config, Carried::default());
+        ticker.set_headlines(
+            vec![headline("One", "Test", None)],
+            SortMode::ByDate,
+        );
+        assert_eq!(ticker.ticker_text, "One | ");
+
+        config.show_source = true;
+        config.delimiter = " ~ ".to_string();
+        config.speed = 40;
+        ticker.reconfigure(&config);
+        assert_eq!(ticker.ticker_text, "[Test] One ~ ");
+        assert_eq!(ticker.speed(), 10);
+    }
+
+    #[test]
+    fn test_safe_mode() {
+        let mut config = test_config();
+        config.show_source = true;
+        let mut ticker = Ticker::with_carried(&config, Carried::default());
+        let headline = headline("One", "Private", Some("https://private.example/1"));
+        ticker.set_headlines(vec![headline.clone()], SortMode::ByDate);
+        assert_eq!(ticker.caption(&headline), "Private: One");
+        assert!(ticker.get_visible_segments(20)[0].url.is_some());
+
+        assert!(ticker.toggle_safe_mode());
+        assert_eq!(ticker.ticker_text, "One | ");
+        assert_eq!(ticker.caption(&headline), "One");
+        assert!(ticker.get_visible_segments(20).iter().all(|s| s.url.is_none()));
+        // A reload doesn't undo the toggle
+        ticker.reconfigure(&config);
+        assert_eq!(ticker.ticker_text, "One | ");
+        assert!(!ticker.toggle_safe_mode());
+        assert_eq!(ticker.ticker_text, "[Private] One | ");
+    }
+
+    #[test]
+    fn test_group_stories() {
+        let mut config = test_config();
+        config.group_stories = true;
+        let mut ticker = Ticker::with_carried(&config, Carried::default());
+        let linked = |title: &str, source: &str| {
+            headline(title, source, Some(&format!("https://{}.example/", source)))
+        };
+        ticker.set_headlines(
+            vec![
+                linked("Quake hits coast town", "wire"),
+                linked("Rates unchanged", "money"),
+                linked("Coast town quake: rescuers arrive", "daily"),
+            ],
+            SortMode::ByDate,
+        );
+        assert_eq!(ticker.headline_count(), 2);
+        assert_eq!(ticker.stories().len(), 1);
+        let slot = "Story: Quake hits coast town (2 sources)";
+        assert_eq!(ticker.ticker_text, format!("{} | Rates unchanged | ", slot));
+
+        // Once the slot has crossed, it shows the story's next headline
+        ticker.advance(ticker.segments[0].end as f64);
+        let slot = "Story: Coast town quake: rescuers arrive (2 sources)";
+        assert!(ticker.ticker_text.starts_with(slot));
+        assert_eq!(ticker.leading_headline().unwrap().title, "Rates unchanged");
+
+        // Without URLs the slot turns all the same, marking the member it showed as shown
+        ticker.rotation_mode = RotationMode::Fair;
+        ticker.set_headlines(
+            vec![
+                headline("Quake hits coast town", "wire", None),
+                headline("Rates unchanged", "money", None),
+                headline("Coast town quake: rescuers arrive", "daily", None),
+            ],
+            SortMode::ByDate,
+        );
+        ticker.advance(ticker.segments[0].end as f64);
+        assert!(ticker.ticker_text.starts_with(slot));
+        assert!(ticker.shown_urls.contains("Quake hits coast town"));
+        assert!(ticker.shown_urls.iter().all(|key| !key.starts_with("Story:")));
+    }
+
+    #[test]
+    fn test_space_sources() {
+        let numbered = |source: &str, n: usize| {
+            headline(&format!("{} {}", source, n), source, None)
+        };
+        let titles = |headlines: Vec<Headline>| -> Vec<String> {
+            headlines.into_iter().map(|h| h.title).collect()
+        };
+        let rotation = vec![
+            numbered("a", 1),
+            numbered("a", 2),
+            numbered("a", 3),
+            numbered("b", 1),
+            numbered("c", 1),
+            numbered("b", 2),
+        ];
+        assert_eq!(
+            titles(space_sources(rotation.clone(), 1)),
+            ["a 1", "b 1", "a 2", "c 1", "a 3", "b 2"]
+        );
+        // Too few others to keep every pair two apart: the last one comes sooner
+        assert_eq!(
+            titles(space_sources(rotation.clone(), 2)),
+            ["a 1", "b 1", "c 1", "a 2", "b 2", "a 3"]
+        );
+        assert_eq!(titles(space_sources(rotation.clone(), 0)), titles(rotation));
+    }
+
+    #[test]
+    fn test_set_sort() {
+        let config = test_config();
+        let headlines: Vec<Headline> = [("Zed", "B"), ("Alpha", "A")]
+            .iter()
+            .enumerate()
+            .map(|(i, (title, source))| Headline {
+                published: Some(Utc::now() - chrono::Duration::hours(i as i64)),
+                ..headline(title, source, None)
+            })
+            .collect();
+        let mut ticker = Ticker::with_carried(&config, Carried::default());
+        ticker.set_headlines(headlines, SortMode::ByDate);
+        assert_eq!(ticker.headlines()[0].title, "Zed");
+
+        ticker.set_sort(ticker.sort().next());
+        assert_eq!(ticker.sort(), SortMode::ByDateAsc);
+        assert_eq!(ticker.headlines()[0].title, "Alpha");
+        assert_eq!(ticker.headline_count(), 2);
+
+        // Reshuffling draws a new random order
+        let headlines = (0..12).map(|i| headline(&format!("Story {}", i), "A", None)).collect();
+        ticker.set_headlines(headlines, SortMode::Random);
+        assert_eq!(ticker.sort(), SortMode::Random);
+        let order = |ticker: &Ticker| -> Vec<String> {
+            ticker.headlines().iter().map(|h| h.title.clone()).collect()
+        };
+        let (seed, before) = (ticker.seed, order(&ticker));
+        ticker.reshuffle();
+        assert_ne!(ticker.seed, seed);
+        assert_eq!(ticker.headline_count(), 12);
+        assert_ne!(order(&ticker), before);
+    }
+
+    #[test]
+    fn test_undated_placement() {
+        let mut config = test_config();
+        let dated = |title: &str, hours: i64| Headline {
+            published: Some(Utc::now() + chrono::Duration::hours(hours)),
+            ..headline(title, "A", None)
+        };
+        let headlines = vec![
+            dated("Old", -2),
+            dated("Future", 48),
+            Headline { published: None, ..dated("Undated", 0) },
+        ];
+        let titles = |ticker: &Ticker| -> Vec<String> {
+            ticker.headlines().iter().map(|h| h.title.clone()).collect()
+        };
+
+        // First seen now, with the far-future date treated as missing
+        let mut ticker = Ticker::with_carried(&config, Carried::default());
+        ticker.set_headlines(headlines.clone(), SortMode::ByDate);
+        assert_eq!(ticker.headlines().len(), 3);
+        assert_eq!(ticker.headlines()[2].title, "Old");
+        assert!(ticker.headlines().iter().all(|h| h.published.is_some()));
+
+        config.undated = UndatedMode::Bottom;
+        ticker.reconfigure(&config);
+        assert_eq!(titles(&ticker)[0], "Old");
+        ticker.set_sort(SortMode::ByDateAsc);
+        assert_eq!(titles(&ticker)[0], "Old");
+
+        config.undated = UndatedMode::Top;
+        ticker.reconfigure(&config);
+        assert_eq!(titles(&ticker)[2], "Old");
+
+        config.undated = UndatedMode::Exclude;
+        ticker.reconfigure(&config);
+        assert_eq!(titles(&ticker), vec!["Old"]);
+    }
+
+    #[test]
+    fn test_resume_saved_position() {
+        let mut ticker = Ticker::with_carried(&test_config(), Carried::default());
+        ticker.resume = Some(CrawlPosition {
+            seed: 7,
+            headline_key: Some("https://example.com/b".to_string()),
+            headline_index: 0,
+            into: 3.5,
+        });
+
+        let headlines = ["a", "b", "c"]
+            .iter()
+            .map(|name| {
+                let url = format!("https://example.com/{}", name);
+                headline(&format!("Headline {}", name), "Test", Some(&url))
+            })
+            .collect();
+        ticker.set_headlines(headlines, SortMode::BySource);
+
+        let start = ticker.segments[1].start as f64;
+        assert_eq!(ticker.offset, start + 3.5);
+        assert!(ticker.resume.is_none());
+    }
+
+    #[test]
+    fn test_undo_skip_and_mute() {
+        let mut ticker = Ticker::with_carried(&test_config(), Carried::default());
+        let headlines: Vec<Headline> = [("a", "One"), ("b", "One"), ("c", "Two")]
+            .iter()
+            .map(|(name, source)| {
+                let url = format!("https://example.com/{}", name);
+                headline(&format!("Headline {}", name), source, Some(&url))
+            })
+            .collect();
+        ticker.set_headlines(headlines.clone(), SortMode::BySource);
+
+        ticker.skip_headline(&headlines[2]);
+        ticker.mute_source("One");
+        assert_eq!(ticker.headline_count(), 0);
+
+        assert_eq!(ticker.undo_exclusion(), Some(Exclusion::Muted("One".to_string())));
+        assert_eq!(ticker.headline_count(), 2);
+        assert!(matches!(ticker.undo_exclusion(), Some(Exclusion::Skipped { .. })));
+        assert_eq!(ticker.headline_count(), 3);
+        assert_eq!(ticker.undo_exclusion(), None);
+    }
+
+    #[test]
+    fn test_center() {
+        let mut ticker = Ticker::with_carried(&test_config(), Carried::default());
+        let headlines: Vec<Headline> = ["Short one", "A rather longer second headline", "Third"]
+            .iter()
+            .map(|title| headline(title, "Wire", None))
+            .collect();
+        ticker.set_headlines(headlines.clone(), SortMode::BySource);
+        ticker.tick(0.4);
+
+        assert!(ticker.center(&headlines[1], 60));
+        assert!(ticker.is_paused());
+        ticker.tick(0.25);
+        ticker.tick(0.25);
+        let segment = ticker
+            .get_visible_segments(60)
+            .into_iter()
+            .find(|s| s.index == 1)
+            .unwrap();
+        // Centered: as much room on the left as on the right
+        assert!((segment.start as isize - (60 - segment.end) as isize).abs() <= 1);
+        let offset = ticker.get_visible_text(60);
+        ticker.tick(1.0);
+        assert_eq!(ticker.get_visible_text(60), offset);
+
+        let gone = Headline {
+            title: "Not in rotation".to_string(),
+            ..headlines[0].clone()
+        };
+        assert!(!ticker.center(&gone, 60));
+    }
+
+    #[test]
+    fn test_takeover() {
+        let mut ticker = Ticker::with_carried(&test_config(), Carried::default());
+        let wire = |title: &str| headline(title, "Wire", None);
+        ticker.set_headlines(vec![wire("Calm news day")], SortMode::BySource);
+        let crawl = ticker.get_visible_text(20);
+
+        for title in ["First", "Second", "Third", "Fourth"] {
+            ticker.break_in(wire(title));
+        }
+        assert_eq!(ticker.breaking.len(), MAX_BREAKING);
+        ticker.tick(0.3);
+        assert_eq!(ticker.takeover().unwrap().wipe, 0.5);
+        ticker.tick(1.0);
+        let takeover = ticker.takeover().unwrap();
+        assert_eq!((takeover.headline.title.as_str(), takeover.wipe), ("First", 1.0));
+        ticker.toggle_pause();
+        ticker.tick(10.0);
+        assert_eq!(ticker.takeover().unwrap().headline.title, "First");
+        ticker.toggle_pause();
+        ticker.tick(10.0);
+        assert_eq!(ticker.takeover().unwrap().headline.title, "Second");
+        assert_eq!(ticker.takeover().unwrap().elapsed, 0.0);
+        // The crawl held still throughout, and moves again once the slates are down
+        assert_eq!(ticker.get_visible_text(20), crawl);
+        ticker.end_takeover();
+        assert_eq!(ticker.takeover().unwrap().headline.title, "Third");
+        ticker.end_takeover();
+        assert!(ticker.takeover().is_none());
+        ticker.tick(1.0);
+        assert_ne!(ticker.get_visible_text(20), crawl);
+    }
+
+    #[test]
+    fn test_latest_headline() {
+        let mut ticker = Ticker::with_carried(&test_config(), Carried::default());
+        assert!(ticker.latest_headline().is_none());
+
+        let now = Utc::now();
+        let headlines = [
+            ("Old", Some(now - chrono::Duration::hours(2))),
+            ("Undated", None),
+            ("New", Some(now)),
+        ]
+        .into_iter()
+        .map(|(title, published)| Headline { published, ..headline(title, "Test", None) })
+        .collect();
+        ticker.set_headlines(headlines, SortMode::BySource);
+        assert_eq!(ticker.latest_headline().unwrap().title, "New");
+    }
+
+    #[test]
+    fn test_render_template() {
+        let headline = Headline {
+            published: Some(Utc::now() - chrono::Duration::minutes(90)),
+            ..headline("Launch delayed", "Space", None)
+        };
+        let dates = DateStyle::parse(Some("UTC"), Some("C")).unwrap();
+        assert_eq!(
+            render_template(
+                "{icon} #{index} {source}: {title} ({age})",
+                &headline,
+                3,
+                "*",
+                "",
+                &dates,
+                false
+            ),
+            "* #3 Space: Launch delayed (1h)"
+        );
+        let published = dates.format(headline.published.unwrap(), "%H:%M");
+        assert_eq!(
+            render_template("{title} ({age})", &headline, 1, "", "", &dates, true),
+            format!("Launch delayed ({})", published)
+        );
+        // Empty placeholders don't leave gaps
+        assert_eq!(
+            render_template("{icon} {date} {title}", &headline, 1, "", "", &dates, false),
+            "Launch delayed"
+        );
+    }
+
+    #[test]
+    fn test_delimiters_cycle() {
+        let mut config = test_config();
+        config.delimiters = vec![" * ".to_string(), " - ".to_string()];
+        let mut ticker = Ticker::with_carried(&config, Carried::default());
+        let headlines = ["a", "b", "c"]
+            .iter()
+            .map(|title| headline(title, "Test", None))
+            .collect();
+        ticker.set_headlines(headlines, SortMode::BySource);
+        assert_eq!(ticker.ticker_text, "a * b - c * ");
+    }
+
+    #[test]
+    fn test_flip_holds_each_headline() {
+        let mut config = test_config();
+        config.display = DisplayMode::Flip;
+        config.flip_interval = Duration::from_secs(5);
+        let mut ticker = Ticker::with_carried(&config, Carried::default());
+        ticker.resume = None;
+        let headlines = ["alpha", "beta", "gamma"]
+            .iter()
+            .map(|title| headline(title, "Test", None))
+            .collect();
+        ticker.set_headlines(headlines, SortMode::BySource);
+
+        ticker.tick(4.0);
+        assert_eq!(ticker.leading_text(), "alpha");
+        ticker.tick(1.0);
+        assert_eq!(ticker.leading_text(), "beta");
+        ticker.tick(5.0);
+        assert_eq!(ticker.leading_text(), "gamma");
+        ticker.tick(5.0);
+        assert_eq!(ticker.leading_text(), "alpha");
+        assert_eq!(ticker.take_shown().len(), 3);
+    }
 }
@@ -1,11 +1,29 @@
-use crate::ticker::Ticker;
+use crate::config::SortMode;
+use crate::feeds::Headline;
+use crate::logos::LOGO_WIDTH;
+use crate::theme::Theme;
+use crate::ticker::{Takeover, Ticker, VisibleSegment};
 use ratatui::{
     buffer::Buffer,
     layout::Rect,
-    style::{Color, Style, Stylize},
+    style::{Color, Modifier, Style, Stylize},
     widgets::Widget,
 };
 use std::io::{self, Write};
+use std::ops::Range;
+use std::time::Duration;
+
+/// Background of the --lower-third band (xterm 256-color navy)
+const LOWER_THIRD_BG: u8 = 18;
+
+/// Narrower than this, the crawl gives way to a single still headline
+pub const MIN_CRAWL_WIDTH: u16 = 10;
+
+/// Rows taken by the --lower-third band
+pub const LOWER_THIRD_HEIGHT: u16 = 3;
+
+/// Widest the source bug in the lower third may get, in chars
+const BUG_MAX_WIDTH: usize = 20;
 
 /// Widget for rendering the ticker with clickable links
 pub struct TickerWidget<'a> {
@@ -33,105 +51,507 @@ impl Widget for TickerWidget<'_> {
             return;
         }
 
+        if let Some(text) = single_headline_text(self.ticker, area.width) {
+            let linked = self.ticker.leading_headline().is_some_and(|h| h.url.is_some());
+            let theme = self.ticker.theme();
+            let headline = theme.headline.patch(self.ticker.leading_style());
+            let style = match (linked, self.hovered_x.is_some()) {
+                (true, true) => theme.hover,
+                (true, false) => headline.underlined(),
+                (false, _) => headline,
+            };
+            let lines = wrap(&text, area.width as usize, area.height as usize);
+            for (row, line) in lines.iter().enumerate() {
+                let y = area.y + row as u16;
+                buf.set_stringn(area.x, y, line, area.width as usize, style);
+            }
+            return;
+        }
+
         let width = area.width as usize;
-        let visible_text = self.ticker.get_visible_text(width);
         let visible_segments = self.ticker.get_visible_segments(width);
-        let frac = self.ticker.get_fractional_offset();
-        let chars: Vec<char> = visible_text.chars().collect();
+        let chars = crawl_chars(self.ticker, width);
 
-        // Render character by character
-        // We get width+1 chars, and use fractional offset to blend between them
-        for i in 0..width {
+        for (i, &ch) in chars.iter().enumerate() {
             let x = area.x + i as u16;
             let y = area.y;
 
-            // Select character based on fractional offset
-            // When frac > 0.5, we're closer to showing the next character
-            let char_idx = if frac > 0.5 { i + 1 } else { i };
-            let ch = chars.get(char_idx).copied().unwrap_or(' ');
+            let is_hovered = self.hovered_x == Some(x);
+            let style = crawl_style(self.ticker, &visible_segments, i, width, is_hovered);
 
-            // Check if this position is part of a clickable segment
-            let is_clickable = visible_segments
-                .iter()
-                .any(|seg| i >= seg.start && i < seg.end && seg.url.is_some());
+            buf[(x, y)].set_char(ch).set_style(style);
+        }
+    }
+}
+
+/// Text shown still instead of the crawl: the leading headline in flip display, or
+/// just its title when the area is too narrow to scroll legibly
+pub fn single_headline_text(ticker: &Ticker, width: u16) -> Option<String> {
+    if ticker.is_flip() {
+        Some(ticker.leading_text())
+    } else if width < MIN_CRAWL_WIDTH {
+        Some(ticker.leading_headline().map(|h| h.title.clone()).unwrap_or_default())
+    } else {
+        None
+    }
+}
 
-            // Check if this position is being hovered
-            let is_hovered = self.hovered_x.map(|hx| hx == x).unwrap_or(false);
+/// Word-wrap text into at most `rows` lines of `width` chars, cutting the overflow with '…'
+pub fn wrap(text: &str, width: usize, rows: usize) -> Vec<String> {
+    if width == 0 {
+        return vec![String::new()];
+    }
+    let mut lines: Vec<String> = Vec::new();
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        let mut word: Vec<char> = word.chars().collect();
+        let len = line.chars().count();
+        if len > 0 && len + 1 + word.len() <= width {
+            line.push(' ');
+            line.extend(word);
+            continue;
+        }
+        if len > 0 {
+            lines.push(std::mem::take(&mut line));
+        }
+        // Words wider than a whole line are split wherever they run out of room
+        while word.len() > width {
+            lines.push(word.drain(..width).collect());
+        }
+        line = word.into_iter().collect();
+    }
+    if !line.is_empty() || lines.is_empty() {
+        lines.push(line);
+    }
 
-            let style = if is_hovered && is_clickable {
-                Style::default().fg(Color::Cyan).underlined()
-            } else if is_clickable {
-                Style::default().underlined()
-            } else {
-                Style::default()
-            };
+    let rows = rows.max(1);
+    if lines.len() > rows {
+        let rest = lines[rows - 1..].join(" ");
+        lines.truncate(rows - 1);
+        lines.push(truncate(&rest, width));
+    }
+    lines
+}
 
-            buf[(x, y)].set_char(ch).set_style(style);
+/// Columns of the ticker row (first, count) left to the crawl by the theme's padding, which
+/// gives way on terminals too narrow for it
+pub fn crawl_span(theme: &Theme, width: u16) -> (u16, u16) {
+    let padding = theme.padding.min(width.saturating_sub(MIN_CRAWL_WIDTH) / 2);
+    (padding, width - 2 * padding)
+}
+
+/// Columns the theme's fade covers at each end of a crawl `width` wide: none when that
+/// would hide more than half of it
+fn fade_width(ticker: &Ticker, width: usize) -> usize {
+    let fade = ticker.theme().fade.len();
+    if fade * 4 <= width {
+        fade
+    } else {
+        0
+    }
+}
+
+/// Left-aligned blocks from 1/8 to 7/8 of a cell
+const LEFT_BLOCKS: [char; 7] = ['▏', '▎', '▍', '▌', '▋', '▊', '▉'];
+
+/// The characters shown in each crawl column, shared by the widget and the OSC 8 writer
+fn crawl_chars(ticker: &Ticker, width: usize) -> Vec<char> {
+    let text: Vec<char> = ticker.get_visible_text(width).chars().collect();
+    let frac = ticker.get_fractional_offset();
+    let at = |i: usize| text.get(i).copied().unwrap_or(' ');
+
+    let mut chars: Vec<char> = if !ticker.smooth() {
+        // Snap to whichever character the fractional offset is closer to
+        let shift = usize::from(frac > 0.5);
+        (0..width).map(|i| at(i + shift)).collect()
+    } else {
+        // Step exactly on cell boundaries, and show the sub-cell progress at the edges:
+        // the leaving character shrinks to a partial block, the arriving one grows in
+        let mut chars: Vec<char> = (0..width).map(at).collect();
+        let eighths = (frac * 8.0) as usize;
+        if width >= 2 && eighths > 0 {
+            if !at(0).is_whitespace() {
+                chars[0] = LEFT_BLOCKS[7 - eighths.min(7)];
+            }
+            if !at(width).is_whitespace() {
+                chars[width - 1] = if eighths >= 4 { '▐' } else { '▕' };
+            }
+        }
+        chars
+    };
+
+    let fade = &ticker.theme().fade[..fade_width(ticker, width)];
+    for (i, &ch) in fade.iter().enumerate() {
+        chars[i] = ch;
+        chars[width - 1 - i] = ch;
+    }
+    chars
+}
+
+/// Style of column i of a crawl `width` wide, shared by the widget and the OSC 8 writer so
+/// they agree
+fn crawl_style(
+    ticker: &Ticker,
+    segments: &[VisibleSegment],
+    i: usize,
+    width: usize,
+    hovered: bool,
+) -> Style {
+    let theme = ticker.theme();
+    // The edge fade takes the delimiter's style
+    let fade = fade_width(ticker, width);
+    if i < fade || i >= width - fade {
+        return theme.delimiter;
+    }
+    match segments.iter().find(|seg| i >= seg.start && i < seg.end) {
+        Some(seg) if i < seg.badge_end => theme.badge,
+        Some(seg) if seg.url.is_some() && hovered => theme.hover,
+        Some(seg) if seg.url.is_some() => {
+            theme.headline.patch(ticker.headline_style(seg.index)).underlined()
+        }
+        Some(seg) => theme.headline.patch(ticker.headline_style(seg.index)),
+        // Outside any headline: a delimiter, unless there are no headlines at all
+        None if ticker.headline_count() > 0 => theme.delimiter,
+        None => Style::default(),
+    }
+}
+
+/// Static line holding a single headline (pinned, or the latest in broadcast layout)
+pub struct StaticHeadlineWidget<'a> {
+    headline: &'a Headline,
+    label: &'a str,
+    label_style: Style,
+    hovered: bool,
+    /// Leave the source out (screen-share safe mode)
+    safe_mode: bool,
+}
+
+impl<'a> StaticHeadlineWidget<'a> {
+    pub fn new(headline: &'a Headline, label: &'a str) -> Self {
+        Self {
+            headline,
+            label,
+            label_style: Style::default().fg(Color::Black).bg(Color::Cyan).bold(),
+            hovered: false,
+            safe_mode: false,
+        }
+    }
+
+    pub fn label_style(mut self, style: Style) -> Self {
+        self.label_style = style;
+        self
+    }
+
+    pub fn safe_mode(mut self, safe_mode: bool) -> Self {
+        self.safe_mode = safe_mode;
+        self
+    }
+
+    pub fn hovered(mut self, hovered: bool) -> Self {
+        self.hovered = hovered;
+        self
+    }
+}
+
+impl Widget for StaticHeadlineWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.width == 0 || area.height == 0 {
+            return;
+        }
+
+        let label = format!(" {} ", self.label);
+        let text = if self.safe_mode {
+            format!(" {}", self.headline.title)
+        } else {
+            format!(" [{}] {}", self.headline.source, self.headline.title)
+        };
+        let linked = self.headline.url.is_some() && !self.safe_mode;
+        let text_style = match (self.hovered, linked) {
+            (true, true) => Style::default().fg(Color::Cyan).underlined(),
+            (false, true) => Style::default().underlined(),
+            _ => Style::default(),
+        };
+
+        let chars = label
+            .chars()
+            .map(|c| (c, self.label_style))
+            .chain(text.chars().map(|c| (c, text_style)));
+        for (i, (ch, style)) in chars.take(area.width as usize).enumerate() {
+            buf[(area.x + i as u16, area.y)].set_char(ch).set_style(style);
+        }
+    }
+}
+
+/// Broadcast-style band: headline strip, source bug + crawl + clock, status row
+pub struct LowerThirdWidget<'a> {
+    ticker: &'a Ticker,
+    headline: Option<StaticHeadlineWidget<'a>>,
+    status: Option<StatusBar<'a>>,
+    hovered_x: Option<u16>,
+    clock: String,
+    /// Text for the logo slot opening the bug (blank under a logo image); None: no slot
+    logo: Option<&'a str>,
+}
+
+impl<'a> LowerThirdWidget<'a> {
+    pub fn new(ticker: &'a Ticker) -> Self {
+        Self {
+            ticker,
+            headline: None,
+            status: None,
+            hovered_x: None,
+            clock: ticker.dates().now("%H:%M"),
+            logo: None,
+        }
+    }
+
+    /// Leave room for a logo before the source name, showing `text` there
+    pub fn logo(mut self, text: Option<&'a str>) -> Self {
+        self.logo = text;
+        self
+    }
+
+    /// Static headline for the top row of the band
+    pub fn headline(mut self, headline: Option<StaticHeadlineWidget<'a>>) -> Self {
+        self.headline = headline;
+        self
+    }
+
+    /// Status bar for the bottom row of the band
+    pub fn status(mut self, status: Option<StatusBar<'a>>) -> Self {
+        self.status = status;
+        self
+    }
+
+    pub fn hovered(mut self, x: Option<u16>) -> Self {
+        self.hovered_x = x;
+        self
+    }
+
+    /// Bug width: fits the longest source in rotation, so the crawl doesn't shift
+    fn bug_width(&self) -> u16 {
+        let longest = self
+            .ticker
+            .headlines()
+            .iter()
+            .map(|h| h.source.chars().count())
+            .max()
+            .unwrap_or(0);
+        let slot = self.logo.map_or(0, |_| LOGO_WIDTH + 1);
+        longest.min(BUG_MAX_WIDTH) as u16 + 2 + slot
+    }
+
+    /// Where the crawl sits inside the band
+    pub fn crawl_area(&self, area: Rect) -> Rect {
+        let bug = self.bug_width();
+        let clock = self.clock.chars().count() as u16 + 2;
+        let width = area.width.saturating_sub(bug + clock);
+        Rect::new(area.x + bug.min(area.width), area.y + 1, width, 1)
+    }
+}
+
+impl Widget for LowerThirdWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.width == 0 || area.height < 2 {
+            return;
+        }
+
+        let band = Style::default().fg(Color::White).bg(Color::Indexed(LOWER_THIRD_BG));
+        buf.set_style(area, band);
+
+        let crawl = self.crawl_area(area);
+        let row = crawl.y;
+
+        // Source bug for the headline currently leading the crawl
+        let source = self
+            .ticker
+            .leading_headline()
+            .filter(|_| !self.ticker.safe_mode())
+            .map(|h| h.source.to_uppercase())
+            .unwrap_or_default();
+        let bug_width = (crawl.x - area.x) as usize;
+        let slot = self.logo.map_or(0, |_| LOGO_WIDTH as usize + 1);
+        let name_width = bug_width.saturating_sub(2 + slot);
+        let bug: String = format!(" {:slot$}{:<name_width$} ", "", source)
+            .chars()
+            .take(bug_width)
+            .collect();
+        let bug_style = Style::default().fg(Color::Black).bg(Color::Yellow).bold();
+        buf.set_stringn(area.x, row, &bug, bug_width, bug_style);
+        if let Some(logo) = self.logo {
+            let width = (LOGO_WIDTH as usize).min(bug_width.saturating_sub(1));
+            buf.set_stringn(area.x + 1, row, logo, width, bug_style);
+        }
+
+        TickerWidget::new(self.ticker)
+            .hovered(self.hovered_x)
+            .render(crawl, buf);
+
+        let clock_x = crawl.right();
+        let clock_width = area.right().saturating_sub(clock_x) as usize;
+        let clock = format!(" {} ", self.clock);
+        buf.set_stringn(clock_x, row, &clock, clock_width, band.bold());
+
+        if let Some(headline) = self.headline {
+            headline.render(Rect::new(area.x, area.y, area.width, 1), buf);
+        }
+        if let (Some(status), true) = (self.status, area.height > 2) {
+            status.render(Rect::new(area.x, area.y + 2, area.width, 1), buf);
+        }
+    }
+}
+
+/// Seconds the BREAKING label flashes for
+const TAKEOVER_FLASH: f64 = 2.0;
+
+/// BREAKING slate over the ticker row: a red bar wipes across it, then holds the breaking
+/// headline behind a flashing label
+pub struct TakeoverWidget<'a> {
+    takeover: Takeover<'a>,
+}
+
+impl<'a> TakeoverWidget<'a> {
+    pub fn new(takeover: Takeover<'a>) -> Self {
+        Self { takeover }
+    }
+}
+
+impl Widget for TakeoverWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.width == 0 || area.height == 0 {
+            return;
+        }
+        let Takeover {
+            wipe,
+            elapsed,
+            caption,
+            ..
+        } = self.takeover;
+        let filled = (area.width as f64 * wipe).ceil() as u16;
+        let slate = Style::default().fg(Color::White).bg(Color::Red).bold();
+        let bar = Rect::new(area.x, area.y, filled.min(area.width), area.height);
+        for y in bar.top()..bar.bottom() {
+            buf.set_stringn(bar.x, y, " ".repeat(bar.width as usize), bar.width as usize, slate);
+        }
+
+        let label = " BREAKING ";
+        let flash = elapsed < TAKEOVER_FLASH && (elapsed * 4.0) as u64 % 2 == 1;
+        let label_style = if flash { slate.reversed() } else { slate };
+        let row = area.y + (area.height - 1) / 2;
+        buf.set_stringn(area.x, row, label, bar.width as usize, label_style);
+        let x = area.x + label.chars().count() as u16;
+        let room = area.right().saturating_sub(x + 1) as usize;
+        if wipe >= 1.0 && room > 0 {
+            buf.set_stringn(x + 1, row, truncate(&caption, room), room, slate);
         }
     }
 }
 
 /// Write OSC 8 hyperlinks directly to terminal for click support
-/// This bypasses ratatui's buffer to inject escape sequences
+/// This bypasses ratatui's buffer to inject escape sequences, so it repeats the widget's styling
 pub struct HyperlinkRenderer {
     buffer: Vec<u8>,
+    /// Background to paint behind the crawl (the lower-third band)
+    band: bool,
+    /// Wrap headlines in OSC 8 links (off where the terminal would print them as garbage)
+    links: bool,
+    /// Draw everything faint (quiet hours)
+    dim: bool,
+    hovered_x: Option<u16>,
 }
 
 impl HyperlinkRenderer {
     pub fn new() -> Self {
-        Self { buffer: Vec::new() }
+        Self {
+            buffer: Vec::new(),
+            band: false,
+            links: true,
+            dim: false,
+            hovered_x: None,
+        }
+    }
+
+    /// Paint the crawl on the lower-third band background
+    pub fn with_band(mut self, band: bool) -> Self {
+        self.band = band;
+        self
+    }
+
+    pub fn with_links(mut self, links: bool) -> Self {
+        self.links = links;
+        self
+    }
+
+    pub fn dimmed(mut self, dim: bool) -> Self {
+        self.dim = dim;
+        self
+    }
+
+    pub fn hovered(mut self, x: Option<u16>) -> Self {
+        self.hovered_x = x;
+        self
     }
 
-    /// Render ticker line with embedded hyperlinks
+    /// Render ticker line with embedded hyperlinks, starting at column col of row
     pub fn render_ticker_line(
         &mut self,
         ticker: &Ticker,
         width: usize,
         row: u16,
+        col: u16,
     ) -> io::Result<()> {
         self.buffer.clear();
+        // Flip display and narrow terminals show a still headline (see TickerWidget), and
+        // the breaking slate covers the crawl
+        if ticker.is_flip() || width < MIN_CRAWL_WIDTH as usize || ticker.takeover().is_some() {
+            return Ok(());
+        }
 
-        let visible_text = ticker.get_visible_text(width);
         let visible_segments = ticker.get_visible_segments(width);
-        let frac = ticker.get_fractional_offset();
-        let all_chars: Vec<char> = visible_text.chars().collect();
-
-        // Apply same fractional offset logic as widget
-        let chars: Vec<char> = (0..width)
-            .map(|i| {
-                let char_idx = if frac > 0.5 { i + 1 } else { i };
-                all_chars.get(char_idx).copied().unwrap_or(' ')
-            })
-            .collect();
+        let chars = crawl_chars(ticker, width);
+        let base = if self.band {
+            Style::default().fg(Color::White).bg(Color::Indexed(LOWER_THIRD_BG))
+        } else {
+            ticker.theme().band
+        };
 
         // Move cursor to position
-        write!(self.buffer, "\x1b[{};1H", row + 1)?;
+        write!(self.buffer, "\x1b[{};{}H", row + 1, col + 1)?;
 
-        let mut pos = 0;
-        while pos < chars.len() && pos < width {
-            // Find if we're starting a segment
-            if let Some(seg) = visible_segments
+        let mut link: Option<&str> = None;
+        let mut current: Option<Style> = None;
+        for (i, &ch) in chars.iter().enumerate() {
+            // Open or close OSC 8 links at segment boundaries
+            let url = visible_segments
                 .iter()
-                .find(|s| s.start == pos && s.url.is_some())
-            {
-                let url = seg.url.as_ref().unwrap();
-                let end = seg.end.min(width);
-                let segment_text: String = chars[pos..end].iter().collect();
-
-                // Write hyperlink with OSC 8
-                write!(self.buffer, "\x1b]8;;{}\x07{}\x1b]8;;\x07", url, segment_text)?;
-                pos = end;
-            } else {
-                // Regular character
-                write!(self.buffer, "{}", chars[pos])?;
-                pos += 1;
+                .find(|s| i >= s.start && i < s.end)
+                .and_then(|s| s.url.as_deref())
+                .filter(|_| self.links);
+            if url != link {
+                if link.is_some() {
+                    write!(self.buffer, "\x1b]8;;\x07")?;
+                }
+                if let Some(url) = url {
+                    write!(self.buffer, "\x1b]8;;{}\x07", url)?;
+                }
+                link = url;
             }
-        }
 
-        // Pad remaining width
-        for _ in pos..width {
-            write!(self.buffer, " ")?;
+            let hovered = self.hovered_x == Some(col + i as u16);
+            let mut style = base.patch(crawl_style(ticker, &visible_segments, i, width, hovered));
+            if self.dim {
+                style = style.add_modifier(Modifier::DIM);
+            }
+            if current != Some(style) {
+                write!(self.buffer, "{}", sgr(style))?;
+                current = Some(style);
+            }
+            write!(self.buffer, "{}", ch)?;
+        }
+        if link.is_some() {
+            write!(self.buffer, "\x1b]8;;\x07")?;
         }
+        write!(self.buffer, "\x1b[0m")?;
 
         Ok(())
     }
@@ -144,21 +564,106 @@ impl HyperlinkRenderer {
     }
 }
 
+/// Cut text to width chars, marking the cut with an ellipsis
+pub fn truncate(text: &str, width: usize) -> String {
+    if text.chars().count() <= width {
+        return text.to_string();
+    }
+    let cut: String = text.chars().take(width.saturating_sub(1)).collect();
+    let mut cut = cut.trim_end().to_string();
+    if width > 0 {
+        cut.push('…');
+    }
+    cut
+}
+
+/// SGR escape selecting exactly this style (resets first)
+fn sgr(style: Style) -> String {
+    let mut codes = vec!["0".to_string()];
+    for (modifier, code) in [
+        (Modifier::BOLD, "1"),
+        (Modifier::DIM, "2"),
+        (Modifier::ITALIC, "3"),
+        (Modifier::UNDERLINED, "4"),
+        (Modifier::REVERSED, "7"),
+    ] {
+        if style.add_modifier.contains(modifier) {
+            codes.push(code.to_string());
+        }
+    }
+    if let Some(fg) = style.fg.and_then(|c| color_code(c, false)) {
+        codes.push(fg);
+    }
+    if let Some(bg) = style.bg.and_then(|c| color_code(c, true)) {
+        codes.push(bg);
+    }
+    format!("\x1b[{}m", codes.join(";"))
+}
+
+/// SGR parameters for a color (None for the terminal default)
+fn color_code(color: Color, background: bool) -> Option<String> {
+    let offset = if background { 10 } else { 0 };
+    let basic = |code: u8| Some((code + offset).to_string());
+    match color {
+        Color::Reset => None,
+        Color::Black => basic(30),
+        Color::Red => basic(31),
+        Color::Green => basic(32),
+        Color::Yellow => basic(33),
+        Color::Blue => basic(34),
+        Color::Magenta => basic(35),
+        Color::Cyan => basic(36),
+        Color::Gray => basic(37),
+        Color::DarkGray => basic(90),
+        Color::LightRed => basic(91),
+        Color::LightGreen => basic(92),
+        Color::LightYellow => basic(93),
+        Color::LightBlue => basic(94),
+        Color::LightMagenta => basic(95),
+        Color::LightCyan => basic(96),
+        Color::White => basic(97),
+        Color::Indexed(i) => Some(format!("{};5;{}", 38 + offset, i)),
+        Color::Rgb(r, g, b) => Some(format!("{};2;{};{};{}", 38 + offset, r, g, b)),
+    }
+}
+
 /// Status bar widget showing ticker state
 pub struct StatusBar<'a> {
     headline_count: usize,
     paused: bool,
     speed: u32,
+    sort: SortMode,
+    style: Style,
     status_msg: Option<&'a str>,
+    failing: usize,
+    offline_since: Option<&'a str>,
+    next_refresh: Option<Duration>,
+    preset: Option<usize>,
+    /// Source and title of the headline at the crawl's left edge
+    now_showing: Option<String>,
 }
 
+/// Characters of the "now showing" field before its title is cut short
+const NOW_SHOWING_WIDTH: usize = 48;
+
 impl<'a> StatusBar<'a> {
     pub fn new(ticker: &Ticker) -> Self {
         Self {
             headline_count: ticker.headline_count(),
             paused: ticker.is_paused(),
             speed: ticker.speed(),
+            sort: ticker.sort(),
+            style: ticker.theme().status,
             status_msg: None,
+            failing: 0,
+            offline_since: None,
+            next_refresh: None,
+            preset: None,
+            // Flip display already holds the leading headline still
+            now_showing: ticker
+                .leading_headline()
+                .filter(|_| !ticker.is_flip())
+                .map(|h| truncate(&ticker.caption(h), NOW_SHOWING_WIDTH)),
         }
     }
 
@@ -166,34 +671,102 @@ impl<'a> StatusBar<'a> {
         self.status_msg = Some(msg);
         self
     }
+
+    /// Number of feeds whose last fetch failed
+    pub fn with_failing(mut self, failing: usize) -> Self {
+        self.failing = failing;
+        self
+    }
+
+    /// When every feed started failing, if they all still are
+    pub fn with_offline_since(mut self, since: Option<&'a str>) -> Self {
+        self.offline_since = since;
+        self
+    }
+
+    /// Time left until the next automatic refresh
+    pub fn with_next_refresh(mut self, remaining: Option<Duration>) -> Self {
+        self.next_refresh = remaining;
+        self
+    }
+
+    /// Number (1-based) of the speed preset matching the current speed
+    pub fn with_preset(mut self, preset: Option<usize>) -> Self {
+        self.preset = preset;
+        self
+    }
+
+    /// Columns of the refresh countdown within the bar, if shown (clicking it refreshes)
+    pub fn refresh_span(&self) -> Option<Range<u16>> {
+        self.line().1
+    }
+
+    /// The status text, and where the countdown sits in it
+    fn line(&self) -> (String, Option<Range<u16>>) {
+        let pause_indicator = if self.paused { "⏸ PAUSED" } else { "▶ PLAYING" };
+        let failing = if let Some(since) = self.offline_since {
+            format!(" | offline since {}", since)
+        } else if self.failing > 0 {
+            format!(" | {} failing", self.failing)
+        } else {
+            String::new()
+        };
+
+        let now_showing = match &self.now_showing {
+            Some(now) => format!(" | now: {}", now),
+            None => String::new(),
+        };
+        let mut status = format!(
+            " {}{} | {} headlines{} | speed: {}",
+            pause_indicator, now_showing, self.headline_count, failing, self.speed
+        );
+        if let Some(preset) = self.preset {
+            status.push_str(&format!(" (preset {})", preset));
+        }
+        status.push_str(&format!(" | sort: {}", self.sort.name()));
+        let span = self.next_refresh.map(|remaining| {
+            status.push_str(" | ");
+            let start = status.chars().count() as u16;
+            status.push_str(&format!("next: {}", countdown(remaining)));
+            start..status.chars().count() as u16
+        });
+        match self.status_msg {
+            Some(msg) => status.push_str(&format!(" | {} ", msg)),
+            None => status.push_str(" | q=quit space=pause ±=speed r=refresh "),
+        }
+        (status, span)
+    }
+}
+
+/// Compact countdown such as "42s", "3m12s", or "1h05m"
+pub fn countdown(remaining: Duration) -> String {
+    let secs = remaining.as_secs();
+    if secs >= 3600 {
+        format!("{}h{:02}m", secs / 3600, secs % 3600 / 60)
+    } else if secs >= 60 {
+        format!("{}m{:02}s", secs / 60, secs % 60)
+    } else {
+        format!("{}s", secs)
+    }
 }
 
 impl Widget for StatusBar<'_> {
     fn render(self, area: Rect, buf: &mut Buffer) {
-        if area.width == 0 {
+        if area.width == 0 || area.height == 0 {
             return;
         }
 
-        let pause_indicator = if self.paused { "⏸ PAUSED" } else { "▶ PLAYING" };
-
-        let status = if let Some(msg) = self.status_msg {
-            format!(
-                " {} | {} headlines | speed: {} | {} ",
-                pause_indicator, self.headline_count, self.speed, msg
-            )
-        } else {
-            format!(
-                " {} | {} headlines | speed: {} | q=quit space=pause ±=speed ",
-                pause_indicator, self.headline_count, self.speed
-            )
-        };
-
-        let style = Style::default().fg(Color::DarkGray);
+        let (status, span) = self.line();
+        let style = self.style;
 
         for (i, ch) in status.chars().enumerate() {
             if i >= area.width as usize {
                 break;
             }
+            let style = match &span {
+                Some(span) if span.contains(&(i as u16)) => style.underlined(),
+                _ => style,
+            };
             buf[(area.x + i as u16, area.y)]
                 .set_char(ch)
                 .set_style(style);
@@ -201,3 +774,142 @@ impl Widget for StatusBar<'_> {
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{test_config, SortMode};
+    use crate::feeds::headline;
+    use crate::ticker::Carried;
+
+    #[test]
+    fn test_truncate() {
+        assert_eq!(truncate("Headline", 20), "Headline");
+        assert_eq!(truncate("Headline", 5), "Head…");
+        assert_eq!(truncate("Headline", 0), "");
+    }
+
+    #[test]
+    fn test_countdown() {
+        assert_eq!(countdown(Duration::from_secs(42)), "42s");
+        assert_eq!(countdown(Duration::from_secs(192)), "3m12s");
+        assert_eq!(countdown(Duration::from_secs(3900)), "1h05m");
+
+        let config = test_config();
+        let ticker = Ticker::with_carried(&config, Carried::default());
+        let bar = StatusBar::new(&ticker).with_next_refresh(Some(Duration::from_secs(192)));
+        let (line, span) = bar.line();
+        let span = span.unwrap();
+        let field: String = line
+            .chars()
+            .skip(span.start as usize)
+            .take(span.len())
+            .collect();
+        assert_eq!(field, "next: 3m12s");
+
+        let (line, _) = StatusBar::new(&ticker).with_preset(Some(5)).line();
+        assert!(line.contains("| speed: 10 (preset 5) | sort: by_date |"));
+    }
+
+    #[test]
+    fn test_offline_status() {
+        let mut ticker = Ticker::with_carried(&test_config(), Carried::default());
+        let (line, _) = StatusBar::new(&ticker).with_failing(3).line();
+        assert!(line.contains("| 3 failing |"));
+        let bar = StatusBar::new(&ticker).with_failing(3).with_offline_since(Some("12:04"));
+        assert!(bar.line().0.contains("| offline since 12:04 |"));
+
+        let title = "Central bank holds rates steady for a third straight meeting";
+        let headline = headline(title, "Wire", None);
+        ticker.set_headlines(vec![headline], SortMode::ByDate);
+        let (line, _) = StatusBar::new(&ticker).line();
+        assert!(line.starts_with(
+            " ▶ PLAYING | now: Wire: Central bank holds rates steady for a thi… | 1 headlines"
+        ));
+
+        ticker.set_headlines(Vec::new(), SortMode::ByDate);
+        ticker.set_banner(Some("OFFLINE".to_string()));
+        assert!(ticker.leading_text().starts_with("OFFLINE"));
+    }
+
+    #[test]
+    fn test_wrap() {
+        assert_eq!(wrap("Short one", 20, 3), vec!["Short one"]);
+        assert_eq!(
+            wrap("Markets rally as rates hold steady", 12, 3),
+            vec!["Markets", "rally as", "rates hold…"]
+        );
+        assert_eq!(wrap("Markets rally", 8, 1), vec!["Markets…"]);
+        assert_eq!(wrap("Supercalifragilistic", 8, 3), vec!["Supercal", "ifragili", "stic"]);
+        assert_eq!(wrap("", 8, 3), vec![""]);
+        assert_eq!(wrap("Anything", 0, 3), vec![""]);
+    }
+
+    #[test]
+    fn test_tiny_areas_render() {
+        let mut config = test_config();
+        config.smooth = true;
+        let mut ticker = Ticker::with_carried(&config, Carried::default());
+        let headlines = (0..3)
+            .map(|i| {
+                let url = format!("https://example.com/{}", i);
+                headline(&format!("A fairly long headline number {}", i), "Test", Some(&url))
+            })
+            .collect();
+        ticker.set_headlines(headlines, SortMode::BySource);
+        ticker.tick(0.37);
+
+        for width in 0..=12u16 {
+            for height in 0..=3u16 {
+                let area = Rect::new(0, 0, width, height);
+                let mut buf = Buffer::empty(area);
+                TickerWidget::new(&ticker).render(area, &mut buf);
+                StatusBar::new(&ticker).render(area, &mut buf);
+                LowerThirdWidget::new(&ticker).render(area, &mut buf);
+                LowerThirdWidget::new(&ticker).logo(Some("📰")).render(area, &mut buf);
+            }
+            let mut renderer = HyperlinkRenderer::new();
+            renderer.render_ticker_line(&ticker, width as usize, 0, 0).unwrap();
+        }
+
+        let area = Rect::new(0, 0, 6, 1);
+        let mut buf = Buffer::empty(area);
+        TickerWidget::new(&ticker).render(area, &mut buf);
+        assert!(buf.content().iter().any(|c| c.symbol() == "…"));
+
+        ticker.break_in(ticker.headlines()[0].clone());
+        for delta in [0.1, 1.0] {
+            ticker.tick(delta);
+            for width in 0..=12u16 {
+                let area = Rect::new(0, 0, width, 2);
+                let mut buf = Buffer::empty(area);
+                TakeoverWidget::new(ticker.takeover().unwrap()).render(area, &mut buf);
+            }
+        }
+    }
+
+    #[test]
+    fn test_crawl_fade_and_padding() {
+        let mut config = test_config();
+        config.theme.fade = vec!['░', '▒', '▓'];
+        config.theme.padding = 3;
+        let mut ticker = Ticker::with_carried(&config, Carried::default());
+        let headline = headline("Markets rally as rates hold steady", "Test", None);
+        ticker.set_headlines(vec![headline], SortMode::BySource);
+
+        let area = Rect::new(0, 0, 20, 1);
+        let mut buf = Buffer::empty(area);
+        TickerWidget::new(&ticker).render(area, &mut buf);
+        let symbols: Vec<&str> = buf.content().iter().map(|c| c.symbol()).collect();
+        assert_eq!(symbols[..3], ["░", "▒", "▓"]);
+        assert_eq!(symbols[17..], ["▓", "▒", "░"]);
+        // Too narrow to give up a quarter of the crawl
+        let area = Rect::new(0, 0, 11, 1);
+        let mut buf = Buffer::empty(area);
+        TickerWidget::new(&ticker).render(area, &mut buf);
+        assert!(buf.content().iter().all(|c| c.symbol() != "░"));
+
+        assert_eq!(crawl_span(ticker.theme(), 80), (3, 74));
+        assert_eq!(crawl_span(ticker.theme(), MIN_CRAWL_WIDTH + 2), (1, MIN_CRAWL_WIDTH));
+        assert_eq!(crawl_span(ticker.theme(), 4), (0, 4));
+    }
+}
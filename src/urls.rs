@@ -0,0 +1,259 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Feeds-file prefix for a command that prints headlines as JSON
+pub const PLUGIN_PREFIX: &str = "plugin:";
+
+/// A feed line from a newsboat-style urls file: `url tag "tag two" "~Display Name"`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeedEntry {
+    pub url: String,
+    /// Display name overriding the feed's own title (newsboat's `~` tag)
+    pub name: Option<String>,
+    pub tags: Vec<String>,
+}
+
+impl FeedEntry {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            name: None,
+            tags: Vec::new(),
+        }
+    }
+
+    /// Parse a line, or None if it isn't an http(s) feed or a plugin (comments, queries,
+    /// blanks)
+    pub fn parse(line: &str) -> Option<Self> {
+        let line = line.trim();
+        if line.starts_with('#') {
+            return None;
+        }
+        let mut tokens = split_quoted(line).into_iter();
+        let url = tokens.next()?;
+        if !["http://", "https://", PLUGIN_PREFIX].iter().any(|p| url.starts_with(p)) {
+            return None;
+        }
+        let mut entry = Self::new(url);
+        for token in tokens {
+            match token.strip_prefix('~') {
+                Some(name) => entry.name = Some(name.to_string()),
+                None => entry.tags.push(token),
+            }
+        }
+        Some(entry)
+    }
+
+    /// Command line of a `plugin:` entry
+    pub fn plugin_command(&self) -> Option<&str> {
+        self.url.strip_prefix(PLUGIN_PREFIX)
+    }
+
+    /// The line as written back to the file
+    pub fn to_line(&self) -> String {
+        let mut line = quote(&self.url);
+        for tag in &self.tags {
+            line.push(' ');
+            line.push_str(&quote(tag));
+        }
+        if let Some(name) = &self.name {
+            line.push(' ');
+            line.push_str(&quote(&format!("~{}", name)));
+        }
+        line
+    }
+}
+
+/// Split on whitespace, keeping double-quoted runs together (quotes removed, and `\"` or
+/// `\\` within them unescaped)
+fn split_quoted(line: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut token = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => quoted = !quoted,
+            '\\' if quoted => token.extend(chars.next()),
+            c if c.is_whitespace() && !quoted => {
+                if !token.is_empty() {
+                    tokens.push(std::mem::take(&mut token));
+                }
+            }
+            c => token.push(c),
+        }
+    }
+    if !token.is_empty() {
+        tokens.push(token);
+    }
+    tokens
+}
+
+fn quote(token: &str) -> String {
+    if token.contains(char::is_whitespace) || token.contains('"') || token.starts_with('~') {
+        format!("\"{}\"", token.replace('\\', "\\\\").replace('"', "\\\""))
+    } else {
+        token.to_string()
+    }
+}
+
+#[derive(Debug, Clone)]
+enum UrlsLine {
+    Feed(FeedEntry),
+    /// Comments, blank lines, and anything else, kept verbatim
+    Other(String),
+}
+
+/// A urls file held line by line, so edits keep its comments and layout
+#[derive(Debug, Clone)]
+pub struct UrlsFile {
+    path: PathBuf,
+    lines: Vec<UrlsLine>,
+}
+
+impl UrlsFile {
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read feeds file: {}", path.display()))?;
+        Ok(Self::parse(path, &content))
+    }
+
+    pub fn parse(path: &Path, content: &str) -> Self {
+        let lines = content
+            .lines()
+            .map(|line| match FeedEntry::parse(line) {
+                Some(entry) => UrlsLine::Feed(entry),
+                None => UrlsLine::Other(line.to_string()),
+            })
+            .collect();
+        Self {
+            path: path.to_path_buf(),
+            lines,
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let mut content: String = self
+            .lines
+            .iter()
+            .map(|line| match line {
+                UrlsLine::Feed(entry) => entry.to_line(),
+                UrlsLine::Other(text) => text.clone(),
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        content.push('\n');
+        fs::write(&self.path, content)
+            .with_context(|| format!("Failed to write feeds file: {}", self.path.display()))
+    }
+
+    /// Feeds in file order
+    pub fn entries(&self) -> Vec<FeedEntry> {
+        self.lines
+            .iter()
+            .filter_map(|line| match line {
+                UrlsLine::Feed(entry) => Some(entry.clone()),
+                UrlsLine::Other(_) => None,
+            })
+            .collect()
+    }
+
+    /// The nth feed, for editing
+    pub fn entry_mut(&mut self, index: usize) -> Option<&mut FeedEntry> {
+        self.lines
+            .iter_mut()
+            .filter_map(|line| match line {
+                UrlsLine::Feed(entry) => Some(entry),
+                UrlsLine::Other(_) => None,
+            })
+            .nth(index)
+    }
+
+    pub fn contains(&self, url: &str) -> bool {
+        self.entries().iter().any(|e| e.url == url)
+    }
+
+    pub fn push(&mut self, entry: FeedEntry) {
+        self.lines.push(UrlsLine::Feed(entry));
+    }
+
+    pub fn remove(&mut self, index: usize) -> Option<FeedEntry> {
+        let line = self.line_of(index)?;
+        match self.lines.remove(line) {
+            UrlsLine::Feed(entry) => Some(entry),
+            UrlsLine::Other(_) => None,
+        }
+    }
+
+    /// Swap the nth feed with the one before it (up) or after it; false at either end
+    pub fn move_entry(&mut self, index: usize, up: bool) -> bool {
+        let other = if up { index.checked_sub(1) } else { Some(index + 1) };
+        match (self.line_of(index), other.and_then(|i| self.line_of(i))) {
+            (Some(a), Some(b)) => {
+                self.lines.swap(a, b);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Line number of the nth feed
+    fn line_of(&self, index: usize) -> Option<usize> {
+        self.lines
+            .iter()
+            .enumerate()
+            .filter(|(_, line)| matches!(line, UrlsLine::Feed(_)))
+            .nth(index)
+            .map(|(i, _)| i)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_entry() {
+        let entry = FeedEntry::parse(r#"https://a.example/rss news "long reads" "~A Daily""#).unwrap();
+        assert_eq!(entry.url, "https://a.example/rss");
+        assert_eq!(entry.tags, vec!["news", "long reads"]);
+        assert_eq!(entry.name.as_deref(), Some("A Daily"));
+        assert_eq!(entry.to_line(), r#"https://a.example/rss news "long reads" "~A Daily""#);
+
+        let plugin = FeedEntry::parse(r#""plugin:~/bin/my-source --json" "~Mine""#).unwrap();
+        assert_eq!(plugin.plugin_command(), Some("~/bin/my-source --json"));
+        assert_eq!(plugin.to_line(), r#""plugin:~/bin/my-source --json" "~Mine""#);
+
+        // Quotes within a tag or name survive a round trip
+        let mut entry = FeedEntry::new("https://b.example/rss");
+        entry.tags.push(r#"say "hi""#.to_string());
+        entry.name = Some(r#"The "B" Times"#.to_string());
+        let line = entry.to_line();
+        assert_eq!(line, r#"https://b.example/rss "say \"hi\"" "~The \"B\" Times""#);
+        assert_eq!(FeedEntry::parse(&line), Some(entry));
+
+        assert!(FeedEntry::parse("# https://commented.example/rss").is_none());
+        assert!(FeedEntry::parse(r#""query:Unread:unread = \"yes\"""#).is_none());
+    }
+
+    #[test]
+    fn test_edits_keep_other_lines() {
+        let content = "# news\nhttps://a.example/rss\n\nhttps://b.example/rss tech\n";
+        let mut file = UrlsFile::parse(Path::new("urls"), content);
+        assert!(file.move_entry(1, true));
+        assert!(!file.move_entry(1, false));
+        file.entry_mut(0).unwrap().name = Some("B".to_string());
+        file.push(FeedEntry::new("https://c.example/rss"));
+        assert_eq!(file.remove(1).unwrap().url, "https://a.example/rss");
+
+        let urls: Vec<_> = file.entries().into_iter().map(|e| e.url).collect();
+        assert_eq!(urls, vec!["https://b.example/rss", "https://c.example/rss"]);
+        assert!(matches!(&file.lines[0], UrlsLine::Other(text) if text == "# news"));
+        assert_eq!(file.entries()[0].to_line(), r#"https://b.example/rss tech "~B""#);
+    }
+}